@@ -1,3 +1,5 @@
 fn main() {
+  #[cfg(feature = "grpc")]
+  tonic_build::compile_protos("proto/pipeline.proto").expect("failed to compile proto/pipeline.proto");
   tauri_build::build()
-}
\ No newline at end of file
+}
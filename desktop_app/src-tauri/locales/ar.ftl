@@ -0,0 +1,29 @@
+# Backend-originated strings, keyed by the codes the Rust side emits
+
+stage-download = التنزيل
+stage-transcribe = التفريغ
+stage-dataset = مجموعة البيانات
+stage-analysis = التحليل
+
+status-started = بدأ
+status-completed = اكتمل
+status-failed = فشل
+
+review-pending = قيد الانتظار
+review-in_review = قيد المراجعة
+review-approved = معتمد
+review-rejected = مرفوض
+
+error-not_found = لم يتم العثور على النص: { $file }
+error-conflict = تم تعديل النص في مكان آخر: { $file }
+error-revision-required = احفظ { $file } مع المراجعة التي فُتح عندها
+error-io-read = تعذرت قراءة النص: { $error }
+error-io-write = تعذرت كتابة النص: { $error }
+error-unknown-review-status = حالة مراجعة غير معروفة: { $status }
+error-unknown-dataset = مجموعة بيانات غير معروفة: { $dataset }
+error-read-only = مساحة العمل للقراءة فقط: { $holder } يستخدم { $workspace }. أغلقه وأعد التشغيل لإجراء التغييرات.
+error-invalid-name = اسم غير صالح: { $name }
+error-job-killed-waiting = أُوقفت المهمة { $job } أثناء انتظار بدئها
+error-create-directory = تعذر إنشاء المجلد: { $error }
+error-execute = تعذر تشغيل { $tool }: { $error }
+error-unknown-sort-key = مفتاح فرز غير معروف: { $key }
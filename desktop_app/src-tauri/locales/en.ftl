@@ -0,0 +1,29 @@
+# Backend-originated strings, keyed by the codes the Rust side emits
+
+stage-download = Download
+stage-transcribe = Transcribe
+stage-dataset = Dataset
+stage-analysis = Analysis
+
+status-started = Started
+status-completed = Completed
+status-failed = Failed
+
+review-pending = Pending
+review-in_review = In review
+review-approved = Approved
+review-rejected = Rejected
+
+error-not_found = Transcript not found: { $file }
+error-conflict = Transcript was modified elsewhere: { $file }
+error-revision-required = Save { $file } with the revision it was opened at
+error-io-read = Failed to read transcript: { $error }
+error-io-write = Failed to write transcript: { $error }
+error-unknown-review-status = Unknown review status: { $status }
+error-unknown-dataset = Unknown dataset: { $dataset }
+error-read-only = Workspace is read-only: { $holder } is using { $workspace }. Close it and restart to make changes.
+error-invalid-name = Invalid name: { $name }
+error-job-killed-waiting = Job { $job } was killed while waiting to start
+error-create-directory = Failed to create directory: { $error }
+error-execute = Failed to execute { $tool }: { $error }
+error-unknown-sort-key = Unknown sort key: { $key }
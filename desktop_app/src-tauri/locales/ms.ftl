@@ -0,0 +1,29 @@
+# Backend-originated strings, keyed by the codes the Rust side emits
+
+stage-download = Muat turun
+stage-transcribe = Transkripsi
+stage-dataset = Set data
+stage-analysis = Analisis
+
+status-started = Dimulakan
+status-completed = Selesai
+status-failed = Gagal
+
+review-pending = Menunggu
+review-in_review = Dalam semakan
+review-approved = Diluluskan
+review-rejected = Ditolak
+
+error-not_found = Transkrip tidak dijumpai: { $file }
+error-conflict = Transkrip telah diubah di tempat lain: { $file }
+error-revision-required = Simpan { $file } bersama semakan semasa ia dibuka
+error-io-read = Gagal membaca transkrip: { $error }
+error-io-write = Gagal menulis transkrip: { $error }
+error-unknown-review-status = Status semakan tidak diketahui: { $status }
+error-unknown-dataset = Set data tidak diketahui: { $dataset }
+error-read-only = Ruang kerja baca sahaja: { $holder } sedang menggunakan { $workspace }. Tutupnya dan mulakan semula untuk membuat perubahan.
+error-invalid-name = Nama tidak sah: { $name }
+error-job-killed-waiting = Tugas { $job } dihentikan semasa menunggu untuk bermula
+error-create-directory = Gagal mencipta direktori: { $error }
+error-execute = Gagal menjalankan { $tool }: { $error }
+error-unknown-sort-key = Kunci isihan tidak diketahui: { $key }
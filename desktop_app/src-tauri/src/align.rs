@@ -0,0 +1,77 @@
+use tauri::AppHandle;
+
+use crate::clips;
+use crate::events::{self, Stage, StageStatus};
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::scheduler::{self, Resource};
+use crate::text;
+use crate::transcript;
+use crate::storage::storage;
+
+// Command to regenerate segment and word timings for a transcript from its audio
+#[tauri::command]
+pub fn align_transcript(
+    app: AppHandle,
+    file_name: String,
+    language: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let transcript_path = transcript::transcript_path(&file_name);
+    if !storage().exists(&transcript_path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let clip = clips::media_file(metadata::stem(&file_name), "mp3");
+    if !storage().exists(&clip) {
+        return Err(format!("Clip not found for transcript: {}", file_name));
+    }
+
+    let language = match language {
+        Some(language) => language,
+        None => {
+            let content = storage().read_to_string(&transcript_path)
+                .map_err(|e| format!("Failed to read transcript: {}", e))?;
+            text::detect_language(&content).to_string()
+        }
+    };
+
+    events::stage_change(&app, Stage::Analysis, StageStatus::Started);
+
+    // Write to a temporary file so a failed run keeps the previous timings;
+    // the script runs inside the pipeline directory, so paths are relative to it
+    let stem = metadata::stem(&file_name);
+    let temp = storage().path(format!("output/transcripts/{}.aligning.json", stem));
+    let permit = scheduler::acquire(Resource::Gpu)?;
+    let output = Invocation::new("python3")
+        .args([
+            "scripts/align_transcript.py",
+            &clips::media_arg(stem, "mp3"),
+            &format!("output/transcripts/{}", file_name),
+            &language,
+            &format!("output/transcripts/{}.aligning.json", stem),
+        ])
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute alignment: {}", e))?;
+    drop(permit);
+
+    if !output.success {
+        storage().remove_file(&temp).ok();
+        let stderr = output.stderr;
+        events::error(&app, Stage::Analysis, &stderr, Some(&file_name));
+        events::stage_change(&app, Stage::Analysis, StageStatus::Failed);
+        return Err(stderr);
+    }
+
+    storage().rename(&temp, transcript::segments_path(&file_name))
+        .map_err(|e| format!("Failed to write segments: {}", e))?;
+    events::stage_change(&app, Stage::Analysis, StageStatus::Completed);
+
+    let segments = transcript::load_segments(&file_name).unwrap_or_default();
+    Ok(serde_json::json!({
+        "file_name": file_name,
+        "language": language,
+        "segments": segments.len(),
+        "words": segments.iter().map(|s| s.words.len()).sum::<usize>()
+    }))
+}
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::questions;
+use crate::sharing::escape;
+use crate::storage::storage;
+
+#[derive(Debug, Serialize)]
+struct Card {
+    front: String,
+    back: String,
+    tags: Vec<String>,
+}
+
+// Transcripts with stored question/answer pairs
+fn transcripts_with_questions() -> Vec<String> {
+    let mut files: Vec<String> = storage()
+        .read_dir(questions::questions_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().to_str().and_then(|n| n.strip_suffix(".json")).map(|s| format!("{}.txt", s)))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// Anki tags cannot contain spaces
+fn tag(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+fn timestamp(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+fn cards(files: &[String], reviewed_only: bool) -> Vec<Card> {
+    let mut cards = Vec::new();
+    for file in files {
+        let Some(pairs) = questions::load(file) else { continue };
+        let meta = metadata::load(file).unwrap_or_default();
+        let title = meta.title.clone().unwrap_or_else(|| metadata::stem(file).to_string());
+
+        let mut tags = vec![tag(metadata::stem(file))];
+        if let Some(speaker) = meta.channel_name() {
+            tags.push(tag(speaker));
+        }
+
+        for pair in pairs.iter().filter(|p| p.reviewed || !reviewed_only) {
+            let back = format!(
+                "{}<br><br><small>{} · {}</small>",
+                escape(pair.answer.trim()).replace('\n', "<br>"),
+                escape(&title),
+                timestamp(pair.start)
+            );
+            cards.push(Card { front: escape(pair.question.trim()).replace('\n', "<br>"), back, tags: tags.clone() });
+        }
+    }
+    cards
+}
+
+// Anki's plain-text import format: tab separated, with header lines naming deck and columns
+fn tab_separated(deck: &str, cards: &[Card]) -> String {
+    let mut content = format!("#separator:tab\n#html:true\n#notetype:Basic\n#deck:{}\n#tags column:3\n", deck);
+    for card in cards {
+        let clean = |field: &str| field.replace(['\t', '\r', '\n'], " ");
+        content.push_str(&format!("{}\t{}\t{}\n", clean(&card.front), clean(&card.back), card.tags.join(" ")));
+    }
+    content
+}
+
+// Command to turn extracted question/answer pairs into an Anki deck, as a tab-separated text
+// import ("txt", the default) or a package built by scripts/make_apkg.py ("apkg")
+#[tauri::command]
+pub fn export_anki(
+    files: Option<Vec<String>>,
+    deck: Option<String>,
+    format: Option<String>,
+    reviewed_only: Option<bool>,
+    path: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let files = files.unwrap_or_else(transcripts_with_questions);
+    let deck = deck.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).unwrap_or_else(|| "Lecture Q&A".to_string());
+    let format = format.unwrap_or_else(|| "txt".to_string());
+    if format != "txt" && format != "apkg" {
+        return Err(format!("Unsupported Anki format: {}", format));
+    }
+
+    let cards = cards(&files, reviewed_only.unwrap_or(false));
+    if cards.is_empty() {
+        return Err("No question/answer pairs to export; run extract_questions first".to_string());
+    }
+
+    // The target is picked by the user, so it is granted for this export only
+    let _grant = path.as_ref().map(|p| storage().allow(p)).transpose().map_err(|e| format!("Failed to access target: {}", e))?;
+    let target = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let name: String = deck.chars().map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' }).collect();
+            storage().path("output/anki").join(format!("{}.{}", name, format))
+        }
+    };
+    if let Some(parent) = target.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    if format == "txt" {
+        storage().write(&target, tab_separated(&deck, &cards)).map_err(|e| format!("Failed to write deck: {}", e))?;
+    } else {
+        let input = serde_json::to_vec(&cards).map_err(|e| format!("Failed to serialize cards: {}", e))?;
+        let output = Invocation::new("python3")
+            .arg("scripts/make_apkg.py")
+            .arg(&deck)
+            .arg(&target)
+            .stdin(input)
+            .in_workspace()
+            .run()
+            .map_err(|e| format!("Failed to execute make_apkg.py: {}", e))?;
+        if !output.success {
+            return Err(format!("Failed to build Anki package: {}", output.stderr.trim()));
+        }
+    }
+
+    Ok(serde_json::json!({ "path": target.to_string_lossy(), "cards": cards.len() }))
+}
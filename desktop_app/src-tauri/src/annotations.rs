@@ -0,0 +1,269 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::metadata;
+use crate::state;
+use crate::transcript;
+use crate::workspace;
+use crate::storage::storage;
+
+// Characters of context kept on each side of an annotated range to find it again after edits
+const CONTEXT_CHARS: usize = 32;
+
+// A reviewer comment anchored to a character range of a transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: u64,
+    pub start: usize,
+    pub end: usize,
+    // The annotated text and the text around it when the annotation was made; the range is
+    // moved to wherever they are now each time annotations are loaded
+    #[serde(default)]
+    pub quote: String,
+    #[serde(default)]
+    pub before: String,
+    #[serde(default)]
+    pub after: String,
+    // The annotated text is no longer in the transcript; the range is where it was last seen
+    #[serde(default)]
+    pub detached: bool,
+    pub author: String,
+    pub text: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: Option<u64>,
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+// Sidecar file holding the annotations of a transcript
+fn sidecar_path(file_name: &str) -> PathBuf {
+    storage()
+        .path("output/annotations")
+        .join(format!("{}.json", metadata::stem(file_name)))
+}
+
+fn slice(text: &[char], range: std::ops::Range<usize>) -> String {
+    text[range.start.min(text.len())..range.end.min(text.len())].iter().collect()
+}
+
+fn positions(text: &[char], needle: &[char]) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > text.len() {
+        return Vec::new();
+    }
+    text.windows(needle.len()).enumerate().filter(|(_, w)| *w == needle).map(|(i, _)| i).collect()
+}
+
+// Move the range to the occurrence of the quoted text whose surroundings match best,
+// nearest the old position on a tie
+fn anchor(annotation: &mut Annotation, text: &[char]) {
+    let quote: Vec<char> = annotation.quote.chars().collect();
+    let before: Vec<char> = annotation.before.chars().collect();
+    let after: Vec<char> = annotation.after.chars().collect();
+    if quote.is_empty() && before.is_empty() && after.is_empty() {
+        // Made before quotes were stored
+        return;
+    }
+    if slice(text, annotation.start..annotation.end) == annotation.quote
+        && slice(text, annotation.start.saturating_sub(before.len())..annotation.start) == annotation.before
+    {
+        annotation.detached = false;
+        return;
+    }
+
+    // An empty range is found by the text on both sides of it
+    let (needle, offset) = if quote.is_empty() { ([&before[..], &after[..]].concat(), before.len()) } else { (quote.clone(), 0) };
+    let score = |at: usize| {
+        let start = at + offset;
+        let end = start + quote.len();
+        let left = text[..start].iter().rev().zip(before.iter().rev()).take_while(|(a, b)| a == b).count();
+        let right = text[end..].iter().zip(&after).take_while(|(a, b)| a == b).count();
+        (left + right, std::cmp::Reverse(start.abs_diff(annotation.start)))
+    };
+    match positions(text, &needle).into_iter().max_by_key(|at| score(*at)) {
+        Some(at) => {
+            annotation.start = at + offset;
+            annotation.end = annotation.start + quote.len();
+            annotation.detached = false;
+        }
+        None => annotation.detached = true,
+    }
+}
+
+// Annotations of a transcript, re-anchored to its current text
+pub fn load(file_name: &str) -> Vec<Annotation> {
+    let mut annotations: Vec<Annotation> = storage().read_to_string(sidecar_path(file_name))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    if let Ok(content) = storage().read_to_string(transcript::transcript_path(file_name)) {
+        let text: Vec<char> = content.chars().collect();
+        annotations.iter_mut().for_each(|a| anchor(a, &text));
+    }
+    annotations
+}
+
+fn save(file_name: &str, annotations: &[Annotation]) -> Result<(), String> {
+    workspace::ensure_writable()?;
+    let path = sidecar_path(file_name);
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(annotations)
+        .map_err(|e| format!("Failed to serialize annotations: {}", e))?;
+
+    storage().write(path, content).map_err(|e| format!("Failed to write annotations: {}", e))
+}
+
+// Command to list the annotations of a transcript
+#[tauri::command]
+pub fn get_annotations(file_name: String) -> Result<Vec<Annotation>, String> {
+    Ok(load(&file_name))
+}
+
+// Command to add a comment anchored to a range of a transcript
+#[tauri::command]
+pub fn add_annotation(
+    file_name: String,
+    start: usize,
+    end: usize,
+    author: String,
+    text: String,
+) -> Result<Annotation, String> {
+    let path = transcript::transcript_path(&file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let transcript: Vec<char> = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?
+        .chars()
+        .collect();
+    if start > end || end > transcript.len() {
+        return Err(format!("Invalid annotation range: {}..{}", start, end));
+    }
+
+    let mut annotations = load(&file_name);
+    let annotation = Annotation {
+        id: annotations.iter().map(|a| a.id).max().unwrap_or(0) + 1,
+        start,
+        end,
+        quote: slice(&transcript, start..end),
+        before: slice(&transcript, start.saturating_sub(CONTEXT_CHARS)..start),
+        after: slice(&transcript, end..end + CONTEXT_CHARS),
+        detached: false,
+        author,
+        text,
+        created_at: state::now(),
+        updated_at: None,
+        resolved: false,
+    };
+    annotations.push(annotation.clone());
+    save(&file_name, &annotations)?;
+    audit::record("annotation.add", &file_name, serde_json::json!({ "id": annotation.id }));
+
+    Ok(annotation)
+}
+
+// Command to edit the text or resolution of an annotation
+#[tauri::command]
+pub fn update_annotation(
+    file_name: String,
+    id: u64,
+    text: Option<String>,
+    resolved: Option<bool>,
+) -> Result<Annotation, String> {
+    let mut annotations = load(&file_name);
+    let annotation = annotations
+        .iter_mut()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("Annotation not found: {}", id))?;
+
+    if let Some(text) = text {
+        annotation.text = text;
+    }
+    if let Some(resolved) = resolved {
+        annotation.resolved = resolved;
+    }
+    annotation.updated_at = Some(state::now());
+
+    let updated = annotation.clone();
+    save(&file_name, &annotations)?;
+    audit::record("annotation.update", &file_name, serde_json::json!({ "id": id }));
+    Ok(updated)
+}
+
+// Command to remove an annotation
+#[tauri::command]
+pub fn delete_annotation(file_name: String, id: u64) -> Result<(), String> {
+    let mut annotations = load(&file_name);
+    let before = annotations.len();
+    annotations.retain(|a| a.id != id);
+
+    if annotations.len() == before {
+        return Err(format!("Annotation not found: {}", id));
+    }
+
+    save(&file_name, &annotations)?;
+    audit::record("annotation.delete", &file_name, serde_json::json!({ "id": id }));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(text: &str, start: usize, end: usize) -> Annotation {
+        let text: Vec<char> = text.chars().collect();
+        Annotation {
+            id: 1,
+            start,
+            end,
+            quote: slice(&text, start..end),
+            before: slice(&text, start.saturating_sub(CONTEXT_CHARS)..start),
+            after: slice(&text, end..end + CONTEXT_CHARS),
+            detached: false,
+            author: "host".to_string(),
+            text: "check this".to_string(),
+            created_at: 0,
+            updated_at: None,
+            resolved: false,
+        }
+    }
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn follows_text_inserted_before_the_range() {
+        let mut a = annotation("be patient and grateful", 3, 10);
+        anchor(&mut a, &chars("Always be patient and grateful"));
+        assert_eq!((a.start, a.end), (10, 17));
+        assert!(!a.detached);
+    }
+
+    #[test]
+    fn picks_the_occurrence_with_matching_context() {
+        let original = "patience first. then gratitude and patience again";
+        let mut a = annotation(original, 35, 43);
+        assert_eq!(a.quote, "patience");
+
+        let edited = format!("Note: {}", original);
+        anchor(&mut a, &chars(&edited));
+        assert_eq!((a.start, a.end), (41, 49));
+    }
+
+    #[test]
+    fn is_detached_when_the_quote_was_edited_away() {
+        let mut a = annotation("be patient and grateful", 3, 10);
+        anchor(&mut a, &chars("be thankful and grateful"));
+        assert!(a.detached);
+        assert_eq!((a.start, a.end), (3, 10));
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::storage::storage;
+
+// Diarization labels such as "SPEAKER_00:" at the start of a line
+static LABELS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\s*\[?(SPEAKER_\d+)\]?:").unwrap());
+// Names introduced by an honorific or form of address
+static TITLED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(Shaykh|Sheikh|Shaikh|Imam|Ustadh|Ustadha|Mufti|Brother|Sister)\s+([A-Z][a-z]+(?:\s+[A-Z][a-z]+)?)").unwrap()
+});
+
+// Original name -> placeholder, per source transcript
+pub type AnonymizationMap = BTreeMap<String, BTreeMap<String, String>>;
+
+// Path of the stored mapping, kept so anonymized entries can be traced back
+pub fn map_path() -> PathBuf {
+    storage().path("output/json/anonymization_map.json")
+}
+
+pub fn load_map() -> AnonymizationMap {
+    storage().read_to_string(map_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_map(map: &AnonymizationMap) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(map)
+        .map_err(|e| format!("Failed to serialize anonymization map: {}", e))?;
+
+    storage().write(map_path(), content)
+        .map_err(|e| format!("Failed to write anonymization map: {}", e))
+}
+
+// Detect speaker names in a transcript and pick a placeholder for each
+pub fn detect_speakers(text: &str) -> BTreeMap<String, String> {
+    let mut mapping = BTreeMap::new();
+
+    // The first labelled speaker is assumed to be the lecturer
+    let mut questioners = 0;
+    for capture in LABELS.captures_iter(text) {
+        let label = capture[1].to_string();
+        if mapping.contains_key(&label) {
+            continue;
+        }
+        let placeholder = if mapping.is_empty() {
+            "Shaykh".to_string()
+        } else {
+            questioners += 1;
+            format!("Questioner {}", questioners)
+        };
+        mapping.insert(label, placeholder);
+    }
+
+    for capture in TITLED.captures_iter(text) {
+        let name = capture[0].to_string();
+        let placeholder = match &capture[1] {
+            "Brother" | "Sister" => "Questioner",
+            _ => "Shaykh",
+        };
+        mapping.entry(name).or_insert_with(|| placeholder.to_string());
+    }
+
+    mapping
+}
+
+// Replace every mapped name in a text, longest names first
+pub fn apply(text: &str, mapping: &BTreeMap<String, String>) -> String {
+    let mut names: Vec<&String> = mapping.keys().collect();
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+
+    names.into_iter().fold(text.to_string(), |acc, name| {
+        acc.replace(name.as_str(), &mapping[name])
+    })
+}
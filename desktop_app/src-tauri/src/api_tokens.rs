@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::audit;
+use crate::review_server::{Role, HOST_USER};
+use crate::state;
+
+// An API token for server mode; only its hash is stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    // Who or what the token was issued to, used for attribution
+    pub name: String,
+    pub role: Role,
+    // SHA-256 of the token, hex encoded
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+    pub created_at: u64,
+}
+
+fn hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// 32 bytes from the OS random number generator, hex encoded
+fn generate() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+// Name and role of the holder of `token`, if it is a live API token; none while the
+// state store is unreadable
+pub fn authenticate(token: &str) -> Option<(String, Role)> {
+    let hashed = hash(token);
+    state::load()
+        .ok()?
+        .api_tokens
+        .into_iter()
+        .find(|t| t.hash == hashed)
+        .map(|t| (t.name, t.role))
+}
+
+// True once at least one API token exists, or when that cannot be told
+pub fn any() -> bool {
+    !state::load().is_ok_and(|s| s.api_tokens.is_empty())
+}
+
+// Command to issue an API token; the token itself is returned only this once
+#[tauri::command]
+pub fn create_api_token(name: String, role: Role) -> Result<serde_json::Value, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name == HOST_USER {
+        return Err(format!("Invalid token name: {:?}", name));
+    }
+
+    let token = generate()?;
+    let record = ApiToken {
+        id: token[..12].to_string(),
+        name: name.clone(),
+        role,
+        hash: hash(&token),
+        created_at: state::now(),
+    };
+    let id = record.id.clone();
+    state::update(|s| s.api_tokens.push(record))?;
+
+    audit::record("api_token.create", &name, serde_json::json!({ "id": id, "role": role }));
+    Ok(serde_json::json!({ "id": id, "name": name, "role": role, "token": token }))
+}
+
+// Command to revoke an API token by id
+#[tauri::command]
+pub fn revoke_api_token(id: String) -> Result<(), String> {
+    let revoked = state::update(|s| {
+        let before = s.api_tokens.len();
+        s.api_tokens.retain(|t| t.id != id);
+        s.api_tokens.len() < before
+    })?;
+    if !revoked {
+        return Err(format!("API token not found: {}", id));
+    }
+
+    audit::record("api_token.revoke", &id, serde_json::json!({}));
+    Ok(())
+}
+
+// Command to list issued API tokens without their hashes
+#[tauri::command]
+pub fn list_api_tokens() -> Result<Vec<ApiToken>, String> {
+    Ok(state::load()?
+        .api_tokens
+        .into_iter()
+        .map(|t| ApiToken { hash: String::new(), ..t })
+        .collect())
+}
@@ -0,0 +1,124 @@
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::review_server::HOST_USER;
+use crate::state;
+use crate::storage::storage;
+
+// Serializes appends so concurrent writers never interleave lines
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    // User the current thread acts for; unset means the desktop app itself
+    static ACTOR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// One mutating operation: who did what to which target, and when
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub at: u64,
+    pub user: String,
+    // Dotted action name, e.g. "transcript.edit" or "settings.update"
+    pub action: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+}
+
+// Append-only log, one JSON entry per line
+fn audit_path() -> PathBuf {
+    storage().path("output/audit.jsonl")
+}
+
+// Run `f` with every audit entry it records attributed to `user`
+pub fn as_user<T>(user: &str, f: impl FnOnce() -> T) -> T {
+    let previous = ACTOR.with(|a| a.replace(Some(user.to_string())));
+    let result = f();
+    ACTOR.with(|a| *a.borrow_mut() = previous);
+    result
+}
+
+// User the current thread acts for
+pub fn current_user() -> String {
+    ACTOR.with(|a| a.borrow().clone()).unwrap_or_else(|| HOST_USER.to_string())
+}
+
+// Append an entry for a completed mutating operation; failures never undo the operation
+pub fn record(action: &str, target: &str, details: serde_json::Value) {
+    let entry = AuditEntry {
+        at: state::now(),
+        user: current_user(),
+        action: action.to_string(),
+        target: target.to_string(),
+        details,
+    };
+    let Ok(line) = serde_json::to_string(&entry) else { return };
+
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = audit_path();
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent).ok();
+    }
+    if let Ok(mut file) = storage().append(&path) {
+        writeln!(file, "{}", line).ok();
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AuditFilter {
+    pub user: Option<String>,
+    // Exact action or a prefix such as "transcript."
+    pub action: Option<String>,
+    // Substring of the target, case-insensitive
+    pub target: Option<String>,
+    // Unix seconds, inclusive
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+}
+
+impl AuditFilter {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        let target = self.target.as_ref().map(|t| t.to_lowercase());
+        self.user.as_ref().is_none_or(|u| &entry.user == u)
+            && self.action.as_ref().is_none_or(|a| entry.action.starts_with(a.as_str()))
+            && target.is_none_or(|t| entry.target.to_lowercase().contains(&t))
+            && self.since.is_none_or(|since| entry.at >= since)
+            && self.until.is_none_or(|until| entry.at <= until)
+    }
+}
+
+// Command to browse the audit log, newest first
+#[tauri::command]
+pub fn get_audit_log(
+    filter: Option<AuditFilter>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    let filter = filter.unwrap_or_default();
+    let page = page.unwrap_or(0);
+    let page_size = page_size.unwrap_or(100).max(1);
+
+    let entries: Vec<AuditEntry> = match storage().open(audit_path()) {
+        Ok(file) => BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str(&line).ok())
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(format!("Failed to read audit log: {}", e)),
+    };
+    let matching: Vec<&AuditEntry> = entries.iter().rev().filter(|e| filter.matches(e)).collect();
+    let items: Vec<&AuditEntry> = matching.iter().skip(page * page_size).take(page_size).copied().collect();
+
+    Ok(serde_json::json!({
+        "items": items,
+        "total": matching.len(),
+        "page": page,
+        "page_size": page_size
+    }))
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::dataset;
+use crate::events::{self, LogLevel, PipelineEvent, Stage};
+use crate::settings;
+use crate::state;
+use crate::transcript;
+
+// How often the background thread looks for newly approved transcripts
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// Automatic dataset regeneration when approved transcripts change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoRebuildSettings {
+    pub enabled: bool,
+    // Dataset to regenerate; the default dataset when unset
+    pub dataset: Option<String>,
+    // Wait this long after the last approval before rebuilding
+    pub debounce_minutes: u64,
+    // Rebuild only between these hours (UTC, 0-23); the window may wrap past midnight
+    pub off_peak_start_hour: u64,
+    pub off_peak_end_hour: u64,
+}
+
+impl Default for AutoRebuildSettings {
+    fn default() -> Self {
+        AutoRebuildSettings {
+            enabled: false,
+            dataset: None,
+            debounce_minutes: 30,
+            off_peak_start_hour: 1,
+            off_peak_end_hour: 5,
+        }
+    }
+}
+
+impl AutoRebuildSettings {
+    fn in_off_peak(&self, now: u64) -> bool {
+        let hour = now / 3600 % 24;
+        let (start, end) = (self.off_peak_start_hour % 24, self.off_peak_end_hour % 24);
+        if start <= end {
+            hour >= start && hour < end
+        } else {
+            hour >= start || hour < end
+        }
+    }
+}
+
+// Approved transcripts the last automatic rebuild was made from, stored in the state store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoRebuildState {
+    // Transcript file name -> "size:modified" at build time
+    pub approved: BTreeMap<String, String>,
+    pub last_run: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+// Change in approved transcripts and dataset size caused by one rebuild
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RebuildDelta {
+    pub added: usize,
+    pub changed: usize,
+    pub removed: usize,
+    pub previous_entries: usize,
+    pub entries: usize,
+}
+
+// Approved transcripts seen changing, and when they were first seen that way
+static PENDING: Mutex<Option<(BTreeMap<String, String>, u64)>> = Mutex::new(None);
+
+fn approved_transcripts(state: &state::PipelineState) -> BTreeMap<String, String> {
+    transcript::stat_transcripts()
+        .into_iter()
+        .filter(|file| transcript::review_status(state, &file.file_name) == "approved")
+        .map(|file| (file.file_name, format!("{}:{}", file.size, file.modified)))
+        .collect()
+}
+
+fn delta(built: &BTreeMap<String, String>, current: &BTreeMap<String, String>) -> RebuildDelta {
+    RebuildDelta {
+        added: current.keys().filter(|k| !built.contains_key(*k)).count(),
+        changed: current.iter().filter(|(k, v)| built.get(*k).is_some_and(|b| b != *v)).count(),
+        removed: built.keys().filter(|k| !current.contains_key(*k)).count(),
+        ..Default::default()
+    }
+}
+
+fn entry_count(dataset: Option<&str>) -> usize {
+    dataset::dataset_path(dataset)
+        .ok()
+        .and_then(|path| dataset::entry_count(&path).ok())
+        .unwrap_or(0)
+}
+
+// Rebuild when approvals have settled for the debounce period inside the off-peak window
+fn tick(app: &AppHandle) {
+    let config = settings::load().auto_rebuild;
+    if !config.enabled {
+        return;
+    }
+
+    // An unreadable state store is reported by the commands that use it
+    let Ok(state) = state::load() else { return };
+    let current = approved_transcripts(&state);
+    let built = state.auto_rebuild.approved;
+    let now = state::now();
+
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if current == built {
+        *pending = None;
+        return;
+    }
+    match pending.as_ref() {
+        Some((seen, _)) if *seen == current => {}
+        _ => {
+            *pending = Some((current, now));
+            return;
+        }
+    }
+
+    let since = pending.as_ref().map_or(now, |(_, at)| *at);
+    if now.saturating_sub(since) < config.debounce_minutes * 60 || !config.in_off_peak(now) {
+        return;
+    }
+    *pending = None;
+    drop(pending);
+
+    let mut delta = delta(&built, &current);
+    delta.previous_entries = entry_count(config.dataset.as_deref());
+    let result = crate::generate_json(app.clone(), config.dataset.clone(), None);
+    delta.entries = entry_count(config.dataset.as_deref());
+
+    let error = result.err();
+    state::update(|s| {
+        s.auto_rebuild.last_run = Some(now);
+        s.auto_rebuild.last_error = error.clone();
+        if error.is_none() {
+            s.auto_rebuild.approved = current;
+        }
+    })
+    .ok();
+
+    match error {
+        None => events::emit(
+            app,
+            PipelineEvent::DatasetRebuilt {
+                dataset: config.dataset.unwrap_or_else(|| dataset::DEFAULT_DATASET.to_string()),
+                delta,
+            },
+        ),
+        Some(e) => events::log(app, Stage::Dataset, LogLevel::Warn, &format!("Automatic rebuild failed: {}", e)),
+    }
+}
+
+// Start the background thread that watches for approved transcripts
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+        tick(&app);
+    });
+}
+
+// Command to show when the dataset was last rebuilt automatically and what is waiting
+#[tauri::command]
+pub fn get_auto_rebuild_status() -> Result<serde_json::Value, String> {
+    let config = settings::load().auto_rebuild;
+    let state = state::load()?;
+    let waiting = delta(&state.auto_rebuild.approved, &approved_transcripts(&state));
+    let built = state.auto_rebuild;
+    let pending_since = PENDING.lock().unwrap_or_else(|e| e.into_inner()).as_ref().map(|(_, at)| *at);
+
+    Ok(serde_json::json!({
+        "enabled": config.enabled,
+        "last_run": built.last_run,
+        "last_error": built.last_error,
+        "waiting": waiting,
+        "pending_since": pending_since
+    }))
+}
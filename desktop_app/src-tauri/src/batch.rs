@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+use crate::groundtruth;
+use crate::journal;
+use crate::metadata;
+use crate::pipeline;
+use crate::settings;
+use crate::state;
+use crate::storage::storage;
+use crate::tags;
+use crate::transcript;
+
+// Outcome of a batch action on one item
+#[derive(Debug, Serialize)]
+pub struct ItemResult {
+    pub id: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+fn run_action(action: &str, id: &str, tags: &[String]) -> Result<(), String> {
+    let exists = storage().exists(transcript::transcript_path(id));
+
+    match action {
+        "delete" => {
+            let files = transcript::output_files(id);
+            if files.is_empty() {
+                return Err(format!("Transcript not found: {}", id));
+            }
+            let mut journal = journal::begin("delete", &format!("Delete transcript {}", id))?;
+            for file in files {
+                journal.remove(&file)?;
+            }
+            journal.commit()
+        }
+        "retranscribe" => {
+            let clip = format!("{}.mp3", metadata::stem(id));
+            let output = pipeline::transcribe_clip(&clip)?;
+            if output.success {
+                groundtruth::score(&clip, None, &settings::load().whisper_model)?;
+                Ok(())
+            } else {
+                Err(output.stderr)
+            }
+        }
+        "approve" | "tag" | "exclude" | "include" if !exists => Err(format!("Transcript not found: {}", id)),
+        "approve" => state::update(|state| {
+            state.review_status.insert(id.to_string(), "approved".to_string());
+        }),
+        "tag" => state::update(|state| {
+            let entry = state.transcript_tags.entry(id.to_string()).or_default();
+            entry.extend(tags.iter().cloned());
+            *entry = tags::normalize(std::mem::take(entry));
+        }),
+        "exclude" => state::update(|state| {
+            state.excluded_from_dataset.insert(id.to_string());
+        }),
+        "include" => state::update(|state| {
+            state.excluded_from_dataset.remove(id);
+        }),
+        _ => Err(format!("Unknown batch action: {}", action)),
+    }
+}
+
+// Command to run delete/retranscribe/approve/tag/exclude/include over many transcripts
+#[tauri::command]
+pub fn batch_action(
+    action: String,
+    item_ids: Vec<String>,
+    tags: Option<Vec<String>>,
+) -> Result<Vec<ItemResult>, String> {
+    let tags = tags.unwrap_or_default();
+    if action == "tag" && tags.is_empty() {
+        return Err("The tag action needs at least one tag".to_string());
+    }
+
+    Ok(item_ids
+        .into_iter()
+        .map(|id| match run_action(&action, &id, &tags) {
+            Ok(()) => ItemResult { id, ok: true, error: None },
+            Err(error) => ItemResult { id, ok: false, error: Some(error) },
+        })
+        .collect())
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::dataset::DatasetEntry;
+
+// Phrases stripped from dataset entries during generation, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoilerplateSettings {
+    pub enabled: bool,
+    // Channel intros, outros and calls to action; matched case-insensitively as whole words
+    pub phrases: Vec<String>,
+    // Also strip the customary opening supplications listed in `openings`
+    pub strip_openings: bool,
+    pub openings: Vec<String>,
+}
+
+impl Default for BoilerplateSettings {
+    fn default() -> Self {
+        let list = |phrases: &[&str]| phrases.iter().map(|p| p.to_string()).collect();
+        BoilerplateSettings {
+            enabled: true,
+            phrases: list(&[
+                "like and subscribe",
+                "don't forget to subscribe",
+                "please subscribe to the channel",
+                "hit the bell icon",
+                "welcome back to the channel",
+                "link in the description",
+                "thank you for watching",
+            ]),
+            strip_openings: false,
+            openings: list(&[
+                "bismillah ir-rahman ir-raheem",
+                "bismillahir rahmanir raheem",
+                "as-salamu alaykum wa rahmatullahi wa barakatuh",
+                "assalamu alaikum wa rahmatullahi wa barakatuh",
+                "alhamdulillahi rabbil alameen",
+                "بسم الله الرحمن الرحيم",
+                "السلام عليكم ورحمة الله وبركاته",
+                "الحمد لله رب العالمين",
+            ]),
+        }
+    }
+}
+
+// A compiled phrase; words may be separated by any whitespace or hyphens, and trailing
+// punctuation goes with the phrase
+struct Rule {
+    phrase: String,
+    pattern: Regex,
+}
+
+fn compile(config: &BoilerplateSettings) -> Vec<Rule> {
+    let openings = config.openings.iter().filter(|_| config.strip_openings);
+    config
+        .phrases
+        .iter()
+        .chain(openings)
+        .filter(|p| !p.trim().is_empty())
+        .filter_map(|phrase| {
+            let words: Vec<String> = phrase.split_whitespace().map(regex::escape).collect();
+            let pattern = format!(r"(?i)\b{}\b[\s.,!;:…]*", words.join(r"[\s\-]+"));
+            Some(Rule { phrase: phrase.trim().to_string(), pattern: Regex::new(&pattern).ok()? })
+        })
+        .collect()
+}
+
+// The text without the phrases, or None when none occurred
+fn strip_text(text: &str, rules: &[Rule], counts: &mut BTreeMap<String, usize>) -> Option<String> {
+    let mut stripped: Option<String> = None;
+    for rule in rules {
+        let current = stripped.as_deref().unwrap_or(text);
+        let found = rule.pattern.find_iter(current).count();
+        if found > 0 {
+            *counts.entry(rule.phrase.clone()).or_insert(0) += found;
+            stripped = Some(rule.pattern.replace_all(current, "").into_owned());
+        }
+    }
+    // Tidy the gaps left behind while keeping line breaks
+    stripped.map(|text| {
+        text.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+// Strip configured phrases from entry inputs and outputs, dropping entries left without
+// output; returns how often each phrase was removed
+pub fn strip(entries: &mut Vec<DatasetEntry>, config: &BoilerplateSettings) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    if !config.enabled {
+        return counts;
+    }
+
+    let rules = compile(config);
+    for entry in entries.iter_mut() {
+        if let Some(input) = strip_text(&entry.input, &rules, &mut counts) {
+            entry.input = input;
+        }
+        if let Some(output) = strip_text(&entry.output, &rules, &mut counts) {
+            entry.output = output;
+        }
+    }
+    entries.retain(|e| !e.output.trim().is_empty());
+    counts
+}
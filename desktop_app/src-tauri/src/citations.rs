@@ -0,0 +1,302 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::state;
+use crate::storage::storage;
+
+// A hadith, Quran or book reference detected in transcript text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    // "hadith", "quran" or "book"
+    pub kind: String,
+    // Collection, surah or book title as normalized by the detector
+    pub work: String,
+    // Hadith number or ayah, when stated
+    pub reference: Option<String>,
+    // The matched text and its byte offset in the transcript
+    pub text: String,
+    pub offset: usize,
+}
+
+// Hadith collections and the spellings ASR output commonly uses for them
+const HADITH_COLLECTIONS: &[(&str, &str)] = &[
+    ("Sahih al-Bukhari", r"(?:sahih\s+)?(?:al[- ])?bukh?ari"),
+    ("Sahih Muslim", r"sahih\s+muslim"),
+    ("Jami at-Tirmidhi", r"(?:at[- ])?tirmidh?i"),
+    ("Sunan Abi Dawud", r"abu\s+dawu?o?d"),
+    ("Sunan an-Nasa'i", r"(?:an[- ])?nasa'?i"),
+    ("Sunan Ibn Majah", r"ibn\s+majah"),
+    ("Muwatta Malik", r"muwatt?a"),
+    ("Musnad Ahmad", r"musnad(?:\s+(?:imam\s+)?ahmad)?"),
+];
+
+// Well-known books of scholarship referenced in lectures
+const BOOKS: &[(&str, &str)] = &[
+    ("Riyad as-Salihin", r"riyadh?\s+(?:as[- ])?salih[ei]en|riyadh?\s+us[- ]saliheen"),
+    ("Tafsir Ibn Kathir", r"tafs[ie]e?r\s+ibn\s+kath[ie]e?r"),
+    ("Ihya Ulum al-Din", r"ihya'?\s+ul[ou]{1,2}m"),
+    ("Madarij as-Salikin", r"madarij\s+(?:as[- ])?salik[ie]e?n"),
+    ("Al-Adab al-Mufrad", r"(?:al[- ])?adab\s+al[- ]mufrad"),
+    ("Bulugh al-Maram", r"bul[ou]{1,2}gh\s+al[- ]maram"),
+];
+
+// The surahs in order, by their common transliteration
+const SURAHS: [&str; 114] = [
+    "Al-Fatihah", "Al-Baqarah", "Al Imran", "An-Nisa", "Al-Ma'idah", "Al-An'am", "Al-A'raf", "Al-Anfal",
+    "At-Tawbah", "Yunus", "Hud", "Yusuf", "Ar-Ra'd", "Ibrahim", "Al-Hijr", "An-Nahl", "Al-Isra", "Al-Kahf",
+    "Maryam", "Ta-Ha", "Al-Anbiya", "Al-Hajj", "Al-Mu'minun", "An-Nur", "Al-Furqan", "Ash-Shu'ara",
+    "An-Naml", "Al-Qasas", "Al-Ankabut", "Ar-Rum", "Luqman", "As-Sajdah", "Al-Ahzab", "Saba", "Fatir",
+    "Ya-Sin", "As-Saffat", "Sad", "Az-Zumar", "Ghafir", "Fussilat", "Ash-Shura", "Az-Zukhruf", "Ad-Dukhan",
+    "Al-Jathiyah", "Al-Ahqaf", "Muhammad", "Al-Fath", "Al-Hujurat", "Qaf", "Adh-Dhariyat", "At-Tur",
+    "An-Najm", "Al-Qamar", "Ar-Rahman", "Al-Waqi'ah", "Al-Hadid", "Al-Mujadilah", "Al-Hashr",
+    "Al-Mumtahanah", "As-Saff", "Al-Jumu'ah", "Al-Munafiqun", "At-Taghabun", "At-Talaq", "At-Tahrim",
+    "Al-Mulk", "Al-Qalam", "Al-Haqqah", "Al-Ma'arij", "Nuh", "Al-Jinn", "Al-Muzzammil", "Al-Muddaththir",
+    "Al-Qiyamah", "Al-Insan", "Al-Mursalat", "An-Naba", "An-Nazi'at", "Abasa", "At-Takwir", "Al-Infitar",
+    "Al-Mutaffifin", "Al-Inshiqaq", "Al-Buruj", "At-Tariq", "Al-A'la", "Al-Ghashiyah", "Al-Fajr", "Al-Balad",
+    "Ash-Shams", "Al-Layl", "Ad-Duha", "Ash-Sharh", "At-Tin", "Al-Alaq", "Al-Qadr", "Al-Bayyinah",
+    "Az-Zalzalah", "Al-Adiyat", "Al-Qari'ah", "At-Takathur", "Al-Asr", "Al-Humazah", "Al-Fil", "Quraysh",
+    "Al-Ma'un", "Al-Kawthar", "Al-Kafirun", "An-Nasr", "Al-Masad", "Al-Ikhlas", "Al-Falaq", "An-Nas",
+];
+
+// Words that mark a following "2:255" as a Quran reference rather than a time or a score
+const QURAN_KEYWORDS: &[&str] = &["quran", "qur'an", "koran", "surah", "sura", "surat", "ayah", "ayat", "verse", "verses"];
+
+static ARTICLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:aal|al|an|ar|as|at|az|ad|adh|ash|ath)[- ]").unwrap());
+static WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[\p{L}'’]+").unwrap());
+static NUMERIC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(\d{1,3}):(\d{1,3})\b").unwrap());
+static NAMED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bsura[ht]?\s+").unwrap());
+// The ayah after a named surah, e.g. ", ayah 255", "verse number 5" or "2:255"
+static AYAH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^,?\s+(?:(?:ayah|ayat|verse)\s+(?:number\s+)?(\d+)\b|\d{1,3}:(\d{1,3})\b)").unwrap());
+static SURAH_KEYS: LazyLock<Vec<String>> = LazyLock::new(|| SURAHS.iter().map(|name| surah_key(name).0).collect());
+static HADITH_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| HADITH_COLLECTIONS.iter().map(|(_, p)| numbered(p)).collect());
+static BOOK_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| BOOKS.iter().map(|(_, p)| numbered(p)).collect());
+
+// Spelling-insensitive form of a surah name, and whether it was written with its article:
+// ASCII letters only, e and o folded into i and u, doubled letters and a final h dropped
+fn surah_key(name: &str) -> (String, bool) {
+    let lower = name.to_lowercase();
+    let stripped = ARTICLE.replace(&lower, "");
+    let mut key = String::new();
+    for c in stripped.chars().filter(char::is_ascii_lowercase) {
+        let c = match c {
+            'e' => 'i',
+            'o' => 'u',
+            c => c,
+        };
+        if !key.ends_with(c) {
+            key.push(c);
+        }
+    }
+    if key.len() > 1 && key.ends_with('h') {
+        key.pop();
+    }
+    (key, stripped.len() < lower.len())
+}
+
+// Surah number of a name, 1-based
+fn surah_number(name: &str) -> Option<usize> {
+    let (key, _) = surah_key(name);
+    SURAH_KEYS.iter().position(|k| *k == key).map(|i| i + 1)
+}
+
+// Longest run of up to three words at the start of `text` naming a surah: its number and byte length
+fn leading_surah(text: &str) -> Option<(usize, usize)> {
+    let mut ends = Vec::new();
+    let mut last = 0;
+    for word in WORD.find_iter(text).take(3) {
+        let gap = &text[last..word.start()];
+        let joined = if ends.is_empty() { gap.is_empty() } else { gap == " " || gap == "-" };
+        if !joined {
+            break;
+        }
+        ends.push(word.end());
+        last = word.end();
+    }
+    ends.iter().rev().find_map(|&end| surah_number(&text[..end]).map(|n| (n, end)))
+}
+
+// Whether the text before a "2:255" names the Quran: a keyword, or a surah name written with its
+// article or after "surah", so that a bare "Hud 10:30" is not taken for one
+fn quran_context(before: &str) -> bool {
+    let words: Vec<String> = before
+        .split_whitespace()
+        .rev()
+        .take(4)
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-').to_lowercase())
+        .collect();
+    let Some(last) = words.first() else { return false };
+    if QURAN_KEYWORDS.contains(&last.as_str()) {
+        return true;
+    }
+    (1..words.len().min(3) + 1).any(|n| {
+        let name: Vec<&str> = words[..n].iter().rev().map(String::as_str).collect();
+        let name = name.join(" ");
+        let after_surah = words.get(n).is_some_and(|w| w.starts_with("sura"));
+        surah_number(&name).is_some() && (surah_key(&name).1 || after_surah)
+    })
+}
+
+// A work's pattern with an optional trailing number, e.g. "Bukhari 6018" or "Muslim, hadith number 45"
+fn numbered(pattern: &str) -> Regex {
+    Regex::new(&format!(r"(?i)\b(?:{})\b(?:,?\s+(?:hadith\s+)?(?:number\s+|no\.?\s*)?(\d+))?", pattern)).unwrap()
+}
+
+fn push_matches(citations: &mut Vec<Citation>, text: &str, kind: &str, work: &str, regex: &Regex) {
+    for capture in regex.captures_iter(text) {
+        let matched = capture.get(0).unwrap();
+        citations.push(Citation {
+            kind: kind.to_string(),
+            work: work.to_string(),
+            reference: capture.get(1).map(|m| m.as_str().to_string()),
+            text: matched.as_str().to_string(),
+            offset: matched.start(),
+        });
+    }
+}
+
+// A citation without its position, as emitted in a dataset entry's `references` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reference {
+    pub kind: String,
+    pub work: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+// Distinct references among citations, in order of first mention
+pub fn references(citations: &[Citation]) -> Vec<Reference> {
+    let mut references: Vec<Reference> = Vec::new();
+    for citation in citations {
+        let reference = Reference {
+            kind: citation.kind.clone(),
+            work: citation.work.clone(),
+            reference: citation.reference.clone(),
+        };
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+// Detect citations in a text, ordered by position
+pub fn extract(text: &str) -> Vec<Citation> {
+    let mut citations = Vec::new();
+
+    for ((work, _), regex) in HADITH_COLLECTIONS.iter().zip(HADITH_PATTERNS.iter()) {
+        push_matches(&mut citations, text, "hadith", work, regex);
+    }
+    for ((work, _), regex) in BOOKS.iter().zip(BOOK_PATTERNS.iter()) {
+        push_matches(&mut citations, text, "book", work, regex);
+    }
+
+    // Quran references in "Surah Al-Baqarah, ayah 255" form; only known surah names count
+    let mut named_spans = Vec::new();
+    for keyword in NAMED.find_iter(text) {
+        let Some((surah, length)) = leading_surah(&text[keyword.end()..]) else { continue };
+        let mut end = keyword.end() + length;
+        let ayah = AYAH.captures(&text[end..]).map(|c| {
+            end += c.get(0).map_or(0, |m| m.end());
+            c.get(1).or_else(|| c.get(2)).map(|m| m.as_str().to_string())
+        });
+        named_spans.push(keyword.start()..end);
+        citations.push(Citation {
+            kind: "quran".to_string(),
+            work: format!("Surah {}", SURAHS[surah - 1]),
+            reference: ayah.flatten(),
+            text: text[keyword.start()..end].to_string(),
+            offset: keyword.start(),
+        });
+    }
+
+    // and in "Quran 2:255" or "Al-Baqarah 2:255" form; a bare "10:30" is usually a time
+    for capture in NUMERIC.captures_iter(text) {
+        let matched = capture.get(0).unwrap();
+        let surah: usize = capture[1].parse().unwrap_or(0);
+        let ayah: usize = capture[2].parse().unwrap_or(0);
+        if !(1..=114).contains(&surah)
+            || ayah == 0
+            || named_spans.iter().any(|span| span.contains(&matched.start()))
+            || !quran_context(&text[..matched.start()])
+        {
+            continue;
+        }
+        citations.push(Citation {
+            kind: "quran".to_string(),
+            work: format!("Surah {}", SURAHS[surah - 1]),
+            reference: Some(capture[2].to_string()),
+            text: matched.as_str().to_string(),
+            offset: matched.start(),
+        });
+    }
+
+    citations.sort_by_key(|c| c.offset);
+    citations
+}
+
+// Command to detect citations in a transcript and store them in the state store
+#[tauri::command]
+pub fn extract_citations(file_name: String) -> Result<Vec<Citation>, String> {
+    let file_path = storage().path("output/transcripts").join(&file_name);
+
+    if !storage().exists(&file_path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    let citations = extract(&content);
+    state::update(|state| {
+        state.citations.insert(file_name, citations.clone());
+    })?;
+
+    Ok(citations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quran(text: &str) -> Vec<(String, Option<String>)> {
+        extract(text)
+            .into_iter()
+            .filter(|c| c.kind == "quran")
+            .map(|c| (c.work, c.reference))
+            .collect()
+    }
+
+    #[test]
+    fn clock_times_are_not_quran_references() {
+        assert!(quran("The lecture starts at 10:30 and ends at 12:15.").is_empty());
+    }
+
+    #[test]
+    fn numeric_references_need_the_quran_named_before_them() {
+        assert_eq!(quran("as in Quran 2:255"), vec![("Surah Al-Baqarah".to_string(), Some("255".to_string()))]);
+        assert_eq!(quran("read Al-Kahf 18:10 tonight"), vec![("Surah Al-Kahf".to_string(), Some("10".to_string()))]);
+    }
+
+    #[test]
+    fn named_references_must_be_a_surah() {
+        assert!(quran("every surah and every ayah").is_empty());
+        assert_eq!(quran("Surah Baqara, ayah 255"), vec![("Surah Al-Baqarah".to_string(), Some("255".to_string()))]);
+        assert_eq!(quran("surat al-ikhlas"), vec![("Surah Al-Ikhlas".to_string(), None)]);
+        assert_eq!(quran("Surah Ya-Sin is the heart"), vec![("Surah Ya-Sin".to_string(), None)]);
+    }
+
+    #[test]
+    fn a_named_reference_with_numbers_is_one_citation() {
+        assert_eq!(quran("Surah Al-Baqarah 2:255"), vec![("Surah Al-Baqarah".to_string(), Some("255".to_string()))]);
+    }
+
+    #[test]
+    fn hadith_numbers_are_kept() {
+        let citations = extract("narrated in Bukhari 6018 and Sahih Muslim, hadith number 45");
+        let found: Vec<_> = citations.iter().map(|c| (c.work.as_str(), c.reference.as_deref())).collect();
+        assert_eq!(found, vec![("Sahih al-Bukhari", Some("6018")), ("Sahih Muslim", Some("45"))]);
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::metadata;
+use crate::state;
+use crate::storage::storage;
+
+// Curator metadata for a clip, keyed by clip stem in the state store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipNotes {
+    // 0-5 stars; 0 means unrated
+    pub rating: u8,
+    pub useful: Option<bool>,
+    pub notes: String,
+}
+
+pub fn clips_dir() -> PathBuf {
+    storage().path("output/clips")
+}
+
+// output/clips and the folders below it that hold clips, e.g. "<channel>/<upload date>" made by a
+// naming template with folders. Clips stay keyed by file name, so every stem is unique across folders.
+// Hidden folders (unfinished downloads) and compressed archive copies are not clip folders.
+pub fn media_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![clips_dir()];
+    let mut index = 0;
+    while index < dirs.len() {
+        if let Ok(entries) = storage().read_dir(&dirs[index]) {
+            for entry in entries.filter_map(Result::ok) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let top = index == 0 && name == "archive";
+                if !name.starts_with('.') && !top && storage().is_dir(entry.path()) {
+                    dirs.push(entry.path());
+                }
+            }
+        }
+        index += 1;
+    }
+    dirs
+}
+
+// Folder holding a clip stem's audio, info JSON and kept video; output/clips for unknown stems
+pub fn media_dir(stem: &str) -> PathBuf {
+    let has_media = |dir: &PathBuf| {
+        storage().exists(dir.join(format!("{}.mp3", stem))) || storage().exists(dir.join(format!("{}.info.json", stem)))
+    };
+    let root = clips_dir();
+    if has_media(&root) {
+        return root;
+    }
+    media_dirs().into_iter().find(has_media).unwrap_or(root)
+}
+
+// Folder of a clip stem relative to output/clips: "" or e.g. "Channel/20240115/"
+pub fn folder(stem: &str) -> String {
+    let dir = media_dir(stem);
+    let relative = dir.strip_prefix(clips_dir()).unwrap_or(Path::new(""));
+    relative
+        .components()
+        .map(|c| format!("{}/", c.as_os_str().to_string_lossy()))
+        .collect()
+}
+
+// A clip stem's file with the given extension, e.g. ("Talk", "mp3")
+pub fn media_file(stem: &str, extension: &str) -> PathBuf {
+    media_dir(stem).join(format!("{}.{}", stem, extension))
+}
+
+// The same file relative to the pipeline directory, for tools run inside the workspace
+pub fn media_arg(stem: &str, extension: &str) -> String {
+    format!("output/clips/{}{}.{}", folder(stem), stem, extension)
+}
+
+// File names of all downloaded mp3 clips, in any clip folder, sorted
+pub fn list_clips() -> Vec<String> {
+    let mut clips: Vec<String> = media_dirs()
+        .into_iter()
+        .filter_map(|dir| storage().read_dir(dir).ok())
+        .flat_map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "mp3"))
+                .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    clips.sort();
+    clips
+}
+
+// Command to list clips with their metadata, notes and transcription status
+#[tauri::command]
+pub fn get_clips() -> Result<serde_json::Value, String> {
+    let state = state::load()?;
+    let transcripts_dir = storage().path("output/transcripts");
+
+    let clips: Vec<serde_json::Value> = list_clips()
+        .into_iter()
+        .map(|file_name| {
+            let stem = metadata::stem(&file_name).to_string();
+            let meta = metadata::load(&file_name).unwrap_or_default();
+            let size = storage().metadata(media_file(&stem, "mp3")).map_or(0, |m| m.len());
+
+            serde_json::json!({
+                "file_name": file_name,
+                "title": meta.title,
+                "channel": meta.channel_name(),
+                "duration": meta.duration,
+                "size": size,
+                "transcribed": storage().exists(transcripts_dir.join(format!("{}.txt", stem))),
+                "notes": state.clip_notes.get(&stem).cloned().unwrap_or_default()
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({ "clips": clips }))
+}
+
+// Command to set the rating, usefulness flag and notes of a clip
+#[tauri::command]
+pub fn set_clip_notes(
+    file_name: String,
+    rating: Option<u8>,
+    useful: Option<bool>,
+    notes: Option<String>,
+) -> Result<ClipNotes, String> {
+    if rating.is_some_and(|r| r > 5) {
+        return Err("Rating must be between 0 and 5".to_string());
+    }
+
+    if !storage().exists(media_file(metadata::stem(&file_name), "mp3")) {
+        return Err(format!("Clip not found: {}", file_name));
+    }
+
+    let stem = metadata::stem(&file_name).to_string();
+    let updated = state::update(|state| {
+        let entry = state.clip_notes.entry(stem).or_default();
+        if let Some(rating) = rating {
+            entry.rating = rating;
+        }
+        if useful.is_some() {
+            entry.useful = useful;
+        }
+        if let Some(notes) = notes {
+            entry.notes = notes;
+        }
+        entry.clone()
+    })?;
+    audit::record("clip.notes", &file_name, serde_json::json!({ "rating": rating, "useful": useful }));
+    Ok(updated)
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::clips;
+use crate::dataset;
+use crate::integrity;
+use crate::metadata;
+use crate::settings;
+use crate::state;
+use crate::transcript;
+use crate::video;
+use crate::workspace;
+use crate::storage::{storage, Grant};
+
+// Archive location for processed audio, stored in settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColdStorageSettings {
+    // Directory on an external drive or NAS; items are kept in one subdirectory per clip stem
+    pub path: Option<String>,
+}
+
+// An item whose audio lives in cold storage; its info JSON and transcripts stay in the workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdItem {
+    // Directory holding the item's files
+    pub location: String,
+    // Paths relative to output/clips, e.g. "<stem>.mp3", "<channel>/<stem>.mp3" or "archive/<stem>.opus"
+    pub files: Vec<String>,
+    pub bytes: u64,
+    pub moved_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ColdStorageReport {
+    pub moved: Vec<String>,
+    pub bytes: u64,
+    pub errors: Vec<String>,
+}
+
+// Configured cold storage directory, which must be mounted, with access to it while the grant lives
+fn root() -> Result<(PathBuf, Grant<'static>), String> {
+    let path = settings::load()
+        .cold_storage
+        .path
+        .filter(|p| !p.trim().is_empty())
+        .ok_or("No cold storage path is configured")?;
+    let grant = storage().allow(&path).map_err(|e| format!("Failed to access cold storage: {}", e))?;
+    if !storage().exists(&path) {
+        return Err(format!("Cold storage is not reachable: {}", path));
+    }
+    Ok((PathBuf::from(path), grant))
+}
+
+// Audio and its sidecars for a clip stem: the mp3, a kept video and any compressed archive copy,
+// matched by exact name. The info JSON is metadata and stays live.
+fn item_files(stem: &str, current: &state::PipelineState) -> Vec<String> {
+    let folder = clips::folder(stem);
+    let mut files: Vec<String> = std::iter::once(&"mp3")
+        .chain(video::VIDEO_EXTENSIONS)
+        .map(|extension| format!("{}{}.{}", folder, stem, extension))
+        .collect();
+    if let Some(record) = current.archived_clips.get(&format!("{}.mp3", stem)) {
+        files.push(record.archived.clone());
+    }
+    files.retain(|file| storage().is_file(clips::clips_dir().join(file)));
+    files.sort();
+    files
+}
+
+// Copy a file and check the copy before the source is deleted
+fn transfer(from: &Path, to: &Path) -> Result<u64, String> {
+    if let Some(parent) = to.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let bytes = storage().copy(from, to).map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+    if integrity::sha256_file(from)? != integrity::sha256_file(to)? {
+        storage().remove_file(to).ok();
+        return Err(format!("Copy of {} does not match the original", from.display()));
+    }
+    storage().remove_file(from).map_err(|e| format!("Failed to delete {}: {}", from.display(), e))?;
+    Ok(bytes)
+}
+
+fn move_item(stem: &str, root: &Path, current: &state::PipelineState) -> Result<ColdItem, String> {
+    let files = item_files(stem, current);
+    if files.is_empty() {
+        return Err(format!("No audio for {}", stem));
+    }
+
+    let location = root.join(stem);
+    let mut item = ColdItem { location: location.to_string_lossy().to_string(), files: Vec::new(), bytes: 0, moved_at: state::now() };
+    let mut failed = None;
+    for file in files {
+        match transfer(&clips::clips_dir().join(&file), &location.join(&file)) {
+            Ok(bytes) => {
+                item.bytes += bytes;
+                item.files.push(file);
+            }
+            Err(e) => {
+                failed = Some(e);
+                break;
+            }
+        }
+    }
+
+    // Files moved before a failure are recorded too, so restore_item can bring them back
+    if !item.files.is_empty() {
+        state::update(|s| s.cold_items.insert(stem.to_string(), item.clone()))?;
+    }
+    match failed {
+        Some(e) => Err(e),
+        None => Ok(item),
+    }
+}
+
+// Command to move the audio of fully processed items (approved and in a generated dataset) to
+// cold storage; `ids` limits it to those clip stems
+#[tauri::command]
+pub fn move_to_cold_storage(ids: Option<Vec<String>>) -> Result<ColdStorageReport, String> {
+    workspace::ensure_writable()?;
+    let (root, _grant) = root()?;
+
+    let current = state::load()?;
+    let used = dataset::sources_in_datasets();
+    // Clips compressed by archive_clips have no mp3 left but are processed all the same
+    let clip_names = clips::list_clips().into_iter().chain(current.archived_clips.keys().cloned());
+    let stems: BTreeSet<String> = clip_names
+        .map(|clip| metadata::stem(&clip).to_string())
+        .filter(|stem| !current.cold_items.contains_key(stem))
+        .filter(|stem| ids.as_ref().is_none_or(|ids| ids.contains(stem)))
+        .filter(|stem| {
+            let transcript = format!("{}.txt", stem);
+            transcript::review_status(&current, &transcript) == "approved" && used.contains(&transcript)
+        })
+        .collect();
+
+    let mut report = ColdStorageReport::default();
+    for stem in stems {
+        match move_item(&stem, &root, &current) {
+            Ok(item) => {
+                report.bytes += item.bytes;
+                report.moved.push(stem);
+            }
+            Err(e) => report.errors.push(format!("{}: {}", stem, e)),
+        }
+    }
+
+    if !report.moved.is_empty() {
+        integrity::refresh().ok();
+        audit::record(
+            "cold_storage.move",
+            &root.to_string_lossy(),
+            serde_json::json!({ "items": report.moved.len(), "bytes": report.bytes }),
+        );
+    }
+    Ok(report)
+}
+
+// Command to bring an item's audio back from cold storage into output/clips
+#[tauri::command]
+pub fn restore_item(id: String) -> Result<ColdItem, String> {
+    workspace::ensure_writable()?;
+    let item = state::load()?.cold_items.get(&id).cloned().ok_or_else(|| format!("Item is not in cold storage: {}", id))?;
+
+    let location = PathBuf::from(&item.location);
+    let _grant = storage().allow(&location).map_err(|e| format!("Failed to access cold storage: {}", e))?;
+    if !storage().exists(&location) {
+        return Err(format!("Cold storage is not reachable: {}", item.location));
+    }
+    for file in &item.files {
+        // Already back from an earlier, interrupted restore
+        if !storage().exists(location.join(file)) && storage().exists(clips::clips_dir().join(file)) {
+            continue;
+        }
+        transfer(&location.join(file), &clips::clips_dir().join(file))?;
+    }
+    storage().remove_dir_all(&location).ok();
+
+    state::update(|s| s.cold_items.remove(&id))?;
+    integrity::refresh().ok();
+    audit::record("cold_storage.restore", &id, serde_json::json!({ "files": item.files }));
+    Ok(item)
+}
+
+// Command to list items in cold storage by clip stem
+#[tauri::command]
+pub fn get_cold_items() -> Result<BTreeMap<String, ColdItem>, String> {
+    Ok(state::load()?.cold_items)
+}
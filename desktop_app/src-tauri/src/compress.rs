@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::audit;
+use crate::clips;
+use crate::events::{self, LogLevel, Stage};
+use crate::integrity;
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::scheduler::{self, Resource};
+use crate::state;
+use crate::workspace;
+use crate::storage::storage;
+
+// A transcribed clip whose mp3 was replaced by a smaller archive copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedClip {
+    pub codec: String,
+    // Relative to output/clips, e.g. "archive/<stem>.opus"
+    pub archived: String,
+    pub archived_sha256: String,
+    pub archived_bytes: u64,
+    // The original mp3, so integrity records and exports can still be matched to it
+    pub original_sha256: String,
+    pub original_bytes: u64,
+    pub archived_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArchiveReport {
+    pub archived: Vec<String>,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub errors: Vec<String>,
+}
+
+// ffmpeg encoder, its arguments and the file extension for each supported codec;
+// bitrates are chosen for speech
+fn encoder(codec: &str) -> Result<(&'static [&'static str], &'static str), String> {
+    match codec {
+        "opus" => Ok((&["-c:a", "libopus", "-b:a", "32k", "-application", "voip"], "opus")),
+        "aac" => Ok((&["-c:a", "aac", "-b:a", "48k"], "m4a")),
+        _ => Err(format!("Unsupported archive codec: {}", codec)),
+    }
+}
+
+fn transcode(input: &std::path::Path, output: &std::path::Path, args: &[&str]) -> Result<(), String> {
+    let _permit = scheduler::acquire(Resource::Cpu)?;
+    let result = Invocation::new("ffmpeg")
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-i"])
+        .arg(input)
+        .arg("-vn")
+        .args(args)
+        .arg(output)
+        .run()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    if !result.success {
+        storage().remove_file(output).ok();
+        return Err(result.stderr.trim().to_string());
+    }
+    Ok(())
+}
+
+fn archive_clip(clip: &str, codec: &str) -> Result<ArchivedClip, String> {
+    let (args, extension) = encoder(codec)?;
+    let original = clips::media_file(metadata::stem(clip), "mp3");
+
+    // A restored clip still has its archive copy, so the mp3 only needs to go again
+    if let Some(record) = state::load()?.archived_clips.get(clip) {
+        if storage().exists(clips::clips_dir().join(&record.archived)) {
+            storage().remove_file(&original).map_err(|e| format!("Failed to delete {}: {}", clip, e))?;
+            return Ok(record.clone());
+        }
+    }
+
+    let relative = format!("archive/{}.{}", metadata::stem(clip), extension);
+    let archived = clips::clips_dir().join(&relative);
+    storage()
+        .create_dir_all(clips::clips_dir().join("archive"))
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let original_sha256 = integrity::sha256_file(&original)?;
+    let original_bytes = storage().metadata(&original).map_or(0, |m| m.len());
+    transcode(&original, &archived, args)?;
+
+    let record = ArchivedClip {
+        codec: codec.to_string(),
+        archived: relative,
+        archived_sha256: integrity::sha256_file(&archived)?,
+        archived_bytes: storage().metadata(&archived).map_or(0, |m| m.len()),
+        original_sha256,
+        original_bytes,
+        archived_at: state::now(),
+    };
+    if record.archived_bytes == 0 {
+        storage().remove_file(&archived).ok();
+        return Err("ffmpeg wrote an empty file".to_string());
+    }
+
+    // The record is stored before the original goes, so a crash never loses the mapping
+    state::update(|s| s.archived_clips.insert(clip.to_string(), record.clone()))?;
+    storage().remove_file(&original).map_err(|e| format!("Failed to delete {}: {}", clip, e))?;
+    Ok(record)
+}
+
+// Command to transcode transcribed clips to a compact codec ("opus" or "aac"), replacing the
+// mp3s; the original hashes are kept in the state store
+#[tauri::command]
+pub fn archive_clips(app: AppHandle, codec: String) -> Result<ArchiveReport, String> {
+    workspace::ensure_writable()?;
+    encoder(&codec)?;
+
+    let transcripts = storage().path("output/transcripts");
+    let mut report = ArchiveReport::default();
+    for clip in clips::list_clips() {
+        // Untranscribed clips are still needed as they are
+        if !storage().exists(transcripts.join(format!("{}.txt", metadata::stem(&clip)))) {
+            continue;
+        }
+
+        match archive_clip(&clip, &codec) {
+            Ok(record) => {
+                report.bytes_before += record.original_bytes;
+                report.bytes_after += record.archived_bytes;
+                report.archived.push(clip);
+            }
+            Err(e) => {
+                events::log(&app, Stage::Analysis, LogLevel::Warn, &format!("Archiving {} failed: {}", clip, e));
+                report.errors.push(format!("{}: {}", clip, e));
+            }
+        }
+    }
+
+    if !report.archived.is_empty() {
+        integrity::refresh().ok();
+        audit::record(
+            "clips.archive",
+            &codec,
+            serde_json::json!({ "clips": report.archived.len(), "bytes_before": report.bytes_before, "bytes_after": report.bytes_after }),
+        );
+    }
+    Ok(report)
+}
+
+// Command to decode an archived clip back to an mp3 for tools that need the audio, e.g. excerpts
+// or re-transcription; the archive copy and its record are kept, so archiving it again is free
+#[tauri::command]
+pub fn restore_clip(clip: String) -> Result<String, String> {
+    workspace::ensure_writable()?;
+    let record = state::load()?
+        .archived_clips
+        .get(&clip)
+        .cloned()
+        .ok_or_else(|| format!("Clip is not archived: {}", clip))?;
+
+    // Back into the clip's folder, where its info JSON stayed
+    let restored = clips::media_file(metadata::stem(&clip), "mp3");
+    if storage().exists(&restored) {
+        return Ok(clip);
+    }
+    transcode(
+        &clips::clips_dir().join(&record.archived),
+        &restored,
+        &["-c:a", "libmp3lame", "-q:a", "2"],
+    )?;
+    integrity::refresh().ok();
+    Ok(clip)
+}
@@ -0,0 +1,137 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+
+use serde::Serialize;
+
+use crate::dataset;
+use crate::wer;
+use crate::storage::storage;
+
+// Overlap length in words; 13-grams are the usual contamination threshold
+const DEFAULT_NGRAM: usize = 13;
+
+// Benchmark items shorter than the n-gram are matched whole when they have at least this many words
+const MIN_SHORT_ITEM_WORDS: usize = 6;
+
+// Dataset entry sharing text with benchmark items
+#[derive(Debug, Serialize)]
+pub struct Overlap {
+    pub id: String,
+    pub source: Option<String>,
+    pub matched_ngrams: usize,
+    // Share of the entry's n-grams found in the benchmark
+    pub ratio: f64,
+    // Indexes of the benchmark items the entry overlaps
+    pub benchmark_items: Vec<usize>,
+    // First overlapping passage, for judging the match
+    pub example: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContaminationReport {
+    pub benchmark_file: String,
+    pub benchmark_items: usize,
+    pub entries_checked: usize,
+    pub ngram: usize,
+    pub overlaps: Vec<Overlap>,
+}
+
+// Every string in a JSON value, e.g. the question, choices and answer of an eval item
+fn strings(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| strings(v, out)),
+        serde_json::Value::Object(fields) => fields.values().for_each(|v| strings(v, out)),
+        _ => {}
+    }
+}
+
+// Benchmark items from a JSON array, JSONL, or plain text with one item per line
+fn benchmark_items(content: &str) -> Vec<String> {
+    let to_text = |value: &serde_json::Value| {
+        let mut parts = Vec::new();
+        strings(value, &mut parts);
+        parts.join(" ")
+    };
+    if let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(content) {
+        return items.iter().map(to_text).collect();
+    }
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).map_or_else(|_| line.to_string(), |v| to_text(&v)))
+        .collect()
+}
+
+fn hash(words: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    words.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Command to find dataset entries that share n-grams with an eval benchmark
+#[tauri::command]
+pub fn check_contamination(
+    benchmark_file: String,
+    dataset: Option<String>,
+    ngram: Option<usize>,
+) -> Result<ContaminationReport, String> {
+    let n = ngram.unwrap_or(DEFAULT_NGRAM).max(1);
+    let _grant = storage().allow(&benchmark_file).map_err(|e| format!("Failed to access benchmark: {}", e))?;
+    let content = storage().read_to_string(&benchmark_file)
+        .map_err(|e| format!("Failed to read benchmark: {}", e))?;
+    let items = benchmark_items(&content);
+
+    // N-gram hashes of the benchmark, by length, with the item each came from
+    let mut index: HashMap<usize, HashMap<u64, usize>> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        let words = wer::words(item);
+        if words.len() >= n {
+            for gram in words.windows(n) {
+                index.entry(n).or_default().entry(hash(gram)).or_insert(i);
+            }
+        } else if words.len() >= MIN_SHORT_ITEM_WORDS {
+            index.entry(words.len()).or_default().entry(hash(&words)).or_insert(i);
+        }
+    }
+
+    let entries = dataset::load_entries(&dataset::dataset_path(dataset.as_deref())?)?;
+    let mut overlaps = Vec::new();
+    for entry in &entries {
+        let words = wer::words(&format!("{} {}", entry.input, entry.output));
+        let mut matched = 0;
+        let mut total = 0;
+        let mut hits = BTreeSet::new();
+        let mut example = None;
+        for (length, grams) in &index {
+            for gram in words.windows(*length) {
+                total += 1;
+                if let Some(item) = grams.get(&hash(gram)) {
+                    matched += 1;
+                    hits.insert(*item);
+                    example.get_or_insert_with(|| gram.join(" "));
+                }
+            }
+        }
+        if matched > 0 {
+            overlaps.push(Overlap {
+                id: entry.id.clone(),
+                source: entry.source.clone(),
+                matched_ngrams: matched,
+                ratio: matched as f64 / total.max(1) as f64,
+                benchmark_items: hits.into_iter().collect(),
+                example: example.unwrap_or_default(),
+            });
+        }
+    }
+    overlaps.sort_by_key(|o| std::cmp::Reverse(o.matched_ngrams));
+
+    Ok(ContaminationReport {
+        benchmark_file,
+        benchmark_items: items.len(),
+        entries_checked: entries.len(),
+        ngram: n,
+        overlaps,
+    })
+}
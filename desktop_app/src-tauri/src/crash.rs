@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use crate::state;
+use crate::storage::storage;
+
+// Number of recent pipeline events kept in memory for crash reports
+const LOG_CAPACITY: usize = 200;
+
+static RECENT_LOGS: Mutex<VecDeque<serde_json::Value>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Serialize)]
+pub struct SystemInfo {
+    pub app_version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub cpus: usize,
+}
+
+fn system_info() -> SystemInfo {
+    SystemInfo {
+        app_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+    }
+}
+
+pub fn crash_dir() -> PathBuf {
+    storage().path("output/crash")
+}
+
+// Keep an event in the in-memory ring buffer that crash reports include
+pub fn remember(event: &impl Serialize) {
+    let Ok(value) = serde_json::to_value(event) else { return };
+    let mut logs = RECENT_LOGS.lock().unwrap_or_else(|e| e.into_inner());
+    if logs.len() == LOG_CAPACITY {
+        logs.pop_front();
+    }
+    logs.push_back(serde_json::json!({ "time": state::now(), "event": value }));
+}
+
+fn recent_logs() -> Vec<serde_json::Value> {
+    RECENT_LOGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
+
+// Install a panic hook that writes a report to output/crash before the default hook runs
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+
+        let report = serde_json::json!({
+            "time": state::now(),
+            "message": message,
+            "location": info.location().map(|l| format!("{}:{}", l.file(), l.line())),
+            "thread": std::thread::current().name().map(str::to_string),
+            "backtrace": std::backtrace::Backtrace::force_capture().to_string(),
+            "system": system_info(),
+            "recent_logs": recent_logs(),
+        });
+
+        // Best effort: a failure here must not mask the original panic
+        let dir = crash_dir();
+        if storage().create_dir_all(&dir).is_ok() {
+            if let Ok(content) = serde_json::to_string_pretty(&report) {
+                storage().write(dir.join(format!("crash-{}.json", state::now())), content).ok();
+            }
+        }
+
+        default_hook(info);
+    }));
+}
+
+// Command to bundle crash reports, recent logs and system info into a single file for issue reports
+#[tauri::command]
+pub fn export_crash_report(path: Option<String>) -> Result<String, String> {
+    let mut crashes = Vec::new();
+    if let Ok(entries) = storage().read_dir(crash_dir()) {
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("crash-")))
+            .collect();
+        files.sort();
+
+        for file in files {
+            let report = storage().read_to_string(&file)
+                .ok()
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+            if let Some(report) = report {
+                crashes.push(report);
+            }
+        }
+    }
+
+    let bundle = serde_json::json!({
+        "created": state::now(),
+        "system": system_info(),
+        "crashes": crashes,
+        "recent_logs": recent_logs(),
+    });
+
+    // The target is picked by the user, so it is granted for this export only
+    let _grant = path.as_ref().map(|p| storage().allow(p)).transpose().map_err(|e| format!("Failed to access target: {}", e))?;
+    let target = match path {
+        Some(path) => PathBuf::from(path),
+        None => crash_dir().join(format!("crash_report_{}.json", state::now())),
+    };
+    if let Some(parent) = target.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    storage().write(&target, content).map_err(|e| format!("Failed to write crash report: {}", e))?;
+
+    Ok(target.to_string_lossy().to_string())
+}
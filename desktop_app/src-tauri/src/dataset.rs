@@ -0,0 +1,942 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::BufReader;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use serde::de::{DeserializeOwned, IgnoredAny, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::anonymize;
+use crate::boilerplate;
+use crate::citations::{self, Citation, Reference};
+use crate::flagging;
+use crate::glossary;
+use crate::i18n;
+use crate::integrity;
+use crate::journal;
+use crate::licenses::{self, LicenseKind};
+use crate::manifest;
+use crate::metadata;
+use crate::numerals;
+use crate::optouts;
+use crate::permissions;
+use crate::quality;
+use crate::roundtrip;
+use crate::settings;
+use crate::state;
+use crate::tags;
+use crate::telemetry;
+use crate::text;
+use crate::transcript;
+use crate::workspace;
+use crate::storage::storage;
+
+// A single LoRA training entry, as written by the JSON generation script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetEntry {
+    // Stable identifier derived from the source and input text
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    // System prompt of the persona the entry was generated for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    pub instruction: String,
+    pub input: String,
+    pub output: String,
+    // Transcript file the entry was generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    // Sampling weight for trainers that support weighted examples
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<Citation>,
+    // Quran, hadith and book references for citation-grounded datasets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<Reference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    // Title of the topical section a section-level entry covers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    // Clip and time range of segment-level entries, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clip: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<f64>,
+}
+
+impl DatasetEntry {
+    // Channel used for balancing, falling back to the source transcript
+    pub fn channel_key(&self) -> String {
+        self.provenance
+            .as_ref()
+            .and_then(|p| p.channel.clone())
+            .or_else(|| self.source.clone())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+// Source attribution for an entry, taken from the clip's yt-dlp metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Provenance {
+    pub video_url: Option<String>,
+    pub channel: Option<String>,
+    // Timestamp range within the source audio, in seconds
+    pub start: f64,
+    pub end: Option<f64>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub license_kind: LicenseKind,
+    // Whether the channel owner gave permission; None when nothing is recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission: Option<bool>,
+}
+
+impl Provenance {
+    pub fn from_metadata(meta: &metadata::ClipMetadata) -> Self {
+        Provenance {
+            video_url: meta.webpage_url.clone(),
+            channel: meta.channel_name().map(str::to_string),
+            start: 0.0,
+            end: meta.duration,
+            license: meta.license.clone(),
+            license_kind: licenses::classify(meta),
+            permission: None,
+        }
+    }
+}
+
+// Criteria for building a subset of the dataset; empty fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatasetFilter {
+    pub topics: Vec<String>,
+    pub min_quality: Option<f64>,
+    pub language: Option<String>,
+    pub sources: Vec<String>,
+    pub tags: Vec<String>,
+    pub speakers: Vec<String>,
+    // Matched against the license detected from the clip's metadata
+    pub licenses: Vec<LicenseKind>,
+    // Only entries from channels whose owners gave explicit permission
+    pub permissioned_only: bool,
+}
+
+// Limits applied on export so no single channel or lecture dominates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BalanceOptions {
+    pub max_per_channel: Option<usize>,
+    pub max_per_source: Option<usize>,
+    // Assign inverse-frequency weights so every channel carries equal total weight
+    pub weight_by_channel: bool,
+}
+
+// Optional transformations applied when exporting a subset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub balance: Option<BalanceOptions>,
+    // Replace speaker names with placeholders such as "Shaykh"/"Questioner"
+    pub anonymize: bool,
+    // Emit detected hadith/book/Quran citations as an auxiliary field
+    pub include_citations: bool,
+}
+
+// Cap entries per channel/source and optionally assign balancing weights
+pub fn balance_entries(entries: Vec<DatasetEntry>, options: &BalanceOptions) -> Vec<DatasetEntry> {
+    let mut per_channel: HashMap<String, usize> = HashMap::new();
+    let mut per_source: HashMap<String, usize> = HashMap::new();
+
+    let mut kept: Vec<DatasetEntry> = entries
+        .into_iter()
+        .filter(|entry| {
+            let channel = per_channel.entry(entry.channel_key()).or_insert(0);
+            let source = per_source
+                .entry(entry.source.clone().unwrap_or_default())
+                .or_insert(0);
+
+            if options.max_per_channel.is_some_and(|max| *channel >= max)
+                || options.max_per_source.is_some_and(|max| *source >= max)
+            {
+                return false;
+            }
+
+            *channel += 1;
+            *source += 1;
+            true
+        })
+        .collect();
+
+    if options.weight_by_channel && !kept.is_empty() {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for entry in &kept {
+            *counts.entry(entry.channel_key()).or_insert(0) += 1;
+        }
+
+        // Normalize so the mean weight stays 1.0
+        let channels = counts.len() as f64;
+        let total = kept.len() as f64;
+        for entry in kept.iter_mut() {
+            let count = counts[&entry.channel_key()] as f64;
+            let weight = total / (channels * count);
+            entry.weight = Some(entry.weight.unwrap_or(1.0) * weight);
+        }
+    }
+
+    kept
+}
+
+impl DatasetFilter {
+    pub fn matches(&self, entry: &DatasetEntry) -> bool {
+        if !self.topics.is_empty()
+            && !entry.topics.iter().any(|t| self.topics.iter().any(|f| f.eq_ignore_ascii_case(t)))
+        {
+            return false;
+        }
+
+        if let Some(min_quality) = self.min_quality {
+            if entry.quality.is_none_or(|q| q < min_quality) {
+                return false;
+            }
+        }
+
+        if let Some(language) = &self.language {
+            if entry.language.as_deref() != Some(language.as_str()) {
+                return false;
+            }
+        }
+
+        if !self.tags.is_empty()
+            && !entry.tags.iter().any(|t| self.tags.iter().any(|f| f.eq_ignore_ascii_case(t)))
+        {
+            return false;
+        }
+
+        if !self.speakers.is_empty() {
+            match &entry.speaker {
+                Some(speaker) if self.speakers.contains(speaker) => {}
+                _ => return false,
+            }
+        }
+
+        if !self.licenses.is_empty() {
+            let kind = entry.provenance.as_ref().map_or(LicenseKind::Unknown, |p| p.license_kind);
+            if !self.licenses.contains(&kind) {
+                return false;
+            }
+        }
+
+        if self.permissioned_only && entry.provenance.as_ref().and_then(|p| p.permission) != Some(true) {
+            return false;
+        }
+
+        if !self.sources.is_empty() {
+            match &entry.source {
+                Some(source) if self.sources.iter().any(|s| s == source) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+// Identifier of the dataset used when commands are not given one
+pub const DEFAULT_DATASET: &str = "default";
+
+// A named dataset generated from the corpus, optionally restricted to a subset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetConfig {
+    pub id: String,
+    // Output file, relative to the pipeline directory
+    pub path: String,
+    #[serde(default)]
+    pub filter: Option<DatasetFilter>,
+    #[serde(default)]
+    pub granularity: Granularity,
+    // Persona used when generate_json is not given one
+    #[serde(default)]
+    pub persona: Option<String>,
+    // Citation-grounded mode: give every entry a structured `references` field
+    #[serde(default)]
+    pub references: bool,
+    // Restore missing punctuation from whisper pauses before text is chunked into entries
+    #[serde(default = "default_punctuation")]
+    pub punctuation: bool,
+}
+
+fn default_punctuation() -> bool {
+    true
+}
+
+// How much transcript text goes into one entry
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Granularity {
+    // One entry per transcript file
+    #[default]
+    File,
+    // One entry per `per_entry` consecutive whisper segments, with timestamps
+    Segment { per_entry: usize },
+    // One entry per topical section found by segment_topics
+    Section,
+}
+
+impl Granularity {
+    // Mode argument of the generation script: segments per entry, 0 for whole files, or "sections"
+    pub fn script_arg(&self) -> String {
+        match self {
+            Granularity::File => "0".to_string(),
+            Granularity::Segment { per_entry } => (*per_entry).max(1).to_string(),
+            Granularity::Section => "sections".to_string(),
+        }
+    }
+}
+
+impl DatasetConfig {
+    pub fn default_dataset() -> Self {
+        DatasetConfig {
+            id: DEFAULT_DATASET.to_string(),
+            path: "output/json/akhi_lora.json".to_string(),
+            filter: None,
+            granularity: Granularity::File,
+            persona: None,
+            references: false,
+            punctuation: true,
+        }
+    }
+}
+
+// Look up a dataset in the workspace settings; the default dataset always exists
+pub fn dataset_config(id: Option<&str>) -> Result<DatasetConfig, String> {
+    let id = id.unwrap_or(DEFAULT_DATASET);
+    settings::load()
+        .datasets
+        .into_iter()
+        .find(|d| d.id == id)
+        .or_else(|| (id == DEFAULT_DATASET).then(DatasetConfig::default_dataset))
+        .ok_or_else(|| i18n::message("error-unknown-dataset", &[("dataset", id)]))
+}
+
+// Path of a dataset file
+pub fn dataset_path(id: Option<&str>) -> Result<PathBuf, String> {
+    let config = dataset_config(id)?;
+    let path = Path::new(&config.path);
+    if path.is_absolute() || path.components().any(|c| c == std::path::Component::ParentDir) {
+        return Err(format!("Dataset path must stay inside the pipeline directory: {}", config.path));
+    }
+
+    Ok(storage().path(path))
+}
+
+// Read and parse a dataset file
+pub fn load_entries(path: &Path) -> Result<Vec<DatasetEntry>, String> {
+    let content = storage().read_to_string(path)
+        .map_err(|e| format!("Failed to read JSON: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+struct StreamVisitor<T, W, V> {
+    wanted: W,
+    visit: V,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T, W, V> Visitor<'de> for StreamVisitor<T, W, V>
+where
+    T: DeserializeOwned,
+    W: Fn(usize) -> bool,
+    V: FnMut(usize, T) -> bool,
+{
+    type Value = usize;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a JSON array of dataset entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<usize, A::Error> {
+        let mut index = 0;
+        let mut done = false;
+        loop {
+            // Unwanted entries are skipped without building values
+            if done || !(self.wanted)(index) {
+                if seq.next_element::<IgnoredAny>()?.is_none() {
+                    break;
+                }
+            } else {
+                match seq.next_element::<T>()? {
+                    Some(entry) => done = !(self.visit)(index, entry),
+                    None => break,
+                }
+            }
+            index += 1;
+        }
+        Ok(index)
+    }
+}
+
+// Read a dataset file one entry at a time instead of parsing it whole. Entries for which
+// `wanted` is false are skipped; `visit` returns false once it has everything it needs.
+// Returns the total number of entries in the file.
+pub fn stream_entries<T, W, V>(path: &Path, wanted: W, visit: V) -> Result<usize, String>
+where
+    T: DeserializeOwned,
+    W: Fn(usize) -> bool,
+    V: FnMut(usize, T) -> bool,
+{
+    let file = storage().open(path).map_err(|e| format!("Failed to read JSON: {}", e))?;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+
+    deserializer
+        .deserialize_seq(StreamVisitor { wanted, visit, marker: PhantomData })
+        .map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+// Entry count of a dataset file, valid while the file keeps this modification time and size
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntryCount {
+    pub count: usize,
+    pub modified: u64,
+    pub size: u64,
+}
+
+// Modification time (unix nanoseconds) and size of a file
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = storage().metadata(path).ok()?;
+    let modified = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_nanos() as u64;
+    Some((modified, meta.len()))
+}
+
+// Remember how many entries a dataset file holds right after the app wrote it
+pub fn record_count(path: &Path, count: usize) {
+    if let Some((modified, size)) = file_stamp(path) {
+        let key = path.to_string_lossy().to_string();
+        let known = state::load().ok().and_then(|mut s| s.entry_counts.remove(&key));
+        if known.is_some_and(|c| (c.count, c.modified, c.size) == (count, modified, size)) {
+            return;
+        }
+        state::update(|s| s.entry_counts.insert(key, EntryCount { count, modified, size })).ok();
+    }
+}
+
+// Number of entries in a dataset file, from the state store when the file is unchanged
+// and otherwise by a streaming count that is then stored
+pub fn entry_count(path: &Path) -> Result<usize, String> {
+    let stamp = file_stamp(path).ok_or_else(|| "JSON file not found".to_string())?;
+    let cached = state::load()?.entry_counts.remove(path.to_string_lossy().as_ref());
+    if let Some(cached) = cached.filter(|c| (c.modified, c.size) == stamp) {
+        return Ok(cached.count);
+    }
+
+    let count = stream_entries(path, |_| false, |_, _: IgnoredAny| true)?;
+    record_count(path, count);
+    Ok(count)
+}
+
+// Serialize entries to a dataset file, creating parent directories as needed
+pub fn save_entries(path: &Path, entries: &[DatasetEntry]) -> Result<(), String> {
+    workspace::ensure_writable()?;
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+
+    storage().write(path, content).map_err(|e| format!("Failed to write JSON: {}", e))?;
+    record_count(path, entries.len());
+    Ok(())
+}
+
+// Stable identifier of an entry, from its source and input
+pub fn entry_id(source: Option<&str>, input: &str) -> String {
+    text::fingerprint(source.unwrap_or("").bytes().chain([b'\n']).chain(input.bytes()))
+}
+
+// Fill in analysis fields that the generation script does not compute
+pub fn annotate(entry: &mut DatasetEntry) {
+    if entry.id.is_empty() {
+        entry.id = entry_id(entry.source.as_deref(), &entry.input);
+    }
+
+    if entry.topics.is_empty() {
+        entry.topics = text::extract_topics(&entry.input);
+    }
+
+    if entry.language.is_none() {
+        entry.language = Some(text::detect_language(&entry.input).to_string());
+    }
+}
+
+// Merge the entry's own tags with those of its source transcript
+pub fn attach_tags(entry: &mut DatasetEntry, state: &state::PipelineState) {
+    let mut tags = std::mem::take(&mut entry.tags);
+    tags.extend(state.entry_tags.get(&entry.id).cloned().unwrap_or_default());
+    if let Some(source) = &entry.source {
+        tags.extend(state.transcript_tags.get(source).cloned().unwrap_or_default());
+    }
+    entry.tags = tags::normalize(tags);
+}
+
+// Use the stored citations of the source transcript, or detect them in the entry
+pub fn attach_citations(entry: &mut DatasetEntry, state: &state::PipelineState) {
+    if !entry.citations.is_empty() {
+        return;
+    }
+
+    entry.citations = match entry.source.as_ref().and_then(|s| state.citations.get(s)) {
+        Some(stored) => stored.clone(),
+        None => citations::extract(&entry.input),
+    };
+}
+
+// Structured references of an entry; entries covering part of a transcript only cite what they contain
+pub fn attach_references(entry: &mut DatasetEntry, state: &state::PipelineState) {
+    let partial = entry.start.is_some() || entry.section.is_some();
+    let citations = if partial {
+        citations::extract(&entry.input)
+    } else {
+        attach_citations(entry, state);
+        std::mem::take(&mut entry.citations)
+    };
+    entry.references = citations::references(&citations);
+}
+
+// Replace detected speaker names, recording the mapping per source
+pub fn anonymize_entries(entries: &mut [DatasetEntry]) -> Result<(), String> {
+    let mut map = anonymize::load_map();
+
+    for entry in entries.iter_mut() {
+        let key = entry.source.clone().unwrap_or_else(|| "unknown".to_string());
+        let mapping = map.entry(key).or_default();
+        for (name, placeholder) in anonymize::detect_speakers(&entry.input) {
+            mapping.entry(name).or_insert(placeholder);
+        }
+
+        entry.input = anonymize::apply(&entry.input, mapping);
+        entry.output = anonymize::apply(&entry.output, mapping);
+    }
+
+    anonymize::save_map(&map)
+}
+
+// Attach provenance to entries that were generated from a known transcript
+pub fn attach_provenance(entry: &mut DatasetEntry) {
+    if entry.provenance.is_some() {
+        return;
+    }
+
+    if let Some(meta) = entry.source.as_deref().and_then(metadata::load) {
+        entry.provenance = Some(Provenance::from_metadata(&meta));
+    }
+}
+
+// Drop excluded transcripts and enrich the freshly generated dataset file in place,
+// keeping only the entries that match the dataset's filter
+pub fn postprocess(path: &Path, config: &DatasetConfig) -> Result<(usize, BTreeMap<String, usize>), String> {
+    let filter = config.filter.as_ref();
+    let mut entries = load_entries(path)?;
+
+    let state = state::load()?;
+    entries.retain(|e| {
+        e.source.as_ref().is_none_or(|s| {
+            !state.excluded_from_dataset.contains(s)
+                && !state.screening.get(s).is_some_and(|r| r.blocks_dataset())
+        })
+    });
+    flagging::apply(&mut entries, &state.segment_flags);
+    optouts::apply(&mut entries, &state.opt_outs);
+
+    // Before annotation, so topics, language and quality see the cleaned text
+    let workspace_settings = settings::load();
+    let stripped = boilerplate::strip(&mut entries, &workspace_settings.boilerplate);
+    if workspace_settings.normalization.enabled {
+        for entry in entries.iter_mut() {
+            entry.input = numerals::normalize(&entry.input, workspace_settings.normalization.direction);
+            entry.output = numerals::normalize(&entry.output, workspace_settings.normalization.direction);
+        }
+    }
+
+    for entry in entries.iter_mut() {
+        annotate(entry);
+        attach_provenance(entry);
+        permissions::attach(entry, &state);
+        if filter.is_some() {
+            attach_tags(entry, &state);
+            attach_speaker(entry, &state);
+        }
+    }
+    quality::score_entries(&mut entries, false);
+
+    for entry in entries.iter_mut() {
+        entry.input = glossary::apply(&entry.input, &workspace_settings.glossary).0;
+        entry.output = glossary::apply(&entry.output, &workspace_settings.glossary).0;
+    }
+
+    if config.references {
+        for entry in entries.iter_mut() {
+            attach_references(entry, &state);
+        }
+    }
+
+    if let Some(filter) = filter {
+        entries.retain(|entry| filter.matches(entry));
+    }
+
+    save_entries(path, &entries)?;
+    Ok((entries.len(), stripped))
+}
+
+// Attribute the entry to the speaker assigned to its source transcript
+pub fn attach_speaker(entry: &mut DatasetEntry, state: &state::PipelineState) {
+    if entry.speaker.is_none() {
+        entry.speaker = entry
+            .source
+            .as_ref()
+            .and_then(|s| state.transcript_speakers.get(s))
+            .cloned();
+    }
+}
+
+// Reject names that would escape the output directory
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(i18n::message("error-invalid-name", &[("name", name)]));
+    }
+
+    Ok(())
+}
+
+// Command to export a filtered subset of the dataset to output/json/subsets
+#[tauri::command]
+pub fn export_dataset_subset(
+    filter: DatasetFilter,
+    name: String,
+    options: Option<ExportOptions>,
+    dataset: Option<String>,
+) -> Result<serde_json::Value, String> {
+    telemetry::record_usage("export_dataset_subset");
+    let options = options.unwrap_or_default();
+    validate_name(&name)?;
+
+    let source = dataset_path(dataset.as_deref())?;
+    if !storage().exists(&source) {
+        return Err("JSON file not found".to_string());
+    }
+
+    let mut entries = load_entries(&source)?;
+    let total = entries.len();
+
+    let state = state::load()?;
+    for entry in entries.iter_mut() {
+        annotate(entry);
+        attach_tags(entry, &state);
+        attach_speaker(entry, &state);
+        attach_provenance(entry);
+        permissions::attach(entry, &state);
+    }
+    entries.retain(|entry| filter.matches(entry));
+
+    if let Some(balance) = &options.balance {
+        entries = balance_entries(entries, balance);
+    }
+
+    if options.include_citations {
+        for entry in entries.iter_mut() {
+            attach_citations(entry, &state);
+        }
+    }
+
+    if options.anonymize {
+        anonymize_entries(&mut entries)?;
+    }
+
+    let target = storage()
+        .path("output/json/subsets")
+        .join(format!("{}.json", name));
+    save_entries(&target, &entries)?;
+    let parameters = serde_json::json!({ "name": name, "filter": filter, "options": options });
+    manifest::record("subset", dataset.as_deref().unwrap_or(DEFAULT_DATASET), &target, entries.len(), parameters)?;
+
+    Ok(serde_json::json!({
+        "path": target.to_string_lossy(),
+        "manifest": manifest::manifest_path(&target).to_string_lossy(),
+        "total": total,
+        "exported": entries.len()
+    }))
+}
+
+// Command to summarize where dataset entries came from, for publishing credits
+#[tauri::command]
+pub fn export_attribution_report(dataset: Option<String>) -> Result<serde_json::Value, String> {
+    let source = dataset_path(dataset.as_deref())?;
+    if !storage().exists(&source) {
+        return Err("JSON file not found".to_string());
+    }
+
+    let mut entries = load_entries(&source)?;
+    entries.iter_mut().for_each(attach_provenance);
+    let (markdown, report) = attribution(&entries);
+
+    let target = source.with_extension("attribution.md");
+    storage().write(&target, markdown)
+        .map_err(|e| format!("Failed to write attribution report: {}", e))?;
+
+    Ok(serde_json::json!({
+        "path": target.to_string_lossy(),
+        "sources": report
+    }))
+}
+
+// Attribution table as markdown plus one JSON row per source; entries need provenance attached
+pub fn attribution(entries: &[DatasetEntry]) -> (String, Vec<serde_json::Value>) {
+    // Group entries by source transcript, keeping first-seen order
+    let mut sources: Vec<(String, Option<Provenance>, usize)> = Vec::new();
+    for entry in entries {
+        let name = entry.source.clone().unwrap_or_else(|| "unknown".to_string());
+        match sources.iter_mut().find(|(n, _, _)| *n == name) {
+            Some((_, _, count)) => *count += 1,
+            None => sources.push((name, entry.provenance.clone(), 1)),
+        }
+    }
+
+    let mut markdown = String::from("# Dataset Attribution\n\n| Source | Channel | URL | License | Entries |\n|---|---|---|---|---|\n");
+    let mut report = Vec::new();
+    for (name, provenance, count) in &sources {
+        let provenance = provenance.clone().unwrap_or_default();
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            name,
+            provenance.channel.as_deref().unwrap_or("-"),
+            provenance.video_url.as_deref().unwrap_or("-"),
+            provenance.license.as_deref().unwrap_or("unknown"),
+            count
+        ));
+        report.push(serde_json::json!({
+            "source": name,
+            "channel": provenance.channel,
+            "video_url": provenance.video_url,
+            "license": provenance.license,
+            "entries": count
+        }));
+    }
+    (markdown, report)
+}
+
+// Command to remove entries from the dataset by id; the removal can be undone
+#[tauri::command]
+pub fn delete_dataset_entries(ids: Vec<String>, dataset: Option<String>) -> Result<usize, String> {
+    let path = dataset_path(dataset.as_deref())?;
+    if !storage().exists(&path) {
+        return Err("JSON file not found".to_string());
+    }
+
+    let mut entries = load_entries(&path)?;
+    let before = entries.len();
+    entries.iter_mut().for_each(annotate);
+    entries.retain(|e| !ids.contains(&e.id));
+    let removed = before - entries.len();
+
+    if removed > 0 {
+        let mut journal = journal::begin("dataset", &format!("Remove {} dataset entries", removed))?;
+        journal.preserve(&path)?;
+        save_entries(&path, &entries)?;
+        journal.commit()?;
+    }
+
+    Ok(removed)
+}
+
+// Fields of a dataset entry that can be edited; missing fields are left unchanged
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct EntryUpdate {
+    pub instruction: Option<String>,
+    pub input: Option<String>,
+    pub output: Option<String>,
+}
+
+// Command to edit a dataset entry. With `propagate`, input text changes are also applied to
+// the source transcript and its whisper segment, and refused when they cannot be; with
+// `preview`, nothing is written and the transcript diff is returned for confirmation.
+#[tauri::command]
+pub fn update_dataset_entry(
+    id: String,
+    update: EntryUpdate,
+    propagate: Option<bool>,
+    preview: Option<bool>,
+    dataset: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let path = dataset_path(dataset.as_deref())?;
+    if !storage().exists(&path) {
+        return Err("JSON file not found".to_string());
+    }
+
+    let mut entries = load_entries(&path)?;
+    entries.iter_mut().for_each(annotate);
+    let entry = entries
+        .iter_mut()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("Dataset entry not found: {}", id))?;
+
+    let change = match (&update.input, &entry.source) {
+        (Some(input), Some(source)) if propagate.unwrap_or(false) => {
+            roundtrip::propagate(source, &entry.input, input)?
+        }
+        (Some(_), None) if propagate.unwrap_or(false) => {
+            return Err(format!("Dataset entry has no source transcript: {}", id));
+        }
+        _ => None,
+    };
+
+    // The id stays put so tags and review state keep pointing at the entry
+    if let Some(instruction) = update.instruction {
+        entry.instruction = instruction;
+    }
+    if let Some(input) = update.input {
+        entry.input = input;
+        entry.topics = text::extract_topics(&entry.input);
+        entry.quality = None;
+    }
+    if let Some(output) = update.output {
+        entry.output = output;
+    }
+    quality::score_entries(std::slice::from_mut(entry), false);
+    let updated = entry.clone();
+
+    if preview.unwrap_or(false) {
+        return Ok(serde_json::json!({ "entry": updated, "transcript": change, "applied": false }));
+    }
+
+    let mut journal = journal::begin("dataset", &format!("Edit dataset entry {}", id))?;
+    journal.preserve(&path)?;
+    if let Some(change) = &change {
+        let transcript_path = transcript::transcript_path(&change.file_name);
+        journal.preserve(&transcript_path)?;
+        storage().write(&transcript_path, &change.content)
+            .map_err(|e| format!("Failed to write transcript: {}", e))?;
+        integrity::record(&transcript_path);
+        transcript::snapshot(&change.file_name, &change.content);
+
+        if let Some(segments) = &change.segments {
+            let segments_path = transcript::segments_path(&change.file_name);
+            journal.preserve(&segments_path)?;
+            storage().write(&segments_path, segments)
+                .map_err(|e| format!("Failed to write segments: {}", e))?;
+        }
+    }
+    save_entries(&path, &entries)?;
+    integrity::record(&path);
+    journal.commit()?;
+
+    Ok(serde_json::json!({ "entry": updated, "transcript": change, "applied": true }))
+}
+
+// Configured datasets; the default dataset always exists
+pub fn dataset_configs() -> Vec<DatasetConfig> {
+    let mut configs = settings::load().datasets;
+    if !configs.iter().any(|d| d.id == DEFAULT_DATASET) {
+        configs.insert(0, DatasetConfig::default_dataset());
+    }
+    configs
+}
+
+// Transcripts that some generated dataset has entries from
+pub fn sources_in_datasets() -> BTreeSet<String> {
+    dataset_configs()
+        .iter()
+        .filter_map(|config| dataset_path(Some(&config.id)).ok())
+        .filter(|path| storage().exists(path))
+        .filter_map(|path| load_entries(&path).ok())
+        .flatten()
+        .filter_map(|entry| entry.source)
+        .collect()
+}
+
+// Command to list the configured datasets and whether each has been generated
+#[tauri::command]
+pub fn get_datasets() -> Result<serde_json::Value, String> {
+    let datasets: Vec<serde_json::Value> = dataset_configs()
+        .into_iter()
+        .map(|config| {
+            let exists = dataset_path(Some(&config.id)).is_ok_and(|p| storage().exists(&p));
+            serde_json::json!({ "id": config.id, "path": config.path, "filter": config.filter, "persona": config.persona, "references": config.references, "exists": exists })
+        })
+        .collect();
+
+    Ok(serde_json::json!({ "datasets": datasets }))
+}
+
+// Entries sent to the webview per page by default, and at most
+pub const PAGE_SIZE: usize = 100;
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+// Command to get a slice of a dataset's entries without sending the whole file to the webview
+#[tauri::command]
+pub fn get_json_slice(
+    offset: usize,
+    limit: usize,
+    dataset: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let path = dataset_path(dataset.as_deref())?;
+    if !storage().exists(&path) {
+        return Err("JSON file not found".to_string());
+    }
+
+    // Never more than one bounded page, so the webview is not sent a whole dataset
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let end = offset.saturating_add(limit);
+    let mut entries: Vec<serde_json::Value> = Vec::new();
+    let total = stream_entries(&path, |i| i >= offset && i < end, |_, entry| {
+        entries.push(entry);
+        true
+    })?;
+
+    Ok(serde_json::json!({
+        "entries": entries,
+        "offset": offset,
+        "limit": limit,
+        "total": total
+    }))
+}
+
+// Command to get a single dataset entry by id
+#[tauri::command]
+pub fn get_json_entry(id: String, dataset: Option<String>) -> Result<serde_json::Value, String> {
+    let path = dataset_path(dataset.as_deref())?;
+    if !storage().exists(&path) {
+        return Err("JSON file not found".to_string());
+    }
+
+    let mut found = None;
+    stream_entries(&path, |_| true, |index, mut entry: DatasetEntry| {
+        annotate(&mut entry);
+        if entry.id == id {
+            found = Some(serde_json::json!({ "index": index, "entry": entry }));
+        }
+        found.is_none()
+    })?;
+
+    found.ok_or_else(|| format!("Dataset entry not found: {}", id))
+}
@@ -0,0 +1,165 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+
+use crate::clips;
+use crate::http;
+use crate::jobs;
+use crate::metadata::ClipMetadata;
+use crate::naming;
+use crate::partial;
+use crate::pipeline::{Download, Invocation, ToolOutput};
+use crate::text;
+use crate::storage::storage;
+
+// Attempts per download; each one resumes from the bytes already on disk
+const ATTEMPTS: usize = 3;
+
+// Extensions of links fetched directly instead of through yt-dlp
+const MEDIA_EXTENSIONS: &[&str] = &["mp3", "m4a", "aac", "wav", "ogg", "oga", "opus", "flac", "mp4", "m4v", "webm", "mkv"];
+
+// Path of a URL without its query or fragment
+fn url_path(link: &str) -> Option<&str> {
+    let rest = link.strip_prefix("https://").or_else(|| link.strip_prefix("http://"))?;
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    rest.find('/').map(|slash| &rest[slash..])
+}
+
+fn host(link: &str) -> Option<&str> {
+    let rest = link.strip_prefix("https://").or_else(|| link.strip_prefix("http://"))?;
+    rest.split(['/', '?', '#']).next().filter(|h| !h.is_empty())
+}
+
+// True for a plain HTTP link to a media file, e.g. an archive.org /download/ link
+pub fn is_direct_media(link: &str) -> bool {
+    url_path(link)
+        .and_then(|path| path.rsplit_once('.'))
+        .is_some_and(|(_, ext)| MEDIA_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+// Download `link` into `partial`, asking for the remaining bytes with a Range request when part
+// of it is already there
+fn fetch(client: &Client, link: &str, partial: &Path) -> Result<(), String> {
+    let have = storage().metadata(partial).map_or(0, |m| m.len());
+    let mut request = client.get(link);
+    if have > 0 {
+        request = request.header(RANGE, format!("bytes={}-", have));
+    }
+    let mut response = request.send().map_err(|e| format!("Failed to download {}: {}", link, e))?;
+
+    let status = response.status();
+    // Servers answer a range starting at the end of the file with 416 and its size: "bytes */<size>"
+    if status == StatusCode::RANGE_NOT_SATISFIABLE && have > 0 {
+        let total = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("bytes */"))
+            .and_then(|v| v.parse::<u64>().ok());
+        if total == Some(have) {
+            return Ok(());
+        }
+        storage().remove_file(partial).ok();
+        return Err(format!("Partial download of {} no longer matches the file; starting over", link));
+    }
+    if !status.is_success() {
+        // Worded like yt-dlp so ratelimit::is_throttled recognizes a 429
+        return Err(format!("HTTP Error {}: {}", status.as_u16(), status.canonical_reason().unwrap_or_default()));
+    }
+    // A server that ignores the range sends the whole file again
+    if status != StatusCode::PARTIAL_CONTENT {
+        storage().write(partial, []).map_err(|e| format!("Failed to write download: {}", e))?;
+    }
+
+    let mut file = storage().append(partial).map_err(|e| format!("Failed to write download: {}", e))?;
+    let job = jobs::current();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        if job.as_deref().is_some_and(jobs::kill_requested) {
+            return Err(format!("Download of {} was killed", link));
+        }
+        let read = response.read(&mut buffer).map_err(|e| format!("Failed to download {}: {}", link, e))?;
+        if read == 0 {
+            return Ok(());
+        }
+        file.write_all(&buffer[..read]).map_err(|e| format!("Failed to write download: {}", e))?;
+        if let Some(job) = &job {
+            jobs::heartbeat(job);
+        }
+    }
+}
+
+// Fetch a direct media link, resuming an earlier partial download, into an mp3 clip
+pub fn download(link: &str) -> Result<Download, String> {
+    let path = url_path(link).ok_or_else(|| format!("Not a direct media link: {}", link))?;
+    let file_name = http::percent_decode(path.rsplit('/').next().unwrap_or_default());
+    let (title, extension) = file_name.rsplit_once('.').unwrap_or((&file_name, "mp3"));
+    let id = text::fingerprint(link.bytes());
+
+    // Kept between attempts so an interrupted download continues where it stopped
+    let partial_dir = partial::direct_dir();
+    storage().create_dir_all(&partial_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let partial = partial_dir.join(format!("{}.{}", id, extension.to_lowercase()));
+
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .timeout(None)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut errors = Vec::new();
+    while let Err(e) = fetch(&client, link, &partial) {
+        errors.push(e);
+        if errors.len() == ATTEMPTS || jobs::current().as_deref().is_some_and(jobs::kill_requested) {
+            let output = ToolOutput { success: false, stdout: String::new(), stderr: errors.join("\n") };
+            return Ok(Download { output, clips: Vec::new() });
+        }
+    }
+
+    let meta = ClipMetadata {
+        id: Some(id.clone()),
+        title: Some(title.to_string()),
+        webpage_url: Some(link.to_string()),
+        uploader: host(link).map(str::to_string),
+        ..Default::default()
+    };
+    let rendered = naming::render(&naming::template(), &meta, title)?;
+    let (folder, stem) = naming::split(&rendered);
+    let mut stem = stem.to_string();
+    // Different files often share a name such as "01.mp3"
+    if clips::list_clips().contains(&format!("{}.mp3", stem)) {
+        stem = format!("{} [{}]", stem, id);
+    }
+    let dir = clips::clips_dir().join(folder);
+    storage().create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let clip = format!("{}.mp3", stem);
+    let clip_path = dir.join(&clip);
+
+    if extension.eq_ignore_ascii_case("mp3") {
+        storage().rename(&partial, &clip_path).map_err(|e| format!("Failed to move download: {}", e))?;
+    } else {
+        let converted = Invocation::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-i"])
+            .arg(&partial)
+            .args(["-vn", "-codec:a", "libmp3lame", "-q:a", "2"])
+            .arg(&clip_path)
+            .run()
+            .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        if !converted.success {
+            return Ok(Download { output: converted, clips: Vec::new() });
+        }
+        storage().remove_file(&partial).ok();
+    }
+
+    // Same shape as yt-dlp's info JSON; the source URL is what duplicates and provenance use
+    let mut info = serde_json::to_value(&meta).map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    info["extractor"] = serde_json::json!("direct");
+    storage().write(dir.join(format!("{}.info.json", stem)), info.to_string())
+        .map_err(|e| format!("Failed to write clip metadata: {}", e))?;
+
+    let output = ToolOutput { success: true, stdout: format!("Downloaded {}\n", clip), stderr: String::new() };
+    Ok(Download { output, clips: vec![clip] })
+}
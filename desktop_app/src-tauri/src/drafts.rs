@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::metadata;
+use crate::state;
+use crate::transcript;
+use crate::workspace;
+use crate::storage::storage;
+
+// Unsaved editor content kept apart from the canonical transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    pub file_name: String,
+    pub content: String,
+    // Revision of the transcript the draft was started from
+    pub base_revision: Option<String>,
+    pub saved_at: u64,
+}
+
+fn drafts_dir() -> PathBuf {
+    storage().path("output/drafts")
+}
+
+fn draft_path(file_name: &str) -> PathBuf {
+    drafts_dir().join(format!("{}.json", metadata::stem(file_name)))
+}
+
+pub fn load(file_name: &str) -> Option<Draft> {
+    let content = storage().read_to_string(draft_path(file_name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// Remove the draft of a transcript once its content has been saved
+pub fn discard(file_name: &str) {
+    storage().remove_file(draft_path(file_name)).ok();
+}
+
+// Command to autosave editor content without touching the transcript
+#[tauri::command]
+pub fn save_transcript_draft(
+    file_name: String,
+    content: String,
+    base_revision: Option<String>,
+) -> Result<u64, String> {
+    workspace::ensure_writable()?;
+    if !storage().exists(transcript::transcript_path(&file_name)) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    storage().create_dir_all(drafts_dir())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let draft = Draft { file_name: file_name.clone(), content, base_revision, saved_at: state::now() };
+    let json = serde_json::to_string(&draft)
+        .map_err(|e| format!("Failed to serialize draft: {}", e))?;
+
+    // Write then rename so a crash mid-write never loses the previous draft
+    let path = draft_path(&file_name);
+    let temp = path.with_extension("json.tmp");
+    storage().write(&temp, json).map_err(|e| format!("Failed to write draft: {}", e))?;
+    storage().rename(&temp, &path).map_err(|e| format!("Failed to write draft: {}", e))?;
+
+    Ok(draft.saved_at)
+}
+
+// Command to list drafts left over from a previous session, for recovery on startup
+#[tauri::command]
+pub fn get_transcript_drafts() -> Result<Vec<Draft>, String> {
+    let mut drafts: Vec<Draft> = storage().read_dir(drafts_dir())
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|e| storage().read_to_string(e.path()).ok())
+                .filter_map(|content| serde_json::from_str::<Draft>(&content).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // A draft identical to the saved transcript has nothing to recover
+    drafts.retain(|draft| {
+        storage().read_to_string(transcript::transcript_path(&draft.file_name))
+            .map_or(true, |current| current != draft.content)
+    });
+    drafts.sort_by_key(|d| std::cmp::Reverse(d.saved_at));
+
+    Ok(drafts)
+}
+
+// Command to drop a draft without applying it
+#[tauri::command]
+pub fn discard_transcript_draft(file_name: String) -> Result<(), String> {
+    discard(&file_name);
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::clips;
+use crate::events::Stage;
+use crate::jobs::{self, JobStatus};
+use crate::metadata;
+use crate::state;
+
+// Why a submitted link counts as a duplicate
+#[derive(Debug, Clone, Serialize)]
+pub struct Duplicate {
+    pub link: String,
+    // "downloaded", "queued" or "submitted" (repeated within the same submission)
+    pub reason: String,
+    pub clip: Option<String>,
+}
+
+// Canonical form of a link: the video id for YouTube URLs, otherwise the trimmed URL
+pub fn canonical(link: &str) -> String {
+    let link = link.trim();
+    let rest = link.split_once("://").map_or(link, |(_, rest)| rest);
+    let rest = rest.strip_prefix("www.").or_else(|| rest.strip_prefix("m.")).unwrap_or(rest);
+
+    let id = if let Some(path) = rest.strip_prefix("youtu.be/") {
+        path.split(['?', '&', '#', '/']).next()
+    } else if let Some(path) = rest.strip_prefix("youtube.com/") {
+        if let Some(query) = path.strip_prefix("watch?") {
+            query.split('&').find_map(|pair| pair.strip_prefix("v="))
+        } else {
+            path.strip_prefix("shorts/")
+                .or_else(|| path.strip_prefix("live/"))
+                .and_then(|p| p.split(['?', '&', '#', '/']).next())
+        }
+    } else {
+        None
+    };
+
+    match id {
+        Some(id) if !id.is_empty() => format!("youtube:{}", id),
+        _ => link.trim_end_matches('/').to_string(),
+    }
+}
+
+// Links already downloaded, as canonical link and clip file name
+fn archive() -> Result<Vec<(String, Option<String>)>, String> {
+    let mut archive: Vec<(String, Option<String>)> = clips::list_clips()
+        .into_iter()
+        .filter_map(|clip| {
+            let url = metadata::load(&clip)?.webpage_url?;
+            Some((canonical(&url), Some(clip)))
+        })
+        .collect();
+
+    let state = state::load()?;
+    for record in state.download_history {
+        if record.outcome == "success" {
+            archive.push((canonical(&record.url), record.clips.first().cloned()));
+        }
+    }
+
+    // Archived clips and cold storage items no longer have an mp3 but their info JSON stays
+    let stored = state.cold_items.into_keys().map(|stem| format!("{}.mp3", stem));
+    for clip in state.archived_clips.into_keys().chain(stored) {
+        if let Some(url) = metadata::load(&clip).and_then(|m| m.webpage_url) {
+            archive.push((canonical(&url), Some(clip)));
+        }
+    }
+    Ok(archive)
+}
+
+// Links still waiting in running download jobs
+fn queued() -> BTreeSet<String> {
+    jobs::running(Stage::Download)
+        .into_iter()
+        .filter(|job| job.status == JobStatus::Running)
+        .flat_map(|job| job.items.into_iter().skip(job.completed))
+        .map(|link| canonical(&link))
+        .collect()
+}
+
+// Split submitted links into new ones and duplicates
+pub fn find(links: Vec<String>) -> Result<(Vec<String>, Vec<Duplicate>), String> {
+    let archive = archive()?;
+    let queued = queued();
+    let mut seen = BTreeSet::new();
+
+    let mut fresh = Vec::new();
+    let mut duplicates = Vec::new();
+    for link in links {
+        let key = canonical(&link);
+        if let Some((_, clip)) = archive.iter().find(|(url, _)| *url == key) {
+            duplicates.push(Duplicate { link, reason: "downloaded".to_string(), clip: clip.clone() });
+        } else if queued.contains(&key) {
+            duplicates.push(Duplicate { link, reason: "queued".to_string(), clip: None });
+        } else if !seen.insert(key) {
+            duplicates.push(Duplicate { link, reason: "submitted".to_string(), clip: None });
+        } else {
+            fresh.push(link);
+        }
+    }
+    Ok((fresh, duplicates))
+}
+
+// Command to report which links were already downloaded or are already queued
+#[tauri::command]
+pub fn check_duplicate_links(links: Vec<String>) -> Result<Vec<Duplicate>, String> {
+    Ok(find(links)?.1)
+}
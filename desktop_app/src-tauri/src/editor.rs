@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::AppHandle;
+
+use crate::events::{self, PipelineEvent};
+use crate::settings;
+use crate::storage::storage;
+use crate::transcript;
+
+// How often the watched transcript is checked for external saves
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+// Watching stops after this long without a change
+const WATCH_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+
+// Transcripts that currently have a watcher thread
+static WATCHED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+// Editor command from settings or the environment, if any
+fn editor_command() -> Option<String> {
+    settings::load()
+        .editor
+        .filter(|e| !e.trim().is_empty())
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+}
+
+// Launch the configured editor, or the OS default handler for text files
+fn launch(path: &std::path::Path) -> Result<(), String> {
+    let mut command = match editor_command() {
+        Some(editor) => {
+            // Allow editor commands with arguments, e.g. "code --wait"
+            let mut parts = editor.split_whitespace();
+            let mut command = Command::new(parts.next().unwrap_or_default());
+            command.args(parts);
+            command
+        }
+        // Never `cmd /C start`: cmd would run `&` and `|` in the path, which comes from video titles
+        None if cfg!(target_os = "windows") => Command::new("explorer.exe"),
+        None if cfg!(target_os = "macos") => Command::new("open"),
+        None => Command::new("xdg-open"),
+    };
+
+    command
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to launch editor: {}", e))
+}
+
+fn modified(path: &std::path::Path) -> Option<SystemTime> {
+    storage().metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// Poll the transcript and emit an event each time an external save lands
+fn watch(app: AppHandle, file_name: String) {
+    let path = transcript::transcript_path(&file_name);
+    let mut last_modified = modified(&path);
+    let mut last_change = Instant::now();
+
+    while last_change.elapsed() < WATCH_TIMEOUT {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = modified(&path);
+        if current.is_none() {
+            break;
+        }
+        if current != last_modified {
+            last_modified = current;
+            last_change = Instant::now();
+
+            if let Ok(content) = storage().read_to_string(&path) {
+                let revision = transcript::snapshot(&file_name, &content);
+                events::emit(&app, PipelineEvent::TranscriptChanged { file_name: file_name.clone(), revision });
+            }
+        }
+    }
+
+    WATCHED.lock().unwrap_or_else(|e| e.into_inner()).remove(&file_name);
+}
+
+// Command to open a transcript in the external editor and watch it for saves
+#[tauri::command]
+pub fn open_in_editor(app: AppHandle, file_name: String) -> Result<(), String> {
+    let path = transcript::transcript_path(&file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    launch(&path)?;
+
+    let mut watched = WATCHED.lock().unwrap_or_else(|e| e.into_inner());
+    if watched.insert(file_name.clone()) {
+        thread::spawn(move || watch(app, file_name));
+    }
+
+    Ok(())
+}
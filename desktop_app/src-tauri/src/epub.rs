@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use crate::dataset;
+use crate::live;
+use crate::metadata;
+use crate::sharing::escape;
+use crate::state;
+use crate::text;
+use crate::transcript;
+use crate::translation;
+use crate::zip::ZipWriter;
+use crate::storage::storage;
+
+const CONTAINER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+struct Chapter {
+    id: String,
+    title: String,
+    xhtml: String,
+}
+
+fn xhtml(title: &str, language: &str, body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"{}\">\n\
+         <head><title>{}</title></head>\n<body>\n{}\n</body>\n</html>\n",
+        language,
+        escape(title),
+        body
+    )
+}
+
+// Paragraphs from the transcript's own line breaks
+fn paragraphs(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| format!("<p>{}</p>", escape(l)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn chapter(index: usize, file: &str, include_translation: bool) -> Result<Chapter, String> {
+    dataset::validate_name(file)?;
+    let content = storage().read_to_string(transcript::transcript_path(file))
+        .map_err(|e| format!("Failed to read transcript {}: {}", file, e))?;
+    let meta = metadata::load(file).unwrap_or_default();
+    let title = meta.title.clone().unwrap_or_else(|| metadata::stem(file).to_string());
+    let language = text::detect_language(&content);
+
+    let mut body = format!("<h1>{}</h1>", escape(&title));
+    let details: Vec<&str> = [meta.channel_name(), meta.upload_date.as_deref(), meta.webpage_url.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect();
+    if !details.is_empty() {
+        body.push_str(&format!("\n<p><small>{}</small></p>", escape(&details.join(" · "))));
+    }
+
+    let direction = if language == "ar" { " dir=\"rtl\"" } else { "" };
+    body.push_str(&format!("\n<section{}>\n{}\n</section>", direction, paragraphs(&content)));
+
+    if include_translation {
+        if let Some(segments) = translation::load_translation(file) {
+            let english: Vec<&str> = segments.iter().map(|s| s.text.trim()).collect();
+            body.push_str(&format!("\n<h2>English translation</h2>\n<section>\n<p>{}</p>\n</section>", escape(&english.join(" "))));
+        }
+    }
+
+    Ok(Chapter { id: format!("chapter{}", index + 1), title: title.clone(), xhtml: xhtml(&title, language, &body) })
+}
+
+fn package(title: &str, identifier: &str, language: &str, creators: &[String], chapters: &[Chapter]) -> String {
+    let creators: String = creators.iter().map(|c| format!("\n    <dc:creator>{}</dc:creator>", escape(c))).collect();
+    let manifest: String = chapters
+        .iter()
+        .map(|c| format!("\n    <item id=\"{0}\" href=\"{0}.xhtml\" media-type=\"application/xhtml+xml\"/>", c.id))
+        .collect();
+    let spine: String = chapters.iter().map(|c| format!("\n    <itemref idref=\"{}\"/>", c.id)).collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="book-id">urn:akhi:{identifier}</dc:identifier>
+    <dc:title>{title}</dc:title>
+    <dc:language>{language}</dc:language>{creators}
+    <meta property="dcterms:modified">{modified}</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>{manifest}
+  </manifest>
+  <spine>{spine}
+  </spine>
+</package>
+"#,
+        identifier = identifier,
+        title = escape(title),
+        language = language,
+        creators = creators,
+        modified = modified(),
+        manifest = manifest,
+        spine = spine
+    )
+}
+
+// Current UTC time as an ISO 8601 timestamp, as EPUB requires for dcterms:modified
+fn modified() -> String {
+    let now = state::now();
+    let date = live::date_stamp(now);
+    let seconds = now % 86400;
+    format!(
+        "{}-{}-{}T{:02}:{:02}:{:02}Z",
+        &date[..4],
+        &date[4..6],
+        &date[6..],
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn navigation(title: &str, chapters: &[Chapter]) -> String {
+    let items: String = chapters
+        .iter()
+        .map(|c| format!("\n<li><a href=\"{}.xhtml\">{}</a></li>", c.id, escape(&c.title)))
+        .collect();
+    xhtml(title, "en", &format!("<nav epub:type=\"toc\"><h1>{}</h1><ol>{}\n</ol></nav>", escape(title), items))
+}
+
+// Command to compile transcripts into an EPUB with one chapter per lecture, written to
+// output/books unless a path is given
+#[tauri::command]
+pub fn export_epub(
+    selection: Vec<String>,
+    title: String,
+    include_translations: Option<bool>,
+    path: Option<String>,
+) -> Result<String, String> {
+    if selection.is_empty() {
+        return Err("Select at least one transcript".to_string());
+    }
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("The book needs a title".to_string());
+    }
+
+    let chapters = selection
+        .iter()
+        .enumerate()
+        .map(|(i, file)| chapter(i, file, include_translations.unwrap_or(false)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut creators: Vec<String> = Vec::new();
+    for file in &selection {
+        if let Some(name) = metadata::load(file).and_then(|m| m.channel_name().map(str::to_string)) {
+            if !creators.contains(&name) {
+                creators.push(name);
+            }
+        }
+    }
+    let identifier = text::fingerprint(selection.join("\n").bytes().chain(title.bytes()));
+    // The book's language follows the majority of its chapters
+    let arabic = chapters.iter().filter(|c| c.xhtml.contains("xml:lang=\"ar\"")).count();
+    let language = if arabic * 2 > chapters.len() { "ar" } else { "en" };
+
+    // The mimetype entry must come first and uncompressed
+    let mut zip = ZipWriter::default();
+    zip.add("mimetype", b"application/epub+zip")?;
+    zip.add("META-INF/container.xml", CONTAINER.as_bytes())?;
+    zip.add("OEBPS/content.opf", package(&title, &identifier, language, &creators, &chapters).as_bytes())?;
+    zip.add("OEBPS/nav.xhtml", navigation(&title, &chapters).as_bytes())?;
+    for chapter in &chapters {
+        zip.add(&format!("OEBPS/{}.xhtml", chapter.id), chapter.xhtml.as_bytes())?;
+    }
+
+    // The target is picked by the user, so it is granted for this export only
+    let _grant = path.as_ref().map(|p| storage().allow(p)).transpose().map_err(|e| format!("Failed to access target: {}", e))?;
+    let target = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let name: String = title.chars().map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' }).collect();
+            storage().path("output/books").join(format!("{}.epub", name))
+        }
+    };
+    if let Some(parent) = target.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    storage().write(&target, zip.finish()).map_err(|e| format!("Failed to write EPUB: {}", e))?;
+    Ok(target.to_string_lossy().to_string())
+}
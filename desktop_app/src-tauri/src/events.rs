@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::autorebuild::RebuildDelta;
+use crate::crash;
+use crate::i18n;
+use crate::jobs;
+use crate::notify;
+use crate::telemetry;
+
+// Single event channel the frontend subscribes to for all pipeline activity
+pub const PIPELINE_EVENT: &str = "pipeline://event";
+
+// Pipeline stage an event originates from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Download,
+    Transcribe,
+    Dataset,
+    Analysis,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    Started,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Info,
+    Warn,
+}
+
+// Payload of `pipeline://event`, tagged by `type` so the frontend can switch on it
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PipelineEvent {
+    Progress {
+        stage: Stage,
+        current: usize,
+        total: usize,
+        item: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        job_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        eta_seconds: Option<u64>,
+    },
+    StageChange {
+        stage: Stage,
+        status: StageStatus,
+    },
+    Error {
+        stage: Stage,
+        message: String,
+        item: Option<String>,
+    },
+    Log {
+        stage: Stage,
+        level: LogLevel,
+        message: String,
+    },
+    // A transcript was saved outside the app, e.g. by an external editor
+    TranscriptChanged {
+        file_name: String,
+        revision: String,
+    },
+    // A dataset was regenerated automatically after transcripts were approved
+    DatasetRebuilt {
+        dataset: String,
+        delta: RebuildDelta,
+    },
+    // Text of one chunk of a live microphone transcription
+    LiveTranscript {
+        session: String,
+        chunk: usize,
+        // Offset of the chunk from the start of the recording, in seconds
+        start: f64,
+        text: String,
+    },
+    // A job's tool produced no output for longer than the stall timeout
+    JobStalled {
+        job_id: String,
+        stage: Stage,
+        item: Option<String>,
+        idle_seconds: u64,
+    },
+}
+
+// Emit an event to every window; delivery failures are not fatal to the pipeline
+pub fn emit(app: &AppHandle, event: PipelineEvent) {
+    crash::remember(&event);
+    notify::observe(app, &event);
+    app.emit_all(PIPELINE_EVENT, event).ok();
+}
+
+pub fn stage_change(app: &AppHandle, stage: Stage, status: StageStatus) {
+    if status == StageStatus::Started {
+        telemetry::record_stage(stage);
+    }
+    emit(app, PipelineEvent::StageChange { stage, status });
+}
+
+// Progress of a batch job, including its estimated time remaining
+pub fn job_progress(app: &AppHandle, job_id: &str) {
+    if let Some(job) = jobs::get(job_id) {
+        emit(
+            app,
+            PipelineEvent::Progress {
+                stage: job.stage,
+                current: job.completed,
+                total: job.items.len(),
+                item: job.current_item,
+                job_id: Some(job.id),
+                eta_seconds: job.eta_seconds,
+            },
+        );
+    }
+}
+
+pub fn error(app: &AppHandle, stage: Stage, message: &str, item: Option<&str>) {
+    telemetry::record_error(stage, message);
+    emit(
+        app,
+        PipelineEvent::Error { stage, message: i18n::localize(message), item: item.map(str::to_string) },
+    );
+}
+
+pub fn log(app: &AppHandle, stage: Stage, level: LogLevel, message: &str) {
+    emit(app, PipelineEvent::Log { stage, level, message: message.to_string() });
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use crate::clips;
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::render;
+use crate::scheduler::{self, Resource};
+use crate::state;
+use crate::subtitles;
+use crate::transcript;
+use crate::storage::storage;
+
+// Link to the source video at the excerpt's start, for platforms that support it
+fn timestamped_url(url: &str, start: f64) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}t={}", url, separator, start as u64)
+}
+
+// Command to cut an audio snippet with its transcript text and metadata into a bundle directory
+// (audio.mp3, transcript.txt, captions.srt, metadata.json) under `path` or output/excerpts
+#[tauri::command]
+pub fn export_excerpt(file: String, start: f64, end: f64, path: Option<String>) -> Result<serde_json::Value, String> {
+    if start < 0.0 || end <= start || !end.is_finite() {
+        return Err(format!("Invalid excerpt range: {} to {}", start, end));
+    }
+
+    let stem = metadata::stem(&file).to_string();
+    let audio = clips::media_file(&stem, "mp3");
+    if !storage().exists(&audio) {
+        return Err(format!("Clip not found for transcript: {}", file));
+    }
+
+    // The export directory is picked by the user, so it is granted for this export only
+    let _grant = path.as_ref().map(|p| storage().allow(p)).transpose().map_err(|e| format!("Failed to access target: {}", e))?;
+    let parent = match &path {
+        Some(path) => PathBuf::from(path),
+        None => storage().path("output/excerpts"),
+    };
+    let bundle = parent.join(format!("{}_{}-{}", stem, start as u64, end as u64));
+    storage().create_dir_all(&bundle).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let segments = render::excerpt_segments(subtitles::reviewed_segments(&file)?, start, end);
+    let text = segments.iter().map(|s| s.text.trim()).collect::<Vec<_>>().join("\n");
+
+    let _permit = scheduler::acquire(Resource::Cpu)?;
+    let output = Invocation::new("ffmpeg")
+        .args(["-y", "-hide_banner", "-loglevel", "error"])
+        .args(["-ss", &start.to_string(), "-t", &(end - start).to_string(), "-i"])
+        .arg(&audio)
+        .args(["-c:a", "libmp3lame", "-q:a", "2"])
+        .arg(bundle.join("audio.mp3"))
+        .run()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    if !output.success {
+        return Err(format!("Cutting the excerpt failed: {}", output.stderr.trim()));
+    }
+
+    let meta = metadata::load(&file).unwrap_or_default();
+    let info = serde_json::json!({
+        "transcript": file,
+        "title": meta.title,
+        "channel": meta.channel_name(),
+        "upload_date": meta.upload_date,
+        "license": meta.license,
+        "source_url": meta.webpage_url.as_deref().map(|url| timestamped_url(url, start)),
+        "start": start,
+        "end": end,
+        "review_status": transcript::review_status(&state::load()?, &file),
+        "segments": segments
+    });
+
+    let write = |name: &str, content: String| {
+        storage().write(bundle.join(name), content).map_err(|e| format!("Failed to write {}: {}", name, e))
+    };
+    write("transcript.txt", text)?;
+    write("captions.srt", subtitles::render(&segments, "srt"))?;
+    write(
+        "metadata.json",
+        serde_json::to_string_pretty(&info).map_err(|e| format!("Failed to serialize metadata: {}", e))?,
+    )?;
+
+    Ok(serde_json::json!({ "path": bundle.to_string_lossy(), "metadata": info }))
+}
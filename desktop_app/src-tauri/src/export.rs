@@ -0,0 +1,125 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::storage::storage;
+use crate::telemetry;
+use crate::transcript;
+
+// Render a transcript as Markdown with a metadata header
+pub fn to_markdown(file_name: &str, include_timestamps: bool) -> Result<String, String> {
+    let path = transcript::transcript_path(file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let meta = metadata::load(file_name).unwrap_or_default();
+
+    let title = meta
+        .title
+        .clone()
+        .unwrap_or_else(|| metadata::stem(file_name).to_string());
+    let mut md = format!("# {}\n\n", title);
+
+    if let Some(channel) = meta.channel_name() {
+        md.push_str(&format!("- **Channel:** {}\n", channel));
+    }
+    if let Some(url) = &meta.webpage_url {
+        md.push_str(&format!("- **Source:** {}\n", url));
+    }
+    if let Some(date) = &meta.upload_date {
+        md.push_str(&format!("- **Uploaded:** {}\n", date));
+    }
+    if let Some(duration) = meta.duration {
+        md.push_str(&format!("- **Duration:** {}\n", transcript::format_timestamp(duration)));
+    }
+    md.push_str(&format!("- **Words:** {}\n\n---\n\n", content.split_whitespace().count()));
+
+    // Timestamps need whisper segments; fall back to the plain text without them
+    match transcript::load_segments(file_name).filter(|_| include_timestamps) {
+        Some(segments) => {
+            for segment in segments {
+                md.push_str(&format!(
+                    "**[{}]** {}\n\n",
+                    transcript::format_timestamp(segment.start),
+                    segment.text.trim()
+                ));
+            }
+        }
+        None => {
+            md.push_str(content.trim());
+            md.push('\n');
+        }
+    }
+
+    Ok(md)
+}
+
+// Command to export transcripts as markdown, docx or pdf documents into a directory
+#[tauri::command]
+pub fn export_transcripts(
+    files: Vec<String>,
+    format: String,
+    path: String,
+    include_timestamps: Option<bool>,
+) -> Result<Vec<String>, String> {
+    if !["markdown", "docx", "pdf"].contains(&format.as_str()) {
+        return Err(format!("Unknown export format: {}", format));
+    }
+    telemetry::record_usage(&format!("export_transcripts.{}", format));
+
+    // The export directory is picked by the user, so it is granted for this export only
+    let target_dir = Path::new(&path);
+    let _grant = storage().allow(target_dir).map_err(|e| format!("Failed to access target: {}", e))?;
+    storage().create_dir_all(target_dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    if format == "markdown" {
+        let mut written = Vec::new();
+        for file_name in &files {
+            let md_path = target_dir.join(format!("{}.md", metadata::stem(file_name)));
+            storage().write(&md_path, to_markdown(file_name, include_timestamps.unwrap_or(false))?)
+                .map_err(|e| format!("Failed to write document: {}", e))?;
+            written.push(md_path.to_string_lossy().to_string());
+        }
+        return Ok(written);
+    }
+
+    // DOCX and PDF are rendered by pandoc from Markdown staged in a temporary directory,
+    // so nothing but the documents is ever written to the user's directory
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    let staging = std::env::temp_dir().join(format!("akhi-export-{}-{}", std::process::id(), nanos));
+    let _staging_grant = storage().allow(&staging).map_err(|e| format!("Failed to access staging directory: {}", e))?;
+    storage().create_dir_all(&staging)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let written = files
+        .iter()
+        .map(|file_name| render(file_name, &format, include_timestamps.unwrap_or(false), &staging, target_dir))
+        .collect();
+    storage().remove_dir_all(&staging).ok();
+    written
+}
+
+// Convert one transcript with pandoc via a Markdown file in `staging`
+fn render(file_name: &str, format: &str, include_timestamps: bool, staging: &Path, target_dir: &Path) -> Result<String, String> {
+    let stem = metadata::stem(file_name);
+    let md_path = staging.join(format!("{}.md", stem));
+    storage().write(&md_path, to_markdown(file_name, include_timestamps)?)
+        .map_err(|e| format!("Failed to write document: {}", e))?;
+
+    let out_path = target_dir.join(format!("{}.{}", stem, format));
+    let output = Invocation::new("pandoc")
+        .arg(&md_path)
+        .arg("-o")
+        .arg(&out_path)
+        .run()
+        .map_err(|e| format!("Failed to execute pandoc: {}", e))?;
+    if !output.success {
+        return Err(output.stderr);
+    }
+    Ok(out_path.to_string_lossy().to_string())
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::dataset::DatasetEntry;
+use crate::settings;
+use crate::state;
+use crate::transcript;
+
+// Segment-level flagging of sensitive or off-topic terms, stored in settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlaggingSettings {
+    pub enabled: bool,
+    // Matched case-insensitively as whole words or phrases
+    pub terms: Vec<String>,
+}
+
+// A transcript segment that matched a flagged term
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentFlag {
+    pub segment: usize,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    pub matched: Vec<String>,
+    // "flagged" until reviewed, then "approved" (kept) or "rejected" (left out of datasets)
+    pub status: String,
+    pub flagged_at: u64,
+}
+
+fn compile(terms: &[String]) -> Vec<(String, Regex)> {
+    terms
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .filter_map(|term| {
+            let words: Vec<String> = term.split_whitespace().map(regex::escape).collect();
+            let pattern = Regex::new(&format!(r"(?i)\b{}\b", words.join(r"\s+"))).ok()?;
+            Some((term.to_string(), pattern))
+        })
+        .collect()
+}
+
+// Flag matching segments of every transcript; decisions on unchanged segments are kept
+pub fn run(settings: &FlaggingSettings) -> Result<BTreeMap<String, Vec<SegmentFlag>>, String> {
+    let rules = compile(&settings.terms);
+    let mut results: BTreeMap<String, Vec<SegmentFlag>> = BTreeMap::new();
+    for file_name in transcript::list_transcripts() {
+        let Some(segments) = transcript::load_segments(&file_name) else { continue };
+        let flags: Vec<SegmentFlag> = segments
+            .iter()
+            .enumerate()
+            .filter_map(|(index, segment)| {
+                let matched: Vec<String> =
+                    rules.iter().filter(|(_, p)| p.is_match(&segment.text)).map(|(t, _)| t.clone()).collect();
+                (!matched.is_empty()).then(|| SegmentFlag {
+                    segment: index,
+                    start: segment.start,
+                    end: segment.end,
+                    text: segment.text.trim().to_string(),
+                    matched,
+                    status: "flagged".to_string(),
+                    flagged_at: state::now(),
+                })
+            })
+            .collect();
+        if !flags.is_empty() {
+            results.insert(file_name, flags);
+        }
+    }
+
+    state::update(|s| {
+        for (file_name, flags) in results.iter_mut() {
+            let previous = s.segment_flags.get(file_name).map(Vec::as_slice).unwrap_or_default();
+            for flag in flags.iter_mut() {
+                let same = previous.iter().find(|p| p.segment == flag.segment && p.text == flag.text && p.matched == flag.matched);
+                if let Some(same) = same {
+                    flag.status = same.status.clone();
+                    flag.flagged_at = same.flagged_at;
+                }
+            }
+        }
+        s.segment_flags = results.clone();
+    })?;
+
+    Ok(results)
+}
+
+// Flag before dataset generation when enabled
+pub fn run_if_enabled() -> Result<(), String> {
+    let settings = settings::load().flagging;
+    if settings.enabled {
+        run(&settings)?;
+    }
+    Ok(())
+}
+
+// Keep unreviewed and rejected segments out of the dataset: timed entries overlapping one are
+// dropped, whole-transcript entries lose rejected segments' text and wait for pending ones.
+// Returns the number of entries dropped.
+pub fn apply(entries: &mut Vec<DatasetEntry>, flags: &BTreeMap<String, Vec<SegmentFlag>>) -> usize {
+    let before = entries.len();
+    entries.retain_mut(|entry| {
+        let Some(flags) = entry.source.as_ref().and_then(|s| flags.get(s)) else { return true };
+        let blocking: Vec<&SegmentFlag> = flags.iter().filter(|f| f.status != "approved").collect();
+
+        if let (Some(start), Some(end)) = (entry.start, entry.end) {
+            return !blocking.iter().any(|f| f.start < end && f.end > start);
+        }
+        if blocking.iter().any(|f| f.status == "flagged") {
+            return false;
+        }
+        for flag in &blocking {
+            if !entry.input.contains(&flag.text) {
+                return false;
+            }
+            entry.input = entry.input.replace(&flag.text, " ").split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        !entry.input.is_empty()
+    });
+    before - entries.len()
+}
+
+// Command to flag transcript segments containing the configured terms
+#[tauri::command]
+pub fn run_segment_flagging() -> Result<BTreeMap<String, Vec<SegmentFlag>>, String> {
+    run(&settings::load().flagging)
+}
+
+// Command to list flagged segments waiting for review, oldest first
+#[tauri::command]
+pub fn get_flag_queue() -> Result<Vec<serde_json::Value>, String> {
+    let mut queue: Vec<serde_json::Value> = state::load()?
+        .segment_flags
+        .into_iter()
+        .flat_map(|(file_name, flags)| {
+            flags
+                .into_iter()
+                .filter(|f| f.status == "flagged")
+                .map(move |f| serde_json::json!({ "file_name": file_name, "flag": f }))
+        })
+        .collect();
+    queue.sort_by_key(|item| item["flag"]["flagged_at"].as_u64());
+    Ok(queue)
+}
+
+// Command to approve (include) or reject (leave out) a flagged segment
+#[tauri::command]
+pub fn review_segment_flag(file_name: String, segment: usize, decision: String) -> Result<(), String> {
+    let status = match decision.as_str() {
+        "approve" => "approved",
+        "reject" => "rejected",
+        _ => return Err(format!("Unknown flag decision: {}", decision)),
+    };
+
+    state::update(|s| {
+        let flag = s
+            .segment_flags
+            .get_mut(&file_name)
+            .and_then(|flags| flags.iter_mut().find(|f| f.segment == segment));
+        match flag {
+            Some(flag) => {
+                flag.status = status.to_string();
+                Ok(())
+            }
+            None => Err(format!("No flag for segment {} of {}", segment, file_name)),
+        }
+    })??;
+    audit::record("flag.review", &file_name, serde_json::json!({ "segment": segment, "status": status }));
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::dataset;
+use crate::settings;
+use crate::transcript;
+use crate::storage::storage;
+
+// A term with the spelling the corpus should use for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    // Arabic original, e.g. "تقوى"
+    pub term: String,
+    // Preferred transliteration, e.g. "taqwa"
+    pub preferred: String,
+    // Deviating spellings replaced by the preferred one
+    #[serde(default)]
+    pub variants: Vec<String>,
+    #[serde(default)]
+    pub translation: Option<String>,
+}
+
+pub fn default_glossary() -> Vec<GlossaryTerm> {
+    let term = |term: &str, preferred: &str, variants: &[&str], translation: &str| GlossaryTerm {
+        term: term.to_string(),
+        preferred: preferred.to_string(),
+        variants: variants.iter().map(|v| v.to_string()).collect(),
+        translation: Some(translation.to_string()),
+    };
+    vec![
+        term("تقوى", "taqwa", &["taqwah", "taqwaa", "taqua"], "God-consciousness"),
+        term("حديث", "hadith", &["hadeeth", "hadis", "hadees"], "prophetic tradition"),
+        term("صلاة", "salah", &["salat", "salaat", "salaah"], "prayer"),
+        term("زكاة", "zakah", &["zakat", "zakaat"], "obligatory charity"),
+    ]
+}
+
+// A compiled glossary variant
+struct Rule {
+    pattern: Regex,
+    variant: String,
+    preferred: String,
+}
+
+fn compile(glossary: &[GlossaryTerm]) -> Vec<Rule> {
+    glossary
+        .iter()
+        .flat_map(|term| {
+            term.variants
+                .iter()
+                .filter(|v| !v.trim().is_empty() && !v.eq_ignore_ascii_case(&term.preferred))
+                .filter_map(|variant| {
+                    Some(Rule {
+                        pattern: Regex::new(&format!(r"(?i)\b{}\b", regex::escape(variant.trim()))).ok()?,
+                        variant: variant.trim().to_string(),
+                        preferred: term.preferred.clone(),
+                    })
+                })
+        })
+        .collect()
+}
+
+// Keep a capitalized variant capitalized, e.g. at the start of a sentence
+fn matching_case(found: &str, preferred: &str) -> String {
+    let mut chars = preferred.chars();
+    match (found.chars().next(), chars.next()) {
+        (Some(f), Some(p)) if f.is_uppercase() => p.to_uppercase().chain(chars).collect(),
+        _ => preferred.to_string(),
+    }
+}
+
+// Replace glossary variants with the preferred spellings, returning the number of replacements
+pub fn apply(content: &str, glossary: &[GlossaryTerm]) -> (String, usize) {
+    let mut content = content.to_string();
+    let mut replaced = 0;
+    for rule in compile(glossary) {
+        replaced += rule.pattern.find_iter(&content).count();
+        content = rule
+            .pattern
+            .replace_all(&content, |caps: &regex::Captures| matching_case(&caps[0], &rule.preferred))
+            .into_owned();
+    }
+    (content, replaced)
+}
+
+// Apply the workspace glossary to a transcript file in place
+pub fn apply_to_transcript(file_name: &str) -> Result<usize, String> {
+    let glossary = settings::load().glossary;
+    let path = transcript::transcript_path(file_name);
+    let content = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    let (fixed, replaced) = apply(&content, &glossary);
+    if replaced > 0 {
+        storage().write(&path, fixed).map_err(|e| format!("Failed to write transcript: {}", e))?;
+    }
+    Ok(replaced)
+}
+
+// A place where the corpus uses a non-preferred spelling
+#[derive(Debug, Serialize)]
+pub struct Deviation {
+    // Transcript file name, or "dataset:<entry id>"
+    pub location: String,
+    pub found: String,
+    pub preferred: String,
+    pub count: usize,
+}
+
+// Command to list glossary deviations across transcripts and the default dataset
+#[tauri::command]
+pub fn check_glossary_compliance() -> Result<serde_json::Value, String> {
+    let rules = compile(&settings::load().glossary);
+    let mut deviations = Vec::new();
+
+    let mut scan = |location: String, content: &str| {
+        for rule in &rules {
+            let count = rule.pattern.find_iter(content).count();
+            if count > 0 {
+                deviations.push(Deviation {
+                    location: location.clone(),
+                    found: rule.variant.clone(),
+                    preferred: rule.preferred.clone(),
+                    count,
+                });
+            }
+        }
+    };
+
+    let transcripts = transcript::list_transcripts();
+    for name in &transcripts {
+        if let Ok(content) = storage().read_to_string(transcript::transcript_path(name)) {
+            scan(name.clone(), &content);
+        }
+    }
+
+    let path = dataset::dataset_path(None)?;
+    let mut entries_checked = 0;
+    if storage().exists(&path) {
+        for mut entry in dataset::load_entries(&path)? {
+            dataset::annotate(&mut entry);
+            scan(format!("dataset:{}", entry.id), &format!("{}\n{}", entry.input, entry.output));
+            entries_checked += 1;
+        }
+    }
+
+    let total: usize = deviations.iter().map(|d| d.count).sum();
+    Ok(serde_json::json!({
+        "transcripts_checked": transcripts.len(),
+        "entries_checked": entries_checked,
+        "total_deviations": total,
+        "deviations": deviations
+    }))
+}
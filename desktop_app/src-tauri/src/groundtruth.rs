@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::metadata;
+use crate::runs;
+use crate::settings;
+use crate::state;
+use crate::transcript;
+use crate::wer::{self, WordErrors};
+use crate::storage::storage;
+
+// Reserved run holding ground-truth snapshots, so compute_wer can use it as a reference
+pub const GROUND_TRUTH_RUN: &str = "ground_truth";
+
+// Score of one automatic transcription against the clip's ground truth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroundTruthScore {
+    pub file_name: String,
+    pub model: String,
+    // Named run the transcription went to; None for the main transcripts
+    pub run: Option<String>,
+    pub wer: f64,
+    pub errors: WordErrors,
+    pub scored_at: u64,
+}
+
+fn ground_truth_path(file_name: &str) -> std::path::PathBuf {
+    runs::run_dir(Some(GROUND_TRUTH_RUN)).join(format!("{}.txt", metadata::stem(file_name)))
+}
+
+// Score a fresh transcription of `clip` when its ground truth is known
+pub fn score(clip: &str, run: Option<&str>, model: &str) -> Result<Option<GroundTruthScore>, String> {
+    let file_name = format!("{}.txt", metadata::stem(clip));
+    let Ok(reference) = storage().read_to_string(ground_truth_path(&file_name)) else {
+        return Ok(None);
+    };
+    let hypothesis = runs::read_run_transcript(&file_name, run)?;
+
+    let errors = wer::word_errors(&wer::words(&reference), &wer::words(&hypothesis));
+    let score = GroundTruthScore {
+        file_name,
+        model: model.to_string(),
+        run: run.map(str::to_string),
+        wer: errors.rate(),
+        errors,
+        scored_at: state::now(),
+    };
+    state::update(|s| s.ground_truth_scores.push(score.clone()))?;
+    Ok(Some(score))
+}
+
+// Command to mark an approved transcript as the ground truth of its clip, or unmark it
+#[tauri::command]
+pub fn set_ground_truth(file_name: String, enabled: bool) -> Result<(), String> {
+    let path = ground_truth_path(&file_name);
+    if !enabled {
+        if storage().exists(&path) {
+            storage().remove_file(&path).map_err(|e| format!("Failed to remove ground truth: {}", e))?;
+        }
+        audit::record("transcript.ground_truth", &file_name, serde_json::json!({ "enabled": false }));
+        return Ok(());
+    }
+
+    if transcript::review_status(&state::load()?, &file_name) != "approved" {
+        return Err(format!("Only approved transcripts can be ground truth: {}", file_name));
+    }
+    let content = storage().read_to_string(transcript::transcript_path(&file_name))
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    // A snapshot, so re-transcribing the clip later cannot overwrite the reference
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    storage().write(&path, content).map_err(|e| format!("Failed to write ground truth: {}", e))?;
+    audit::record("transcript.ground_truth", &file_name, serde_json::json!({ "enabled": true }));
+    Ok(())
+}
+
+// Command to summarize ground-truth scores per model and recommend the most accurate one
+#[tauri::command]
+pub fn get_ground_truth_report() -> Result<serde_json::Value, String> {
+    // Only the latest score per clip and model counts
+    let mut latest: BTreeMap<(String, String), GroundTruthScore> = BTreeMap::new();
+    for score in state::load()?.ground_truth_scores {
+        latest.insert((score.model.clone(), score.file_name.clone()), score);
+    }
+
+    let mut models: BTreeMap<String, (WordErrors, usize)> = BTreeMap::new();
+    for ((model, _), score) in &latest {
+        let (total, files) = models.entry(model.clone()).or_default();
+        total.add(&score.errors);
+        *files += 1;
+    }
+
+    let summary: Vec<serde_json::Value> = models
+        .iter()
+        .map(|(model, (total, files))| serde_json::json!({ "model": model, "files": files, "wer": total.rate() }))
+        .collect();
+    let recommended = models
+        .iter()
+        .min_by(|a, b| a.1 .0.rate().total_cmp(&b.1 .0.rate()))
+        .map(|(model, _)| model.clone());
+
+    Ok(serde_json::json!({
+        "ground_truth_files": runs::run_transcripts(Some(GROUND_TRUTH_RUN)).len(),
+        "models": summary,
+        "current_model": settings::load().whisper_model,
+        "recommended_model": recommended,
+        "scores": latest.into_values().collect::<Vec<_>>()
+    }))
+}
@@ -0,0 +1,378 @@
+use tauri::AppHandle;
+
+// Address the gRPC server listens on unless another is given
+const DEFAULT_BIND: &str = "127.0.0.1:50051";
+
+// Command to serve proto/pipeline.proto over gRPC; uses the same tokens and roles as the
+// review server and is only available in builds with the "grpc" feature
+#[tauri::command]
+pub fn start_grpc_server(app: AppHandle, bind: Option<String>) -> Result<serde_json::Value, String> {
+    #[cfg(feature = "grpc")]
+    {
+        let address = service::start(app, &bind.unwrap_or_else(|| DEFAULT_BIND.to_string()))?;
+        Ok(serde_json::json!({ "running": true, "address": address.to_string() }))
+    }
+    #[cfg(not(feature = "grpc"))]
+    {
+        let _ = (app, bind, DEFAULT_BIND);
+        Err("This build does not include the gRPC server; build with --features grpc".to_string())
+    }
+}
+
+// Command to stop the gRPC server
+#[tauri::command]
+pub fn stop_grpc_server() -> Result<(), String> {
+    #[cfg(feature = "grpc")]
+    service::stop();
+    Ok(())
+}
+
+#[cfg(feature = "grpc")]
+mod service {
+    use std::collections::BTreeSet;
+    use std::net::{SocketAddr, ToSocketAddrs};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    use tauri::AppHandle;
+    use tokio::sync::{mpsc, oneshot};
+    use tokio_stream::wrappers::ReceiverStream;
+    use tonic::{Request, Response, Status};
+
+    use crate::audit;
+    use crate::dataset;
+    use crate::events::{self, Stage};
+    use crate::jobs::{self, JobStatus};
+    use crate::review_server::{self, Role};
+    use crate::settings;
+    use crate::transcript::SaveError;
+
+    pub mod proto {
+        tonic::include_proto!("akhi.pipeline.v1");
+    }
+
+    use proto::pipeline_server::{Pipeline, PipelineServer};
+
+    // How long StartDownload and StartTranscription wait for the command to register its job
+    const JOB_START_TIMEOUT: Duration = Duration::from_secs(120);
+
+    // How often WatchJob looks for progress
+    const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+    static SERVER: Mutex<Option<(SocketAddr, oneshot::Sender<()>)>> = Mutex::new(None);
+
+    impl From<jobs::Job> for proto::Job {
+        fn from(job: jobs::Job) -> Self {
+            let stage = match job.stage {
+                Stage::Download => proto::Stage::Download,
+                Stage::Transcribe => proto::Stage::Transcribe,
+                Stage::Dataset => proto::Stage::Dataset,
+                Stage::Analysis => proto::Stage::Analysis,
+            };
+            let status = match job.status {
+                JobStatus::Running => proto::JobStatus::Running,
+                JobStatus::Completed => proto::JobStatus::Completed,
+                JobStatus::Failed => proto::JobStatus::Failed,
+            };
+            proto::Job {
+                id: job.id,
+                stage: stage as i32,
+                status: status as i32,
+                items: job.items,
+                completed: job.completed as u64,
+                current_item: job.current_item,
+                started_at: job.started_at,
+                finished_at: job.finished_at,
+                elapsed_seconds: job.elapsed_seconds,
+                throughput: job.throughput,
+                eta_seconds: job.eta_seconds,
+                partial_bytes: job.partial_bytes.into_iter().collect(),
+            }
+        }
+    }
+
+    // Authenticate a call from its "authorization" and "x-reviewer" metadata
+    fn caller<T>(request: &Request<T>, required: Role) -> Result<Option<String>, Status> {
+        let metadata = request.metadata();
+        let header = |name: &str| metadata.get(name).and_then(|v| v.to_str().ok());
+        let token = header("authorization").and_then(|v| v.strip_prefix("Bearer ")).map(str::trim);
+        let (name, role) = review_server::identify(token, header("x-reviewer"), &settings::load().collaboration)
+            .ok_or_else(|| Status::unauthenticated("Invalid or missing token"))?;
+        if role < required {
+            return Err(Status::permission_denied(format!("Requires the {} role", required.as_str())));
+        }
+        Ok(name)
+    }
+
+    // Run a blocking command off the async runtime, attributing its changes to the caller
+    async fn blocking<T, F>(name: Option<String>, f: F) -> Result<Response<T>, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(Option<String>) -> Result<T, Status> + Send + 'static,
+    {
+        let actor = name.clone().unwrap_or_else(|| "anonymous".to_string());
+        tauri::async_runtime::spawn_blocking(move || audit::as_user(&actor, || f(name)))
+            .await
+            .map_err(|e| Status::internal(format!("Request failed: {}", e)))?
+            .map(Response::new)
+    }
+
+    // Start a command that registers its own job and return that job once it appears
+    fn start_job<F>(stage: Stage, command: F) -> Result<proto::Job, Status>
+    where
+        F: FnOnce() -> Result<String, String> + Send + 'static,
+    {
+        let known: BTreeSet<String> = jobs::get_jobs().into_iter().map(|j| j.id).collect();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let actor = audit::current_user();
+        std::thread::spawn(move || sender.send(audit::as_user(&actor, command)).ok());
+
+        let deadline = Instant::now() + JOB_START_TIMEOUT;
+        loop {
+            let finished = receiver.try_recv().ok();
+            if let Some(job) = jobs::get_jobs().into_iter().find(|j| j.stage == stage && !known.contains(&j.id)) {
+                return Ok(job.into());
+            }
+            match finished {
+                Some(Err(e)) => return Err(Status::failed_precondition(e)),
+                Some(Ok(_)) => return Err(Status::internal("Command finished without starting a job")),
+                None if Instant::now() > deadline => return Err(Status::deadline_exceeded("Job did not start in time")),
+                None => std::thread::sleep(Duration::from_millis(100)),
+            }
+        }
+    }
+
+    fn not_found(message: String) -> Status {
+        Status::not_found(message)
+    }
+
+    struct PipelineService {
+        app: AppHandle,
+    }
+
+    #[tonic::async_trait]
+    impl Pipeline for PipelineService {
+        async fn start_download(&self, request: Request<proto::StartDownloadRequest>) -> Result<Response<proto::Job>, Status> {
+            let name = caller(&request, Role::Reviewer)?;
+            let request = request.into_inner();
+            if request.links.is_empty() || request.links.iter().any(|l| !(l.starts_with("https://") || l.starts_with("http://"))) {
+                return Err(Status::invalid_argument("links must be a non-empty list of http(s) URLs"));
+            }
+            let app = self.app.clone();
+            blocking(name, move |_| {
+                start_job(Stage::Download, move || crate::download_videos(app, request.links, Some(request.force)))
+            })
+            .await
+        }
+
+        async fn start_transcription(&self, request: Request<proto::StartTranscriptionRequest>) -> Result<Response<proto::Job>, Status> {
+            let name = caller(&request, Role::Reviewer)?;
+            let request = request.into_inner();
+            let app = self.app.clone();
+            blocking(name, move |_| {
+                start_job(Stage::Transcribe, move || crate::transcribe_audio(app, request.run, request.model))
+            })
+            .await
+        }
+
+        async fn get_job(&self, request: Request<proto::GetJobRequest>) -> Result<Response<proto::Job>, Status> {
+            caller(&request, Role::Viewer)?;
+            let job = jobs::get_job(request.into_inner().job_id).map_err(not_found)?;
+            Ok(Response::new(job.into()))
+        }
+
+        async fn list_jobs(&self, request: Request<proto::ListJobsRequest>) -> Result<Response<proto::ListJobsResponse>, Status> {
+            caller(&request, Role::Viewer)?;
+            let jobs = jobs::get_jobs().into_iter().map(proto::Job::from).collect();
+            Ok(Response::new(proto::ListJobsResponse { jobs }))
+        }
+
+        type WatchJobStream = ReceiverStream<Result<proto::Job, Status>>;
+
+        async fn watch_job(&self, request: Request<proto::GetJobRequest>) -> Result<Response<Self::WatchJobStream>, Status> {
+            caller(&request, Role::Viewer)?;
+            let job_id = request.into_inner().job_id;
+            jobs::get_job(job_id.clone()).map_err(not_found)?;
+
+            let (sender, receiver) = mpsc::channel(16);
+            std::thread::spawn(move || {
+                let mut last = None;
+                // Stops when the job finishes, is dropped from memory or the client goes away
+                while let Ok(job) = jobs::get_job(job_id.clone()) {
+                    let finished = job.status != JobStatus::Running;
+                    let message = proto::Job::from(job);
+                    if last.as_ref() != Some(&message) {
+                        if sender.blocking_send(Ok(message.clone())).is_err() {
+                            return;
+                        }
+                        last = Some(message);
+                    }
+                    if finished {
+                        return;
+                    }
+                    std::thread::sleep(WATCH_INTERVAL);
+                }
+            });
+            Ok(Response::new(ReceiverStream::new(receiver)))
+        }
+
+        async fn list_transcripts(&self, request: Request<proto::ListTranscriptsRequest>) -> Result<Response<proto::ListTranscriptsResponse>, Status> {
+            let name = caller(&request, Role::Viewer)?;
+            let sort_by = request.into_inner().sort_by;
+            blocking(name, move |_| {
+                let page = crate::get_transcripts(None, None, sort_by, None, None).map_err(Status::invalid_argument)?;
+                let transcripts = page["transcripts"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|t| proto::TranscriptSummary {
+                        file_name: t["file_name"].as_str().unwrap_or_default().to_string(),
+                        review_status: t["review_status"].as_str().unwrap_or_default().to_string(),
+                        word_count: t["word_count"].as_u64().unwrap_or(0),
+                        modified: t["modified"].as_u64().unwrap_or(0),
+                    })
+                    .collect();
+                Ok(proto::ListTranscriptsResponse { transcripts })
+            })
+            .await
+        }
+
+        async fn get_transcript(&self, request: Request<proto::GetTranscriptRequest>) -> Result<Response<proto::Transcript>, Status> {
+            let name = caller(&request, Role::Viewer)?;
+            let file_name = request.into_inner().file_name;
+            dataset::validate_name(&file_name).map_err(Status::invalid_argument)?;
+            blocking(name, move |_| {
+                let transcript = crate::get_transcript(file_name.clone()).map_err(not_found)?;
+                Ok(proto::Transcript {
+                    file_name,
+                    content: transcript["content"].as_str().unwrap_or_default().to_string(),
+                    revision: transcript["revision"].as_str().unwrap_or_default().to_string(),
+                })
+            })
+            .await
+        }
+
+        async fn update_transcript(&self, request: Request<proto::UpdateTranscriptRequest>) -> Result<Response<proto::UpdateTranscriptResponse>, Status> {
+            let name = caller(&request, Role::Reviewer)?;
+            let request = request.into_inner();
+            dataset::validate_name(&request.file_name).map_err(Status::invalid_argument)?;
+            blocking(name, move |name| {
+                let user = name.ok_or_else(|| Status::invalid_argument("x-reviewer metadata is required"))?;
+                // Same rule as the review server: only the reviewer holding the claim may edit
+                match review_server::claimed_by(&request.file_name) {
+                    Some(holder) if holder == user => {}
+                    Some(holder) => return Err(Status::failed_precondition(format!("Transcript is claimed by {}", holder))),
+                    None => return Err(Status::failed_precondition("Claim the transcript before editing it")),
+                }
+                let revision = crate::save_transcript(request.file_name, request.content, request.revision, &user)
+                    .map_err(|error| {
+                        let message = serde_json::to_string(&error).unwrap_or_default();
+                        match error {
+                            SaveError::Conflict { .. } => Status::aborted(message),
+                            SaveError::NotFound { .. } => Status::not_found(message),
+                            SaveError::RevisionRequired { .. } => Status::invalid_argument(message),
+                            SaveError::Io { .. } => Status::internal(message),
+                        }
+                    })?;
+                Ok(proto::UpdateTranscriptResponse { revision })
+            })
+            .await
+        }
+
+        async fn generate_dataset(&self, request: Request<proto::GenerateDatasetRequest>) -> Result<Response<proto::Job>, Status> {
+            let name = caller(&request, Role::Reviewer)?;
+            let request = request.into_inner();
+            let config = dataset::dataset_config(request.dataset.as_deref()).map_err(Status::invalid_argument)?;
+
+            // generate_json has no job of its own, so one is registered here for WatchJob
+            let job = jobs::start(Stage::Dataset, vec![config.id.clone()], Vec::new());
+            let app = self.app.clone();
+            let id = job.clone();
+            let actor = name.unwrap_or_else(|| "anonymous".to_string());
+            std::thread::spawn(move || {
+                jobs::item_started(&id, &config.id);
+                let started = Instant::now();
+                let result = audit::as_user(&actor, || crate::generate_json(app.clone(), Some(config.id.clone()), request.persona));
+                jobs::item_finished(&id, started.elapsed().as_secs_f64());
+                if let Err(e) = &result {
+                    events::error(&app, Stage::Dataset, e, Some(&config.id));
+                }
+                jobs::finish(&id, result.is_ok());
+            });
+
+            let job = jobs::get_job(job).map_err(not_found)?;
+            Ok(Response::new(job.into()))
+        }
+
+        async fn list_datasets(&self, request: Request<proto::ListDatasetsRequest>) -> Result<Response<proto::ListDatasetsResponse>, Status> {
+            caller(&request, Role::Viewer)?;
+            let listed = dataset::get_datasets().map_err(Status::internal)?;
+            let datasets = listed["datasets"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|d| proto::Dataset {
+                    id: d["id"].as_str().unwrap_or_default().to_string(),
+                    path: d["path"].as_str().unwrap_or_default().to_string(),
+                    exists: d["exists"].as_bool().unwrap_or(false),
+                    persona: d["persona"].as_str().map(str::to_string),
+                })
+                .collect();
+            Ok(Response::new(proto::ListDatasetsResponse { datasets }))
+        }
+
+        async fn get_dataset_entry(&self, request: Request<proto::GetDatasetEntryRequest>) -> Result<Response<proto::DatasetEntry>, Status> {
+            let name = caller(&request, Role::Viewer)?;
+            let request = request.into_inner();
+            blocking(name, move |_| {
+                let found = dataset::get_json_entry(request.id.clone(), request.dataset).map_err(not_found)?;
+                Ok(proto::DatasetEntry { id: request.id, json: found["entry"].to_string() })
+            })
+            .await
+        }
+    }
+
+    pub fn start(app: AppHandle, bind: &str) -> Result<SocketAddr, String> {
+        let config = settings::load().collaboration;
+        if !config.has_tokens() {
+            return Err("Create an API token, set a collaboration token or add members before starting the gRPC server".to_string());
+        }
+        config.validate()?;
+        review_server::check_bind(bind, config.allow_lan)?;
+
+        let mut server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((address, running)) = server.as_ref() {
+            if !running.is_closed() {
+                return Err(format!("gRPC server already running on {}", address));
+            }
+        }
+
+        let address = bind
+            .to_socket_addrs()
+            .map_err(|e| format!("Failed to resolve {}: {}", bind, e))?
+            .next()
+            .ok_or_else(|| format!("Failed to resolve {}", bind))?;
+        let (stop, stopped) = oneshot::channel::<()>();
+        let service = PipelineServer::new(PipelineService { app: app.clone() });
+        tauri::async_runtime::spawn(async move {
+            let served = tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_shutdown(address, async {
+                    stopped.await.ok();
+                })
+                .await;
+            if let Err(e) = served {
+                events::error(&app, Stage::Analysis, &format!("gRPC server failed: {}", e), None);
+            }
+        });
+
+        *server = Some((address, stop));
+        Ok(address)
+    }
+
+    pub fn stop() {
+        if let Some((_, stop)) = SERVER.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            stop.send(()).ok();
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::clips;
+use crate::metadata;
+use crate::pipeline::Download;
+use crate::state;
+use crate::storage::storage;
+
+// One finished download, kept even after its clip is deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadRecord {
+    pub url: String,
+    pub downloaded_at: u64,
+    // "success" or "failed"
+    pub outcome: String,
+    pub clips: Vec<String>,
+    pub title: Option<String>,
+    // Bytes of the clips and their info JSON at download time
+    pub size: u64,
+    pub attempts: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // yt-dlp version the batch ran with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ytdlp_version: Option<String>,
+    // Reconstructed from an existing clip by migrate_existing_outputs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub migrated: bool,
+}
+
+// Append a finished download to the history in the state store
+pub fn record(url: &str, download: &Download, attempts: usize, ytdlp_version: Option<&str>) {
+    let size = download
+        .clips
+        .iter()
+        .flat_map(|clip| [clips::media_file(metadata::stem(clip), "mp3"), metadata::info_path(clip)])
+        .filter_map(|path| storage().metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum();
+
+    let record = DownloadRecord {
+        url: url.to_string(),
+        downloaded_at: state::now(),
+        outcome: if download.output.success { "success" } else { "failed" }.to_string(),
+        title: download.clips.first().and_then(|clip| metadata::load(clip)?.title),
+        clips: download.clips.clone(),
+        size,
+        attempts,
+        error: (!download.output.success).then(|| download.output.stderr.trim().to_string()),
+        ytdlp_version: ytdlp_version.map(str::to_string),
+        migrated: false,
+    };
+
+    state::update(|s| s.download_history.push(record)).ok();
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct HistoryFilter {
+    // Substring of the URL or title, case-insensitive
+    pub query: Option<String>,
+    pub outcome: Option<String>,
+    // Unix seconds, inclusive
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, record: &DownloadRecord) -> bool {
+        let query = self.query.as_ref().map(|q| q.to_lowercase());
+        query.is_none_or(|q| {
+            record.url.to_lowercase().contains(&q)
+                || record.title.as_ref().is_some_and(|t| t.to_lowercase().contains(&q))
+        }) && self.outcome.as_ref().is_none_or(|o| &record.outcome == o)
+            && self.since.is_none_or(|since| record.downloaded_at >= since)
+            && self.until.is_none_or(|until| record.downloaded_at <= until)
+    }
+}
+
+// Command to browse everything ever downloaded, newest first
+#[tauri::command]
+pub fn get_download_history(
+    filter: Option<HistoryFilter>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    let filter = filter.unwrap_or_default();
+    let page = page.unwrap_or(0);
+    let page_size = page_size.unwrap_or(50).max(1);
+
+    let history = state::load()?.download_history;
+    let matching: Vec<&DownloadRecord> = history.iter().rev().filter(|r| filter.matches(r)).collect();
+
+    let items: Vec<serde_json::Value> = matching
+        .iter()
+        .skip(page * page_size)
+        .take(page_size)
+        .map(|record| {
+            // Clips may have been deleted since; report which still exist
+            let present = record
+                .clips
+                .iter()
+                .any(|clip| storage().exists(clips::media_file(metadata::stem(clip), "mp3")));
+            let mut value = serde_json::to_value(record).unwrap_or_default();
+            value["present"] = serde_json::json!(present);
+            value
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "items": items,
+        "total": matching.len(),
+        "page": page,
+        "page_size": page_size
+    }))
+}
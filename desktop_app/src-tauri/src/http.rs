@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// Largest request body accepted, enough for long transcripts
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+// Longest request or header line accepted, and most header lines per request
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+
+// Connections handled at once; later ones are answered 503 until one finishes
+const MAX_CONNECTIONS: usize = 64;
+
+// How often a stopped server notices it should exit
+const ACCEPT_POLL: Duration = Duration::from_millis(200);
+
+// A parsed HTTP/1.1 request
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    // Decoded path segments, e.g. ["transcripts", "lecture 1.txt"]
+    pub segments: Vec<String>,
+    pub query: BTreeMap<String, String>,
+    // Header names are lowercased
+    pub headers: BTreeMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, Response> {
+        serde_json::from_slice(&self.body).map_err(|e| Response::error(400, &format!("Invalid JSON body: {}", e)))
+    }
+
+    // Token from an "Authorization: Bearer <token>" header
+    pub fn bearer(&self) -> Option<&str> {
+        self.headers.get("authorization")?.strip_prefix("Bearer ").map(str::trim)
+    }
+}
+
+// Body of every error response
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn json(status: u16, value: &impl serde::Serialize) -> Self {
+        Response {
+            status,
+            content_type: "application/json".to_string(),
+            body: serde_json::to_vec(value).unwrap_or_default(),
+        }
+    }
+
+    pub fn html(status: u16, body: String) -> Self {
+        Response {
+            status,
+            content_type: "text/html; charset=utf-8".to_string(),
+            body: body.into_bytes(),
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Response::json(status, &ErrorBody { error: message.to_string() })
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+// Compare a presented token with a stored one without revealing how much of it matched
+pub fn secret_eq(stored: &str, presented: &str) -> bool {
+    let (stored, presented) = (stored.as_bytes(), presented.as_bytes());
+    let differing = stored.iter().zip(presented).fold(0, |acc, (a, b)| acc | (a ^ b));
+    differing == 0 && stored.len() == presented.len()
+}
+
+// One line of the request head, or None when it is longer than MAX_LINE_BYTES
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_LINE_BYTES + 1).read_line(&mut line)?;
+    Ok((line.len() as u64 <= MAX_LINE_BYTES).then_some(line))
+}
+
+// Decode %XX escapes and '+' in a URL component
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn parse(stream: &TcpStream) -> io::Result<Result<Request, Response>> {
+    let mut reader = BufReader::new(stream);
+
+    let Some(line) = read_line(&mut reader)? else {
+        return Ok(Err(Response::error(431, "Request line too long")));
+    };
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Response::error(400, "Malformed request line")));
+    };
+    let method = method.to_string();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut headers = BTreeMap::new();
+    loop {
+        let Some(header) = read_line(&mut reader)? else {
+            return Ok(Err(Response::error(431, "Request header too long")));
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if headers.len() >= MAX_HEADERS {
+            return Ok(Err(Response::error(431, "Too many request headers")));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Ok(Err(Response::error(413, "Request body too large")));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Ok(Request {
+        method,
+        segments: path.split('/').filter(|s| !s.is_empty()).map(percent_decode).collect(),
+        query: query
+            .split('&')
+            .filter(|p| !p.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key), percent_decode(value))
+            })
+            .collect(),
+        headers,
+        body,
+    }))
+}
+
+fn respond(mut stream: &TcpStream, response: Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+// A connection counted against MAX_CONNECTIONS while it is held
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// A running server; dropping the handle does not stop it, `stop` does
+pub struct Server {
+    pub address: SocketAddr,
+    running: Arc<AtomicBool>,
+}
+
+impl Server {
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+// Serve requests on `bind`, one thread per connection up to MAX_CONNECTIONS, until stopped
+pub fn serve<H>(bind: &str, handler: H) -> Result<Server, String>
+where
+    H: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(bind).map_err(|e| format!("Failed to bind {}: {}", bind, e))?;
+    let address = listener.local_addr().map_err(|e| format!("Failed to bind {}: {}", bind, e))?;
+    listener.set_nonblocking(true).map_err(|e| format!("Failed to configure server: {}", e))?;
+
+    let running = Arc::new(AtomicBool::new(true));
+    let handler = Arc::new(handler);
+    let flag = running.clone();
+    let open = Arc::new(AtomicUsize::new(0));
+    std::thread::spawn(move || {
+        while flag.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false).ok();
+                    if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                        open.fetch_sub(1, Ordering::SeqCst);
+                        // Answered from the accept loop, so a slow client cannot hold it up
+                        stream.set_write_timeout(Some(Duration::from_secs(1))).ok();
+                        respond(&stream, Response::error(503, "Too many connections, try again shortly")).ok();
+                        continue;
+                    }
+                    let (handler, open) = (handler.clone(), open.clone());
+                    std::thread::spawn(move || {
+                        // Released however the connection ends
+                        let _slot = Slot(open);
+                        stream.set_read_timeout(Some(Duration::from_secs(30))).ok();
+                        let response = match parse(&stream) {
+                            Ok(Ok(request)) => handler(&request),
+                            Ok(Err(response)) => response,
+                            Err(_) => return,
+                        };
+                        respond(&stream, response).ok();
+                    });
+                }
+                // WouldBlock when idle; other accept errors are transient too
+                Err(_) => std::thread::sleep(ACCEPT_POLL),
+            }
+        }
+    });
+
+    Ok(Server { address, running })
+}
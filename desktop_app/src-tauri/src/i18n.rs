@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use regex::Regex;
+use unic_langid::LanguageIdentifier;
+
+use crate::settings;
+
+// Message catalogs in Fluent syntax
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("ar", include_str!("../locales/ar.ftl")),
+    ("ms", include_str!("../locales/ms.ftl")),
+];
+
+pub const FALLBACK_LOCALE: &str = "en";
+
+fn source(locale: &str) -> Option<&'static str> {
+    CATALOGS.iter().find(|(l, _)| *l == locale).map(|(_, s)| *s)
+}
+
+// Messages of a locale, with untranslated ones taken from the fallback locale
+fn bundle(locale: &str) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = locale.parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new(vec![id]);
+    // Plain text for the UI and logs, without isolation marks around arguments
+    bundle.set_use_isolating(false);
+    for locale in [FALLBACK_LOCALE, locale] {
+        if let Some(source) = source(locale) {
+            // A syntax error only drops the broken message
+            let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, _)| resource);
+            bundle.add_resource_overriding(resource);
+        }
+    }
+    bundle
+}
+
+fn format(bundle: &FluentBundle<FluentResource>, code: &str, args: &[(&str, &str)]) -> Option<String> {
+    let pattern = bundle.get_message(code)?.value()?;
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, *value);
+    }
+    let mut errors = Vec::new();
+    Some(bundle.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned())
+}
+
+// Message ids and their source text, in catalog order
+fn entries(source: &str) -> impl Iterator<Item = (&str, &str)> {
+    source
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t', '#']))
+        .filter_map(|line| line.split_once('='))
+        .map(|(id, text)| (id.trim(), text.trim()))
+        .filter(|(id, _)| !id.is_empty())
+}
+
+// Catalog for a locale; placeholders are left as `{$name}`
+pub fn catalog(locale: &str) -> BTreeMap<String, String> {
+    let bundle = bundle(locale);
+    entries(source(FALLBACK_LOCALE).unwrap_or_default())
+        .filter_map(|(id, _)| format(&bundle, id, &[]).map(|text| (id.to_string(), text)))
+        .collect()
+}
+
+// Localized message for a code in the workspace locale; unknown codes are returned as-is
+pub fn message(code: &str, args: &[(&str, &str)]) -> String {
+    format(&bundle(&settings::load().locale), code, args).unwrap_or_else(|| code.to_string())
+}
+
+// Translate an error built in English with format! when an `error-` message of the fallback
+// catalog matches it, e.g. "Failed to execute yt-dlp: ..." through `error-execute`
+pub fn localize(message: &str) -> String {
+    let locale = settings::load().locale;
+    if locale == FALLBACK_LOCALE {
+        return message.to_string();
+    }
+
+    let placeholder = Regex::new(r"\{\s*\$(\w+)\s*\}").unwrap();
+    for (id, template) in entries(source(FALLBACK_LOCALE).unwrap_or_default()).filter(|(id, _)| id.starts_with("error-")) {
+        let mut pattern = String::from("^");
+        let mut names = Vec::new();
+        let mut rest = 0;
+        for found in placeholder.captures_iter(template) {
+            let whole = found.get(0).map_or(0..0, |m| m.range());
+            pattern.push_str(&regex::escape(&template[rest..whole.start]));
+            pattern.push_str("(.+?)");
+            names.push(found[1].to_string());
+            rest = whole.end;
+        }
+        pattern.push_str(&regex::escape(&template[rest..]));
+        pattern.push('$');
+
+        let Some(captures) = Regex::new(&pattern).ok().and_then(|re| re.captures(message)) else {
+            continue;
+        };
+        let values: Vec<String> = (1..=names.len()).map(|i| captures[i].to_string()).collect();
+        let args: Vec<(&str, &str)> = names.iter().map(String::as_str).zip(values.iter().map(String::as_str)).collect();
+        if let Some(text) = format(&bundle(&locale), id, &args) {
+            return text;
+        }
+    }
+    message.to_string()
+}
+
+// Command to list the locales a catalog exists for
+#[tauri::command]
+pub fn get_locales() -> Vec<&'static str> {
+    CATALOGS.iter().map(|(locale, _)| *locale).collect()
+}
+
+// Command to get the message catalog the UI uses for status labels and error codes
+#[tauri::command]
+pub fn get_message_catalog(locale: Option<String>) -> serde_json::Value {
+    let locale = locale.unwrap_or_else(|| settings::load().locale);
+    serde_json::json!({
+        "locale": locale,
+        "messages": catalog(&locale)
+    })
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::audit;
+use crate::clips;
+use crate::journal;
+use crate::metadata;
+use crate::transcript::{self, Segment};
+use crate::storage::storage;
+
+#[derive(Debug, Serialize)]
+pub struct ImportResult {
+    pub path: String,
+    pub file_name: String,
+    pub segments: usize,
+    // Clip the transcript was linked to, if any
+    pub clip: Option<String>,
+    // Why nothing was written, e.g. an existing transcript without `force`
+    pub skipped: Option<String>,
+    // Clips the name matched loosely; one must be assigned before the file is imported
+    pub candidates: Vec<String>,
+}
+
+// Outcome of linking an imported file to a clip by name
+enum ClipMatch {
+    Unique(String),
+    Ambiguous(Vec<String>),
+    Unmatched,
+}
+
+// Parse "hh:mm:ss,mmm", "hh:mm:ss.mmm" or "mm:ss.mmm" into seconds
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let value = value.trim().replace(',', ".");
+    let parts: Vec<&str> = value.split(':').collect();
+
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<f64>().ok()?, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        [m, s] => (0.0, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        _ => return None,
+    };
+
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+// Parse SRT or WebVTT cues into segments
+pub fn parse_subtitles(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let normalized = content.replace("\r\n", "\n");
+
+    for block in normalized.split("\n\n") {
+        let mut lines = block.lines().skip_while(|l| !l.contains("-->"));
+        let timing = match lines.next() {
+            Some(timing) => timing,
+            None => continue,
+        };
+
+        // VTT cue settings may follow the end timestamp
+        let mut bounds = timing.split("-->");
+        let start = bounds.next().and_then(parse_timestamp);
+        let end = bounds
+            .next()
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(parse_timestamp);
+
+        let text = lines.collect::<Vec<_>>().join(" ");
+        if let (Some(start), Some(end)) = (start, end) {
+            if !text.trim().is_empty() {
+                segments.push(Segment {
+                    start,
+                    end,
+                    text: text.trim().to_string(),
+                    avg_logprob: None,
+                    words: Vec::new(),
+                });
+            }
+        }
+    }
+
+    segments
+}
+
+// Lowercased alphanumerics only, for loose name matching
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Find the clip whose name is the imported file's name, exactly or up to case and punctuation;
+// looser matches are only reported, never linked
+fn match_clip(stem: &str) -> ClipMatch {
+    let clips = clips::list_clips();
+
+    if let Some(clip) = clips.iter().find(|c| metadata::stem(c) == stem) {
+        return ClipMatch::Unique(clip.clone());
+    }
+
+    let wanted = normalize_name(stem);
+    if wanted.is_empty() {
+        return ClipMatch::Unmatched;
+    }
+    let mut same: Vec<String> = clips.iter().filter(|c| normalize_name(metadata::stem(c)) == wanted).cloned().collect();
+    if same.len() == 1 {
+        return ClipMatch::Unique(same.remove(0));
+    }
+    if same.is_empty() {
+        same = clips
+            .into_iter()
+            .filter(|c| {
+                let candidate = normalize_name(metadata::stem(c));
+                !candidate.is_empty() && (candidate.contains(&wanted) || wanted.contains(&candidate))
+            })
+            .collect();
+    }
+    if same.is_empty() {
+        ClipMatch::Unmatched
+    } else {
+        ClipMatch::Ambiguous(same)
+    }
+}
+
+// Command to import txt/srt/vtt transcripts, linking them to clips by name or explicit assignment;
+// existing transcripts are only replaced with `force`, and the replacement can be undone
+#[tauri::command]
+pub fn import_transcripts(
+    paths: Vec<String>,
+    assignments: Option<BTreeMap<String, String>>,
+    force: Option<bool>,
+) -> Result<Vec<ImportResult>, String> {
+    let assignments = assignments.unwrap_or_default();
+    let transcripts_dir = storage().path("output/transcripts");
+    storage().create_dir_all(&transcripts_dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let mut results = Vec::new();
+    for path in &paths {
+        // Source files are picked by the user outside the workspace
+        let source = Path::new(path);
+        let _grant = storage().allow(source).map_err(|e| format!("Failed to access {}: {}", path, e))?;
+        let extension = source
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+
+        let content = storage().read_to_string(source)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+        let (text, segments) = match extension.as_str() {
+            "txt" => (content.trim().to_string(), Vec::new()),
+            "srt" | "vtt" => {
+                let segments = parse_subtitles(&content);
+                let text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+                (text, segments)
+            }
+            _ => return Err(format!("Unsupported transcript format: {}", path)),
+        };
+
+        let source_stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("imported");
+        let clip = match assignments.get(path) {
+            Some(clip) => Some(clip.clone()),
+            None => match match_clip(source_stem) {
+                ClipMatch::Unique(clip) => Some(clip),
+                ClipMatch::Unmatched => None,
+                ClipMatch::Ambiguous(candidates) => {
+                    results.push(ImportResult {
+                        path: path.clone(),
+                        file_name: format!("{}.txt", source_stem),
+                        segments: segments.len(),
+                        clip: None,
+                        skipped: Some("No clip matches this name exactly; assign one of the candidates".to_string()),
+                        candidates,
+                    });
+                    continue;
+                }
+            },
+        };
+
+        // Linked transcripts take the clip's name so the rest of the pipeline pairs them
+        let stem = clip.as_deref().map(metadata::stem).unwrap_or(source_stem);
+        let file_name = format!("{}.txt", stem);
+        let transcript_path = transcript::transcript_path(&file_name);
+        let segments_path = transcript::segments_path(&file_name);
+
+        let mut journal = None;
+        if storage().exists(&transcript_path) {
+            if !force.unwrap_or(false) {
+                results.push(ImportResult {
+                    path: path.clone(),
+                    file_name,
+                    segments: segments.len(),
+                    clip,
+                    skipped: Some("Transcript already exists".to_string()),
+                    candidates: Vec::new(),
+                });
+                continue;
+            }
+            let mut recorder = journal::begin("import", &format!("Replace transcript {} with {}", file_name, path))?;
+            recorder.preserve(&transcript_path)?;
+            if storage().exists(&segments_path) {
+                recorder.preserve(&segments_path)?;
+            }
+            journal = Some(recorder);
+        }
+
+        storage().write(&transcript_path, &text)
+            .map_err(|e| format!("Failed to write transcript: {}", e))?;
+
+        if !segments.is_empty() {
+            let json = serde_json::json!({ "text": text, "segments": segments });
+            storage().write(&segments_path, json.to_string())
+                .map_err(|e| format!("Failed to write segments: {}", e))?;
+        } else if journal.is_some() {
+            // Segments of the replaced transcript would no longer match its text
+            storage().remove_file(&segments_path).ok();
+        }
+        if let Some(journal) = journal {
+            journal.commit()?;
+        }
+
+        audit::record("transcript.import", &file_name, serde_json::json!({ "source": path }));
+        results.push(ImportResult {
+            path: path.clone(),
+            file_name,
+            segments: segments.len(),
+            clip,
+            skipped: None,
+            candidates: Vec::new(),
+        });
+    }
+
+    Ok(results)
+}
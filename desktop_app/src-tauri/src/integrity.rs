@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::clips;
+use crate::storage::storage;
+
+// Output directories covered by the manifest
+const TRACKED_DIRS: &[&str] = &["clips", "transcripts", "json"];
+
+// Checksum record for one file; size and mtime let unchanged files skip rehashing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChecksum {
+    pub sha256: String,
+    pub size: u64,
+    pub modified: u64,
+}
+
+// Relative path under output/ -> checksum
+pub type Manifest = BTreeMap<String, FileChecksum>;
+
+fn output_dir() -> PathBuf {
+    storage().path("output")
+}
+
+fn manifest_path() -> PathBuf {
+    output_dir().join("manifest.sha256.json")
+}
+
+pub fn load_manifest() -> Manifest {
+    storage().read_to_string(manifest_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(manifest: &Manifest) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+
+    storage().write(manifest_path(), content).map_err(|e| format!("Failed to write manifest: {}", e))
+}
+
+// SHA-256 of a file, streamed so large clips are not loaded into memory
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = storage().open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// Size and mtime of a file
+fn stat(path: &Path) -> Option<(u64, u64)> {
+    let meta = storage().metadata(path).ok()?;
+    let modified = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some((meta.len(), modified))
+}
+
+// Tracked files currently on disk, as paths relative to output/
+fn tracked_files() -> Vec<String> {
+    let mut files = Vec::new();
+    // Clips may sit in clip folders below output/clips
+    let dirs = TRACKED_DIRS
+        .iter()
+        .flat_map(|dir| if *dir == "clips" { clips::media_dirs() } else { vec![output_dir().join(dir)] });
+    for dir in dirs {
+        let Ok(relative) = dir.strip_prefix(output_dir()) else { continue };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if let Ok(entries) = storage().read_dir(&dir) {
+            for entry in entries.filter_map(Result::ok) {
+                if storage().is_file(entry.path()) {
+                    files.push(format!("{}/{}", relative, entry.file_name().to_string_lossy()));
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+// Hash tracked files; with `rebaseline` false, files already in the manifest keep
+// their recorded checksum so external modifications stay detectable
+fn rebuild(rebaseline: bool) -> Result<Manifest, String> {
+    let old = load_manifest();
+    let mut manifest = Manifest::new();
+
+    for relative in tracked_files() {
+        let path = output_dir().join(&relative);
+        let (size, modified) = match stat(&path) {
+            Some(stat) => stat,
+            None => continue,
+        };
+
+        let checksum = match old.get(&relative) {
+            Some(existing) if !rebaseline => existing.clone(),
+            Some(existing) if existing.size == size && existing.modified == modified => existing.clone(),
+            _ => FileChecksum { sha256: sha256_file(&path)?, size, modified },
+        };
+        manifest.insert(relative, checksum);
+    }
+
+    save_manifest(&manifest)?;
+    Ok(manifest)
+}
+
+// Add checksums for files produced since the last update
+pub fn refresh() -> Result<Manifest, String> {
+    rebuild(false)
+}
+
+// Record the new checksum of a file the app itself just wrote
+pub fn record(path: &Path) {
+    let relative = match path.strip_prefix(output_dir()) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => return,
+    };
+
+    if let (Ok(sha256), Some((size, modified))) = (sha256_file(path), stat(path)) {
+        let mut manifest = load_manifest();
+        manifest.insert(relative, FileChecksum { sha256, size, modified });
+        save_manifest(&manifest).ok();
+    }
+}
+
+// Command to accept the current state of all files as the new baseline
+#[tauri::command]
+pub fn update_manifest() -> Result<serde_json::Value, String> {
+    let manifest = rebuild(true)?;
+    Ok(serde_json::json!({ "files": manifest.len() }))
+}
+
+// Command to check every tracked file against the manifest
+#[tauri::command]
+pub fn verify_integrity() -> Result<serde_json::Value, String> {
+    let manifest = load_manifest();
+    let mut modified = Vec::new();
+    let mut missing = Vec::new();
+
+    for (relative, checksum) in &manifest {
+        let path = output_dir().join(relative);
+        if !storage().exists(&path) {
+            missing.push(relative.clone());
+        } else if sha256_file(&path)? != checksum.sha256 {
+            modified.push(relative.clone());
+        }
+    }
+
+    let untracked: Vec<String> = tracked_files()
+        .into_iter()
+        .filter(|f| !manifest.contains_key(f))
+        .collect();
+
+    Ok(serde_json::json!({
+        "ok": modified.is_empty() && missing.is_empty(),
+        "checked": manifest.len(),
+        "modified": modified,
+        "missing": missing,
+        "untracked": untracked
+    }))
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::audit;
+use crate::clips;
+use crate::dataset;
+use crate::events::{self, LogLevel, Stage};
+use crate::integrity;
+use crate::metadata;
+use crate::settings;
+use crate::state;
+use crate::transcript;
+use crate::workspace;
+use crate::storage::storage;
+
+// How often the background janitor applies the policies
+const RUN_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const DAY: u64 = 24 * 60 * 60;
+
+// Retention policies for intermediate files, stored in settings; None keeps files forever
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanupSettings {
+    // Run the policies in the background; run_cleanup works either way
+    pub enabled: bool,
+    // Delete a clip's audio once its transcript is approved and in a generated dataset
+    pub delete_approved_audio: bool,
+    pub keep_log_days: Option<u64>,
+    // Days after screening excluded or rejected a clip before its audio is deleted
+    pub purge_quarantined_days: Option<u64>,
+}
+
+impl Default for CleanupSettings {
+    fn default() -> Self {
+        CleanupSettings { enabled: false, delete_approved_audio: false, keep_log_days: Some(30), purge_quarantined_days: Some(30) }
+    }
+}
+
+// A file a policy deletes
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupItem {
+    pub path: String,
+    // "approved_audio", "log" or "quarantined"
+    pub policy: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub items: Vec<CleanupItem>,
+    pub bytes: u64,
+    pub errors: Vec<String>,
+}
+
+fn item(path: PathBuf, policy: &str) -> CleanupItem {
+    let bytes = storage().metadata(&path).map_or(0, |m| m.len());
+    CleanupItem { path: path.to_string_lossy().to_string(), policy: policy.to_string(), bytes }
+}
+
+fn older_than(path: &PathBuf, days: u64) -> bool {
+    storage()
+        .metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age.as_secs() >= days * DAY)
+}
+
+// Files the policies would delete now
+pub fn plan(config: &CleanupSettings) -> Result<Vec<CleanupItem>, String> {
+    let mut items = Vec::new();
+    let current = state::load()?;
+    let now = state::now();
+
+    let used = if config.delete_approved_audio { dataset::sources_in_datasets() } else { BTreeSet::new() };
+    for clip in clips::list_clips() {
+        let transcript = format!("{}.txt", metadata::stem(&clip));
+        let path = clips::media_file(metadata::stem(&clip), "mp3");
+
+        // The info JSON stays, so provenance and attribution keep working
+        if config.delete_approved_audio
+            && transcript::review_status(&current, &transcript) == "approved"
+            && used.contains(&transcript)
+        {
+            items.push(item(path, "approved_audio"));
+            continue;
+        }
+
+        let quarantined = current
+            .screening
+            .get(&transcript)
+            .filter(|r| r.status == "excluded" || r.status == "rejected");
+        if let (Some(days), Some(result)) = (config.purge_quarantined_days, quarantined) {
+            if now.saturating_sub(result.screened_at) >= days * DAY {
+                items.push(item(path, "quarantined"));
+            }
+        }
+    }
+
+    if let Some(days) = config.keep_log_days {
+        if let Ok(entries) = storage().read_dir(storage().path("output/logs/jobs")) {
+            for entry in entries.filter_map(Result::ok) {
+                let path = entry.path();
+                if path.extension().is_some_and(|e| e == "log") && older_than(&path, days) {
+                    items.push(item(path, "log"));
+                }
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+// Apply the policies, or only report what they would delete
+pub fn run(dry_run: bool) -> Result<CleanupReport, String> {
+    if !dry_run {
+        workspace::ensure_writable()?;
+    }
+
+    let items = plan(&settings::load().cleanup)?;
+    let mut report = CleanupReport { dry_run, bytes: items.iter().map(|i| i.bytes).sum(), ..Default::default() };
+    for item in items {
+        if !dry_run {
+            if let Err(e) = storage().remove_file(&item.path) {
+                report.errors.push(format!("Failed to delete {}: {}", item.path, e));
+                report.bytes -= item.bytes;
+                continue;
+            }
+        }
+        report.items.push(item);
+    }
+
+    if !dry_run && !report.items.is_empty() {
+        // Deleted files would otherwise be reported as missing
+        integrity::refresh().ok();
+        audit::record(
+            "cleanup.run",
+            "workspace",
+            serde_json::json!({ "files": report.items.len(), "bytes": report.bytes }),
+        );
+    }
+    Ok(report)
+}
+
+// Start the background janitor; it only deletes while cleanup is enabled
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(RUN_INTERVAL);
+        if !settings::load().cleanup.enabled || workspace::is_read_only() {
+            continue;
+        }
+        match run(false) {
+            Ok(report) if !report.items.is_empty() => {
+                let message = format!("Cleanup deleted {} files ({} bytes)", report.items.len(), report.bytes);
+                events::log(&app, Stage::Analysis, LogLevel::Info, &message);
+            }
+            Ok(_) => {}
+            Err(e) => events::log(&app, Stage::Analysis, LogLevel::Warn, &format!("Cleanup failed: {}", e)),
+        }
+    });
+}
+
+// Command to apply the retention policies now; with dry_run it only reports what would be deleted
+#[tauri::command]
+pub fn run_cleanup(dry_run: bool) -> Result<CleanupReport, String> {
+    run(dry_run)
+}
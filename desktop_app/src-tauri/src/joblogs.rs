@@ -0,0 +1,58 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::state;
+use crate::storage::storage;
+
+// Output of every tool a job ran, one file per job
+pub fn log_path(job_id: &str) -> PathBuf {
+    storage().path("output/logs/jobs").join(format!("{}.log", job_id))
+}
+
+// Open the job's log for appending, creating it on first use
+pub fn open(job_id: &str) -> Option<File> {
+    let path = log_path(job_id);
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent).ok()?;
+    }
+    storage().append(path).ok()
+}
+
+// Header written before a tool's output, e.g. "--- 1718000000 $ yt-dlp <url> ..."
+pub fn command_line(program: &str, args: &[std::ffi::OsString]) -> String {
+    let args: Vec<String> = args.iter().map(|a| a.to_string_lossy().to_string()).collect();
+    format!("--- {} $ {} {}\n", state::now(), program, args.join(" "))
+}
+
+// Append a line that did not come from a child process, such as a worker reply
+pub fn append(job_id: &str, line: &str) {
+    if let Some(mut file) = open(job_id) {
+        writeln!(file, "{}", line.trim_end()).ok();
+    }
+}
+
+// Command to read a job's captured tool output, optionally only its last `tail` lines
+#[tauri::command]
+pub fn get_job_logs(job_id: String, tail: Option<usize>) -> Result<String, String> {
+    // Job ids are "<seconds>-<nanos>"; anything else could point outside the log directory
+    if job_id.is_empty() || !job_id.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Err(format!("Invalid job id: {}", job_id));
+    }
+
+    let path = log_path(&job_id);
+    if !storage().exists(&path) {
+        return Err(format!("No logs for job {}", job_id));
+    }
+    let content = storage().read_to_string(&path).map_err(|e| format!("Failed to read job logs: {}", e))?;
+
+    Ok(match tail {
+        Some(tail) => {
+            let lines: Vec<&str> = content.lines().collect();
+            let mut tailed = lines[lines.len().saturating_sub(tail)..].join("\n");
+            tailed.push('\n');
+            tailed
+        }
+        None => content,
+    })
+}
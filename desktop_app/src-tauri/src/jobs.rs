@@ -0,0 +1,418 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::events::Stage;
+use crate::history::DownloadRecord;
+use crate::i18n;
+use crate::manifest::ExportRecord;
+use crate::partial;
+use crate::state;
+use crate::storage::storage;
+
+// Number of finished jobs kept for get_job
+const MAX_FINISHED_JOBS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+// A download or transcription batch and its observed throughput
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: String,
+    pub stage: Stage,
+    pub status: JobStatus,
+    pub items: Vec<String>,
+    pub completed: usize,
+    pub current_item: Option<String>,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub elapsed_seconds: f64,
+    // Audio minutes processed per wall-clock minute, once an item with a known duration finished
+    pub throughput: Option<f64>,
+    pub eta_seconds: Option<u64>,
+    // Notes per item, e.g. throttling mitigations applied before a retry
+    pub history: BTreeMap<String, Vec<String>>,
+    // Bytes already on disk per unfinished download, filled in when a download job is read
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub partial_bytes: BTreeMap<String, u64>,
+    // Last sign of life: an item starting or finishing, or output from its child process
+    pub last_heartbeat: u64,
+    // No heartbeat for longer than the stall timeout; cleared when output resumes
+    pub stalled: bool,
+    #[serde(skip)]
+    kill_requested: bool,
+    // Audio duration per item in seconds, when known up front
+    #[serde(skip)]
+    durations: Vec<Option<f64>>,
+    #[serde(skip)]
+    started: Instant,
+    #[serde(skip)]
+    processed_audio: f64,
+    #[serde(skip)]
+    processed_wall: f64,
+}
+
+impl Job {
+    fn update_estimate(&mut self) {
+        self.elapsed_seconds = self.started.elapsed().as_secs_f64();
+        let remaining = &self.durations[self.completed.min(self.durations.len())..];
+
+        // Audio-based estimate when every remaining item has a duration, else the per-item average
+        self.eta_seconds = match self.throughput {
+            Some(rate) if rate > 0.0 && remaining.iter().all(Option::is_some) => {
+                let audio: f64 = remaining.iter().flatten().sum();
+                Some((audio / rate).round() as u64)
+            }
+            _ if self.completed > 0 => {
+                let per_item = self.elapsed_seconds / self.completed as f64;
+                Some((per_item * remaining.len() as f64).round() as u64)
+            }
+            _ => None,
+        };
+    }
+}
+
+// A finished job as kept in the state store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub stage: Stage,
+    pub status: JobStatus,
+    pub items: Vec<String>,
+    pub completed: usize,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub elapsed_seconds: f64,
+    pub throughput: Option<f64>,
+    #[serde(default)]
+    pub history: BTreeMap<String, Vec<String>>,
+}
+
+impl From<&Job> for JobRecord {
+    fn from(job: &Job) -> Self {
+        JobRecord {
+            id: job.id.clone(),
+            stage: job.stage,
+            status: job.status,
+            items: job.items.clone(),
+            completed: job.completed,
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+            elapsed_seconds: job.elapsed_seconds,
+            throughput: job.throughput,
+            history: job.history.clone(),
+        }
+    }
+}
+
+static JOBS: Mutex<BTreeMap<String, Job>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    // Job whose items the current thread is working on, so tool runs can report heartbeats
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn with_job<T>(id: &str, f: impl FnOnce(&mut Job) -> T) -> Option<T> {
+    JOBS.lock().unwrap_or_else(|e| e.into_inner()).get_mut(id).map(f)
+}
+
+// Register a batch; `durations` holds the audio length of each item when known
+pub fn start(stage: Stage, items: Vec<String>, durations: Vec<Option<f64>>) -> String {
+    let started_at = state::now();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let id = format!("{}-{:09}", started_at, nanos);
+
+    let mut durations = durations;
+    durations.resize(items.len(), None);
+
+    let job = Job {
+        id: id.clone(),
+        stage,
+        status: JobStatus::Running,
+        items,
+        completed: 0,
+        current_item: None,
+        started_at,
+        finished_at: None,
+        elapsed_seconds: 0.0,
+        throughput: None,
+        eta_seconds: None,
+        history: BTreeMap::new(),
+        partial_bytes: BTreeMap::new(),
+        last_heartbeat: started_at,
+        stalled: false,
+        kill_requested: false,
+        durations,
+        started: Instant::now(),
+        processed_audio: 0.0,
+        processed_wall: 0.0,
+    };
+
+    let mut jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    let finished: Vec<String> = jobs
+        .values()
+        .filter(|j| j.status != JobStatus::Running)
+        .map(|j| j.id.clone())
+        .collect();
+    for old in finished.iter().take(finished.len().saturating_sub(MAX_FINISHED_JOBS - 1)) {
+        jobs.remove(old);
+    }
+    jobs.insert(id.clone(), job);
+    id
+}
+
+// Mark the next item as in progress
+pub fn item_started(id: &str, item: &str) {
+    CURRENT.with(|c| *c.borrow_mut() = Some(id.to_string()));
+    with_job(id, |job| {
+        job.current_item = Some(item.to_string());
+        job.update_estimate();
+    });
+    heartbeat(id);
+}
+
+// Job the current thread is working on, if any
+pub fn current() -> Option<String> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+// Record a sign of life; a stalled job that produces output again is no longer stalled
+pub fn heartbeat(id: &str) {
+    with_job(id, |job| {
+        job.last_heartbeat = state::now();
+        job.stalled = false;
+    });
+}
+
+// Flag a running job as stalled; true when it was not flagged already
+pub fn mark_stalled(id: &str) -> bool {
+    with_job(id, |job| !std::mem::replace(&mut job.stalled, true)).unwrap_or(false)
+}
+
+// Ask the job's running child process to be killed and its remaining items skipped
+pub fn request_kill(id: &str) -> bool {
+    with_job(id, |job| job.status == JobStatus::Running && !std::mem::replace(&mut job.kill_requested, true))
+        .unwrap_or(false)
+}
+
+pub fn kill_requested(id: &str) -> bool {
+    with_job(id, |job| job.kill_requested).unwrap_or(false)
+}
+
+// Called periodically while the current thread waits for a scheduler or host slot: keeps its
+// job from being flagged as stalled, and gives up once the job is killed
+pub fn waiting() -> Result<(), String> {
+    let Some(id) = current() else { return Ok(()) };
+    if kill_requested(&id) {
+        return Err(i18n::message("error-job-killed-waiting", &[("job", &id)]));
+    }
+    heartbeat(&id);
+    Ok(())
+}
+
+// Record that the current item finished after `wall_seconds` and update the estimate
+pub fn item_finished(id: &str, wall_seconds: f64) {
+    with_job(id, |job| {
+        if let Some(Some(audio)) = job.durations.get(job.completed) {
+            job.processed_audio += audio;
+            job.processed_wall += wall_seconds;
+            if job.processed_wall > 0.0 {
+                job.throughput = Some(job.processed_audio / job.processed_wall);
+            }
+        }
+        job.completed += 1;
+        job.current_item = None;
+        job.update_estimate();
+    });
+    heartbeat(id);
+}
+
+// Add a note to an item's history, e.g. a smaller model used in place of the chosen one
+pub fn item_note(id: &str, item: &str, note: &str) {
+    with_job(id, |job| job.history.entry(item.to_string()).or_default().push(note.to_string()));
+}
+
+// Close the current attempt and put the item back at the end of the batch,
+// recording why in its history
+pub fn item_requeued(id: &str, item: &str, note: &str) {
+    with_job(id, |job| {
+        job.history.entry(item.to_string()).or_default().push(note.to_string());
+        job.items.push(item.to_string());
+        job.durations.push(None);
+        job.completed += 1;
+        job.current_item = None;
+        job.update_estimate();
+    });
+}
+
+pub fn finish(id: &str, success: bool) {
+    CURRENT.with(|c| c.borrow_mut().take());
+    let record = with_job(id, |job| {
+        job.status = if success { JobStatus::Completed } else { JobStatus::Failed };
+        job.finished_at = Some(state::now());
+        job.current_item = None;
+        job.stalled = false;
+        job.eta_seconds = Some(0);
+        job.elapsed_seconds = job.started.elapsed().as_secs_f64();
+        JobRecord::from(&*job)
+    });
+
+    // Only the last few jobs stay in memory; the state store keeps all of them
+    if let Some(record) = record {
+        state::update(|s| s.job_history.push(record)).ok();
+    }
+}
+
+// Running jobs of a stage
+pub fn running(stage: Stage) -> Vec<Job> {
+    JOBS.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .filter(|job| job.stage == stage && job.status == JobStatus::Running)
+        .cloned()
+        .collect()
+}
+
+pub fn get(id: &str) -> Option<Job> {
+    JOBS.lock().unwrap_or_else(|e| e.into_inner()).get(id).cloned()
+}
+
+// Report partial-file sizes of the items a running download job has not finished yet
+fn with_partials(mut job: Job) -> Job {
+    if job.stage == Stage::Download && job.status == JobStatus::Running {
+        job.partial_bytes = partial::partial_bytes(&job.items[job.completed.min(job.items.len())..]);
+    }
+    job
+}
+
+// Command to get a batch job with its progress and estimated time remaining
+#[tauri::command]
+pub fn get_job(job_id: String) -> Result<Job, String> {
+    get(&job_id).map(with_partials).ok_or_else(|| format!("Job not found: {}", job_id))
+}
+
+// Command to list running and recently finished jobs
+#[tauri::command]
+pub fn get_jobs() -> Vec<Job> {
+    let jobs: Vec<Job> = JOBS.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect();
+    jobs.into_iter().map(with_partials).collect()
+}
+
+// Command to list every finished job, newest first, including imported ones
+#[tauri::command]
+pub fn get_job_history() -> Result<Vec<JobRecord>, String> {
+    Ok(state::load()?.job_history.into_iter().rev().collect())
+}
+
+// One line of an exported processing history
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum HistoryLine {
+    Job(JobRecord),
+    Download(DownloadRecord),
+    Export(Box<ExportRecord>),
+}
+
+// Command to write the job, download and export history to a JSONL file, one record per line
+#[tauri::command]
+pub fn export_job_history(path: String) -> Result<usize, String> {
+    let current = state::load()?;
+    let lines: Vec<HistoryLine> = current
+        .job_history
+        .into_iter()
+        .map(HistoryLine::Job)
+        .chain(current.download_history.into_iter().map(HistoryLine::Download))
+        .chain(current.export_history.into_iter().map(|e| HistoryLine::Export(Box::new(e))))
+        .collect();
+
+    let mut content = String::new();
+    for line in &lines {
+        let json = serde_json::to_string(line).map_err(|e| format!("Failed to serialize history: {}", e))?;
+        content.push_str(&json);
+        content.push('\n');
+    }
+
+    // The target is picked by the user, usually next to a project archive
+    let _grant = storage().allow(&path).map_err(|e| format!("Failed to access history: {}", e))?;
+    if let Some(parent) = std::path::Path::new(&path).parent().filter(|p| !p.as_os_str().is_empty()) {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    storage().write(&path, content).map_err(|e| format!("Failed to write history: {}", e))?;
+    Ok(lines.len())
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct HistoryImport {
+    pub jobs: usize,
+    pub downloads: usize,
+    pub exports: usize,
+    // Records already present, e.g. from importing the same file twice
+    pub skipped: usize,
+}
+
+// Command to merge a JSONL history written by export_job_history into this workspace
+#[tauri::command]
+pub fn import_job_history(path: String) -> Result<HistoryImport, String> {
+    let _grant = storage().allow(&path).map_err(|e| format!("Failed to access history: {}", e))?;
+    let content = storage().read_to_string(&path).map_err(|e| format!("Failed to read history: {}", e))?;
+
+    let mut lines = Vec::new();
+    for (number, line) in content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let parsed: HistoryLine = serde_json::from_str(line)
+            .map_err(|e| format!("Failed to parse history line {}: {}", number + 1, e))?;
+        lines.push(parsed);
+    }
+
+    let summary = state::update(|s| {
+        let mut summary = HistoryImport::default();
+        for line in lines {
+            match line {
+                HistoryLine::Job(job) if !s.job_history.iter().any(|j| j.id == job.id) => {
+                    s.job_history.push(job);
+                    summary.jobs += 1;
+                }
+                HistoryLine::Download(download)
+                    if !s.download_history.iter().any(|d| {
+                        d.url == download.url && d.downloaded_at == download.downloaded_at
+                    }) =>
+                {
+                    s.download_history.push(download);
+                    summary.downloads += 1;
+                }
+                HistoryLine::Export(export)
+                    if !s.export_history.iter().any(|e| e.manifest.sha256 == export.manifest.sha256
+                        && e.manifest.created_at == export.manifest.created_at) =>
+                {
+                    s.export_history.push(*export);
+                    summary.exports += 1;
+                }
+                _ => summary.skipped += 1,
+            }
+        }
+
+        // Histories are kept oldest first
+        s.job_history.sort_by_key(|j| j.started_at);
+        s.download_history.sort_by_key(|d| d.downloaded_at);
+        s.export_history.sort_by_key(|e| e.manifest.created_at);
+        summary
+    })?;
+
+    audit::record(
+        "history.import",
+        &path,
+        serde_json::json!({ "jobs": summary.jobs, "downloads": summary.downloads, "exports": summary.exports }),
+    );
+    Ok(summary)
+}
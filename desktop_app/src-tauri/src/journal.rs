@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::state;
+use crate::workspace;
+use crate::storage::storage;
+
+// Number of operations that can be undone; older backups are discarded
+const MAX_OPERATIONS: usize = 20;
+
+// Serializes journal updates
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+// A file touched by an operation and where its previous content was kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalFile {
+    pub original: PathBuf,
+    pub backup: PathBuf,
+}
+
+// A destructive operation with enough data to reverse it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Operation {
+    pub id: String,
+    pub kind: String,
+    pub description: String,
+    pub created_at: u64,
+    pub files: Vec<JournalFile>,
+}
+
+fn journal_dir() -> PathBuf {
+    storage().path("output/journal")
+}
+
+fn journal_path() -> PathBuf {
+    journal_dir().join("journal.json")
+}
+
+fn load() -> Vec<Operation> {
+    storage().read_to_string(journal_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(operations: &[Operation]) -> Result<(), String> {
+    workspace::ensure_writable()?;
+    let content = serde_json::to_string_pretty(operations)
+        .map_err(|e| format!("Failed to serialize journal: {}", e))?;
+
+    storage().write(journal_path(), content).map_err(|e| format!("Failed to write journal: {}", e))
+}
+
+// Collects backups for one operation; nothing is journaled until `commit`
+pub struct Recorder {
+    operation: Operation,
+    dir: PathBuf,
+}
+
+// Start recording a destructive operation; fails when the workspace is read-only
+pub fn begin(kind: &str, description: &str) -> Result<Recorder, String> {
+    workspace::ensure_writable()?;
+    let created_at = state::now();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let id = format!("{}-{:09}", created_at, nanos);
+
+    Ok(Recorder {
+        dir: journal_dir().join(&id),
+        operation: Operation {
+            id,
+            kind: kind.to_string(),
+            description: description.to_string(),
+            created_at,
+            files: Vec::new(),
+        },
+    })
+}
+
+impl Recorder {
+    fn next_backup(&self) -> Result<PathBuf, String> {
+        storage().create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+        Ok(self.dir.join(self.operation.files.len().to_string()))
+    }
+
+    // Delete a file by moving it into the journal
+    pub fn remove(&mut self, path: &Path) -> Result<(), String> {
+        let backup = self.next_backup()?;
+        if storage().rename(path, &backup).is_err() {
+            // Fall back to copy + delete across filesystems
+            storage().copy(path, &backup).map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+            storage().remove_file(path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        }
+
+        self.operation.files.push(JournalFile { original: path.to_path_buf(), backup });
+        Ok(())
+    }
+
+    // Keep a copy of a file that is about to be overwritten
+    pub fn preserve(&mut self, path: &Path) -> Result<(), String> {
+        let backup = self.next_backup()?;
+        storage().copy(path, &backup).map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+
+        self.operation.files.push(JournalFile { original: path.to_path_buf(), backup });
+        Ok(())
+    }
+
+    // Append the operation to the journal, pruning the oldest beyond the limit
+    pub fn commit(self) -> Result<(), String> {
+        if self.operation.files.is_empty() {
+            return Ok(());
+        }
+
+        let _guard = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        audit::record(
+            &self.operation.kind,
+            &self.operation.description,
+            serde_json::json!({ "operation": self.operation.id, "files": self.operation.files.len() }),
+        );
+        let mut operations = load();
+        operations.push(self.operation);
+
+        while operations.len() > MAX_OPERATIONS {
+            let old = operations.remove(0);
+            storage().remove_dir_all(journal_dir().join(&old.id)).ok();
+        }
+
+        save(&operations)
+    }
+}
+
+// Command to list undoable operations, most recent first
+#[tauri::command]
+pub fn get_operation_journal() -> Result<Vec<Operation>, String> {
+    let mut operations = load();
+    operations.reverse();
+    Ok(operations)
+}
+
+// Command to restore the files changed by the most recent destructive operation
+#[tauri::command]
+pub fn undo_last_operation() -> Result<Option<Operation>, String> {
+    let _guard = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut operations = load();
+
+    let operation = match operations.pop() {
+        Some(operation) => operation,
+        None => return Ok(None),
+    };
+
+    // Restore in reverse so a file preserved twice ends at its oldest content
+    for file in operation.files.iter().rev() {
+        if let Some(parent) = file.original.parent() {
+            storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        storage().copy(&file.backup, &file.original)
+            .map_err(|e| format!("Failed to restore {}: {}", file.original.display(), e))?;
+    }
+
+    storage().remove_dir_all(journal_dir().join(&operation.id)).ok();
+    save(&operations)?;
+    audit::record("undo", &operation.description, serde_json::json!({ "operation": operation.id }));
+
+    Ok(Some(operation))
+}
@@ -0,0 +1,750 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use tauri::AppHandle;
+
+use events::{LogLevel, Stage, StageStatus};
+use pipeline::Invocation;
+use storage::storage;
+use transcript::SaveError;
+
+mod align;
+mod anki;
+mod annotations;
+mod anonymize;
+mod api_tokens;
+mod audit;
+mod autorebuild;
+mod batch;
+mod boilerplate;
+mod citations;
+mod clips;
+mod coldstorage;
+mod compress;
+mod contamination;
+mod crash;
+mod dataset;
+mod drafts;
+mod direct;
+mod duplicates;
+mod editor;
+mod epub;
+mod events;
+mod excerpt;
+mod export;
+mod flagging;
+mod glossary;
+mod groundtruth;
+mod grpc;
+mod history;
+mod http;
+mod i18n;
+mod import;
+mod integrity;
+mod janitor;
+mod joblogs;
+mod jobs;
+mod journal;
+mod licenses;
+mod live;
+mod manifest;
+mod mcp;
+mod metadata;
+mod migrate;
+mod naming;
+mod notify;
+mod numerals;
+mod openapi;
+mod optouts;
+mod organize;
+mod partial;
+mod permissions;
+mod persona;
+pub mod pipeline;
+pub mod portable;
+mod quality;
+mod questions;
+mod ratelimit;
+mod release;
+mod render;
+mod report;
+mod review_server;
+mod roundtrip;
+mod runs;
+mod sanitize;
+mod scheduler;
+mod screening;
+mod sections;
+mod settings;
+mod sharing;
+mod site;
+mod sources;
+mod speakers;
+mod spelling;
+mod state;
+mod storage;
+mod subtitles;
+mod tags;
+mod telemetry;
+mod text;
+mod transcript;
+mod translation;
+mod transliterate;
+mod updater;
+mod video;
+mod vram;
+mod watchdog;
+mod wer;
+mod whisper;
+mod workspace;
+mod ytdlp;
+mod zip;
+
+// Define the pipeline directory path (see portable.rs for the portable-mode override)
+const PIPELINE_DIR: &str = "../../pipeline";
+
+// Command to download videos from YouTube
+#[tauri::command]
+fn download_videos(app: AppHandle, links: Vec<String>, force: Option<bool>) -> Result<String, String> {
+    workspace::ensure_writable()?;
+    events::stage_change(&app, Stage::Download, StageStatus::Started);
+
+    // Links from blocked or unlisted sources never enter the pipeline
+    let (links, rejected) = sources::screen(links);
+    for (link, reason) in &rejected {
+        events::log(&app, Stage::Download, LogLevel::Warn, &format!("Skipped {}: {}", link, reason));
+    }
+
+    // Already downloaded or queued links are skipped unless a re-download is forced
+    let links = if force.unwrap_or(false) {
+        links
+    } else {
+        let (fresh, duplicates) = duplicates::find(links)?;
+        for duplicate in &duplicates {
+            let message = format!("Skipped duplicate {} ({})", duplicate.link, duplicate.reason);
+            events::log(&app, Stage::Download, LogLevel::Warn, &message);
+        }
+        fresh
+    };
+
+    // Extractor breakage is the most common failure, so large batches can update yt-dlp first
+    let ytdlp_version = ytdlp::prepare_batch(&app, links.len());
+
+    let mut stdout = String::new();
+    let mut failures = Vec::new();
+    let job = jobs::start(Stage::Download, links.clone(), Vec::new());
+    let retries = settings::load().download_policy.throttle_retries;
+
+    // Throttled links go back to the end of the queue and are retried at reduced speed
+    let mut queue: VecDeque<(&String, usize)> = links.iter().map(|link| (link, 0)).collect();
+    while let Some((link, attempt)) = queue.pop_front() {
+        if jobs::kill_requested(&job) {
+            failures.push(format!("Job killed before {}", link));
+            break;
+        }
+        jobs::item_started(&job, link);
+        events::job_progress(&app, &job);
+
+        // Run yt-dlp command
+        let started = Instant::now();
+        let download = pipeline::download_link(link).inspect_err(|_| jobs::finish(&job, false))?;
+        let output = &download.output;
+
+        if !output.success && attempt < retries && ratelimit::is_throttled(&output.stderr) {
+            let backoff = ratelimit::throttled(link);
+            let note = format!(
+                "Throttled on attempt {}; retrying with {}x delays and one download per host",
+                attempt + 1,
+                1u32 << backoff.min(6)
+            );
+            events::log(&app, Stage::Download, LogLevel::Warn, &format!("{}: {}", link, note));
+            jobs::item_requeued(&job, link, &note);
+            queue.push_back((link, attempt + 1));
+            continue;
+        }
+        jobs::item_finished(&job, started.elapsed().as_secs_f64());
+        history::record(link, &download, attempt + 1, ytdlp_version.as_deref());
+
+        stdout.push_str(&output.stdout);
+        if !output.success {
+            events::error(&app, Stage::Download, &output.stderr, Some(link));
+            failures.push(output.stderr.clone());
+        }
+    }
+
+    jobs::finish(&job, failures.is_empty());
+    events::job_progress(&app, &job);
+
+    // Record checksums of the new clips
+    integrity::refresh().ok();
+
+    if failures.is_empty() {
+        events::stage_change(&app, Stage::Download, StageStatus::Completed);
+        Ok(stdout)
+    } else {
+        let message = format!("{} of {} downloads failed", failures.len(), links.len());
+        events::log(&app, Stage::Download, LogLevel::Warn, &message);
+        events::stage_change(&app, Stage::Download, StageStatus::Failed);
+        Err(failures.join("\n"))
+    }
+}
+
+// Command to transcribe audio files; a named `run` writes to output/transcripts/<run> instead
+// of the main transcripts so models can be compared, using the run name as model by default
+#[tauri::command]
+fn transcribe_audio(app: AppHandle, run: Option<String>, model: Option<String>) -> Result<String, String> {
+    workspace::ensure_writable()?;
+    let model = model.or_else(|| run.clone()).unwrap_or_else(|| settings::load().whisper_model);
+    // Fails fast when the model cannot fit in GPU memory
+    let plan = vram::prepare(&model)?;
+    if let Some(note) = &plan.note {
+        let level = if plan.replaced.is_some() { LogLevel::Warn } else { LogLevel::Info };
+        events::log(&app, Stage::Transcribe, level, note);
+    }
+    let model = plan.model.clone();
+    let output_dir = match &run {
+        Some(run) => {
+            runs::begin_run(run, &model)?;
+            format!("output/transcripts/{}", run)
+        }
+        None => "output/transcripts".to_string(),
+    };
+    events::stage_change(&app, Stage::Transcribe, StageStatus::Started);
+
+    let clips = clips::list_clips();
+
+    let mut stdout = String::new();
+    let mut failures = Vec::new();
+    let durations = clips.iter().map(|c| metadata::load(c).and_then(|m| m.duration)).collect();
+    let job = jobs::start(Stage::Transcribe, clips.clone(), durations);
+
+    for clip in &clips {
+        if jobs::kill_requested(&job) {
+            failures.push(format!("Job killed before {}", clip));
+            break;
+        }
+        jobs::item_started(&job, clip);
+        events::job_progress(&app, &job);
+
+        // Run the transcription for a single clip
+        let started = Instant::now();
+        let output = pipeline::transcribe_clip_to(clip, &plan, &output_dir);
+        jobs::item_finished(&job, started.elapsed().as_secs_f64());
+        let output = output.inspect_err(|_| jobs::finish(&job, false))?;
+
+        stdout.push_str(&output.stdout);
+        if !output.success {
+            events::error(&app, Stage::Transcribe, &output.stderr, Some(clip));
+            failures.push(output.stderr);
+        } else {
+            // Runs keep the raw model output for comparison
+            if run.is_none() {
+                if let Err(e) = glossary::apply_to_transcript(&format!("{}.txt", metadata::stem(clip))) {
+                    events::log(&app, Stage::Transcribe, LogLevel::Warn, &e);
+                }
+            }
+            match groundtruth::score(clip, run.as_deref(), &model) {
+                Ok(Some(score)) => {
+                    let message = format!("{}: WER {:.1}% against ground truth", clip, score.wer * 100.0);
+                    events::log(&app, Stage::Transcribe, LogLevel::Info, &message);
+                }
+                Ok(None) => {}
+                Err(e) => events::log(&app, Stage::Transcribe, LogLevel::Warn, &e),
+            }
+        }
+    }
+
+    jobs::finish(&job, failures.is_empty());
+    events::job_progress(&app, &job);
+
+    // Record checksums of the new transcripts
+    integrity::refresh().ok();
+
+    if failures.is_empty() {
+        events::stage_change(&app, Stage::Transcribe, StageStatus::Completed);
+        Ok(stdout)
+    } else {
+        let message = format!("{} of {} transcriptions failed", failures.len(), clips.len());
+        events::log(&app, Stage::Transcribe, LogLevel::Warn, &message);
+        events::stage_change(&app, Stage::Transcribe, StageStatus::Failed);
+        Err(failures.join("\n"))
+    }
+}
+
+// Command to generate JSON for a dataset (the default dataset when none is given)
+#[tauri::command]
+fn generate_json(app: AppHandle, dataset: Option<String>, persona: Option<String>) -> Result<String, String> {
+    workspace::ensure_writable()?;
+    let config = dataset::dataset_config(dataset.as_deref())?;
+    let persona = persona::persona(persona.as_deref().or(config.persona.as_deref()))?;
+    let persona_json = serde_json::to_string(&persona)
+        .map_err(|e| format!("Failed to serialize persona: {}", e))?;
+    let dataset_path = dataset::dataset_path(Some(&config.id))?;
+    if let Some(parent) = dataset_path.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    events::stage_change(&app, Stage::Dataset, StageStatus::Started);
+
+    // Flagged or excluded items are dropped from the new entries in postprocess
+    screening::run_if_enabled()?;
+    flagging::run_if_enabled()?;
+
+    // Run the Python script
+    let output = Invocation::new("python3")
+        .args(["scripts/make_quran_lora_json.py", "output/transcripts", &config.path])
+        .arg(config.granularity.script_arg())
+        .arg(persona_json)
+        .arg(if config.punctuation { "1" } else { "0" })
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute JSON generation: {}", e))?;
+
+    if output.success {
+        // Add topics, language and source attribution to the new entries
+        let (count, stripped) = dataset::postprocess(&dataset_path, &config)?;
+        integrity::record(&dataset_path);
+        if !stripped.is_empty() {
+            let total: usize = stripped.values().sum();
+            let phrases: Vec<String> = stripped.iter().map(|(phrase, n)| format!("\"{}\" ×{}", phrase, n)).collect();
+            events::log(&app, Stage::Dataset, LogLevel::Info, &format!("Stripped {} boilerplate phrases: {}", total, phrases.join(", ")));
+        }
+        let parameters = serde_json::json!({
+            "filter": config.filter,
+            "granularity": config.granularity,
+            "persona": persona,
+            "stripped_phrases": stripped,
+        });
+        if let Err(e) = manifest::record("generate", &config.id, &dataset_path, count, parameters) {
+            events::log(&app, Stage::Dataset, LogLevel::Warn, &e);
+        }
+        events::log(&app, Stage::Dataset, LogLevel::Info, &format!("Generated {} entries", count));
+        events::stage_change(&app, Stage::Dataset, StageStatus::Completed);
+        Ok(output.stdout)
+    } else {
+        let stderr = output.stderr;
+        events::error(&app, Stage::Dataset, &stderr, None);
+        events::stage_change(&app, Stage::Dataset, StageStatus::Failed);
+        Err(stderr)
+    }
+}
+
+// Command to get pipeline status
+#[tauri::command]
+fn get_status(dataset: Option<String>) -> Result<serde_json::Value, String> {
+    let transcripts_dir = storage().path("output/transcripts");
+    let json_file = dataset::dataset_path(dataset.as_deref())?;
+
+    // Count MP3 files
+    let clips_count = clips::list_clips().len();
+
+    // Count transcript files
+    let transcripts_count = storage().read_dir(transcripts_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| {
+                    e.path().extension().is_some_and(|ext| ext == "txt")
+                })
+                .count()
+        })
+        .unwrap_or(0);
+
+    // Check if JSON exists and count entries
+    let json_exists = storage().exists(&json_file);
+    let json_count = if json_exists {
+        dataset::entry_count(&json_file).unwrap_or(0)
+    } else {
+        0
+    };
+
+    // Create the status JSON
+    let status = serde_json::json!({
+        "clips": clips_count,
+        "transcripts": transcripts_count,
+        "json_exists": json_exists,
+        "json_count": json_count
+    });
+
+    Ok(status)
+}
+
+// Command to get a filtered page of transcripts, sorted by name, date, word_count or confidence
+#[tauri::command]
+fn get_transcripts(
+    page: Option<usize>,
+    page_size: Option<usize>,
+    sort_by: Option<String>,
+    descending: Option<bool>,
+    filter: Option<transcript::TranscriptFilter>,
+) -> Result<serde_json::Value, String> {
+    let state = state::load()?;
+    let mut files = transcript::stat_transcripts();
+    let mut cache = state.transcript_cache.clone();
+    let mut dirty = false;
+
+    if let Some(filter) = filter {
+        let matcher = filter.compile()?;
+        files.retain(|file| matcher.matches(file, &state, &mut cache, &mut dirty));
+    }
+
+    // Sorting by content-derived keys needs every summary; name and date do not
+    let sort_by = sort_by.unwrap_or_else(|| "name".to_string());
+    match sort_by.as_str() {
+        "name" => files.sort_by(|a, b| a.file_name.cmp(&b.file_name)),
+        "date" => files.sort_by_key(|f| f.modified),
+        "word_count" | "confidence" => {
+            let mut keyed: Vec<(transcript::TranscriptFile, f64)> = files
+                .into_iter()
+                .map(|file| {
+                    let summary = transcript::cached_summary(&mut cache, &file, &mut dirty);
+                    let key = match (&summary, sort_by.as_str()) {
+                        (Some(s), "word_count") => s.word_count as f64,
+                        (Some(s), _) => s.confidence.unwrap_or(0.0),
+                        (None, _) => 0.0,
+                    };
+                    (file, key)
+                })
+                .collect();
+            keyed.sort_by(|a, b| a.1.total_cmp(&b.1));
+            files = keyed.into_iter().map(|(file, _)| file).collect();
+        }
+        other => return Err(i18n::message("error-unknown-sort-key", &[("key", other)])),
+    }
+    if descending.unwrap_or(false) {
+        files.reverse();
+    }
+
+    let total = files.len();
+    let page_size = page_size.unwrap_or(total.max(1));
+    let page = page.unwrap_or(1).max(1);
+
+    let mut transcripts = Vec::new();
+    for file in files.iter().skip((page - 1) * page_size).take(page_size) {
+        if let Some(summary) = transcript::cached_summary(&mut cache, file, &mut dirty) {
+            transcripts.push(serde_json::json!({
+                "file_name": file.file_name,
+                "word_count": summary.word_count,
+                "preview": summary.preview,
+                "modified": summary.modified,
+                "confidence": summary.confidence,
+                "language": summary.language,
+                "review_status": transcript::review_status(&state, &file.file_name),
+                "clip_notes": state.clip_notes.get(metadata::stem(&file.file_name))
+            }));
+        }
+    }
+
+    if dirty {
+        let names = transcript::list_transcripts();
+        cache.retain(|name, _| names.contains(name));
+        state::update(|state| state.transcript_cache = cache)?;
+    }
+
+    Ok(serde_json::json!({
+        "transcripts": transcripts,
+        "total": total,
+        "page": page,
+        "page_size": page_size
+    }))
+}
+
+// Command to get a specific transcript
+#[tauri::command]
+fn get_transcript(file_name: String) -> Result<serde_json::Value, String> {
+    let file_path = storage().path("output/transcripts").join(&file_name);
+    
+    if !storage().exists(&file_path) {
+        return Err(i18n::message("error-not_found", &[("file", &file_name)]));
+    }
+
+    let content = storage().read_to_string(&file_path)
+        .map_err(|e| i18n::message("error-io-read", &[("error", &e.to_string())]))?;
+    // Snapshots are taken on save, so reading writes nothing
+    let revision = transcript::revision(&content);
+
+    Ok(serde_json::json!({
+        "file_name": file_name,
+        "content": content,
+        "revision": revision,
+        "draft": drafts::load(&file_name).filter(|d| d.content != content)
+    }))
+}
+
+// Command to update a transcript, rejecting the write if it changed since `revision`;
+// the revision comes from get_transcript and is required
+#[tauri::command]
+fn update_transcript(
+    file_name: String,
+    content: String,
+    revision: Option<String>,
+) -> Result<String, SaveError> {
+    save_transcript(file_name, content, revision, review_server::HOST_USER)
+}
+
+// Save a transcript on behalf of `user`, recording who made the edit
+fn save_transcript(
+    file_name: String,
+    content: String,
+    revision: Option<String>,
+    user: &str,
+) -> Result<String, SaveError> {
+    workspace::ensure_writable().map_err(|message| SaveError::Io { message })?;
+    let file_path = storage().path("output/transcripts").join(&file_name);
+    
+    if !storage().exists(&file_path) {
+        return Err(SaveError::NotFound { message: i18n::message("error-not_found", &[("file", &file_name)]) });
+    }
+
+    // Without a base revision a save could silently overwrite someone else's edit
+    let Some(base_revision) = revision.filter(|r| !r.is_empty()) else {
+        return Err(SaveError::RevisionRequired { message: i18n::message("error-revision-required", &[("file", &file_name)]) });
+    };
+    let current = storage().read_to_string(&file_path)
+        .map_err(|e| SaveError::Io { message: i18n::message("error-io-read", &[("error", &e.to_string())]) })?;
+    let current_revision = transcript::revision(&current);
+
+    if current_revision != base_revision {
+        return Err(SaveError::Conflict {
+            message: i18n::message("error-conflict", &[("file", &file_name)]),
+            merge: Box::new(transcript::MergePayload {
+                base: transcript::load_snapshot(&file_name, &base_revision),
+                ours: content,
+                theirs: current,
+                base_revision,
+                current_revision,
+            }),
+        });
+    }
+    // The replaced content is the merge base of any other save still based on it
+    transcript::snapshot(&file_name, &current);
+
+    storage().write(&file_path, &content)
+        .map_err(|e| SaveError::Io { message: i18n::message("error-io-write", &[("error", &e.to_string())]) })?;
+    drafts::discard(&file_name);
+    integrity::record(&file_path);
+
+    let revision = transcript::snapshot(&file_name, &content);
+    review_server::attribute(&file_name, user, "edit", Some(&revision));
+    audit::record("transcript.edit", &file_name, serde_json::json!({ "revision": revision }));
+    Ok(revision)
+}
+
+// Command to get the JSON data one page at a time, as { entries, offset, limit, total }
+#[tauri::command]
+fn get_json(
+    dataset: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    dataset::get_json_slice(offset.unwrap_or(0), limit.unwrap_or(dataset::PAGE_SIZE), dataset)
+}
+
+// Command to reset all data
+#[tauri::command]
+fn reset_data() -> Result<(), String> {
+    let transcripts_dir = storage().path("output/transcripts");
+    let json_dir = storage().path("output/json");
+
+    // Files are moved into the operation journal so the reset can be undone
+    let mut journal = journal::begin("reset", "Reset all data")?;
+
+    // Clear clips directory and its clip folders
+    for clips_dir in clips::media_dirs() {
+        let Ok(entries) = storage().read_dir(&clips_dir) else { continue };
+        for entry in entries.filter_map(Result::ok) {
+            if storage().is_file(entry.path()) {
+                journal.remove(&entry.path()).ok();
+            }
+        }
+    }
+
+    // Clear transcripts directory
+    if let Ok(entries) = storage().read_dir(&transcripts_dir) {
+        for entry in entries.filter_map(Result::ok) {
+            if storage().is_file(entry.path()) {
+                journal.remove(&entry.path()).ok();
+            }
+        }
+    }
+
+    // Clear JSON directory
+    if let Ok(entries) = storage().read_dir(&json_dir) {
+        for entry in entries.filter_map(Result::ok) {
+            if storage().is_file(entry.path()) {
+                journal.remove(&entry.path()).ok();
+            }
+        }
+    }
+
+    journal.commit()
+}
+
+// Start the app; main.rs only calls this so the integration tests can link the modules
+pub fn run() {
+    crash::install();
+    workspace::acquire();
+
+    tauri::Builder::default()
+        .invoke_handler(tauri::generate_handler![
+            download_videos,
+            transcribe_audio,
+            generate_json,
+            get_status,
+            get_transcripts,
+            get_transcript,
+            update_transcript,
+            get_json,
+            reset_data,
+            dataset::export_dataset_subset,
+            dataset::export_attribution_report,
+            tags::set_tags,
+            tags::get_by_tag,
+            citations::extract_citations,
+            speakers::register_speaker,
+            speakers::get_speakers,
+            speakers::assign_speaker,
+            transliterate::transliterate_transcript,
+            report::get_corpus_report,
+            transcript::set_review_status,
+            export::export_transcripts,
+            import::import_transcripts,
+            annotations::get_annotations,
+            annotations::add_annotation,
+            annotations::update_annotation,
+            annotations::delete_annotation,
+            drafts::save_transcript_draft,
+            drafts::get_transcript_drafts,
+            drafts::discard_transcript_draft,
+            settings::get_settings,
+            settings::update_settings,
+            editor::open_in_editor,
+            spelling::get_suggestions,
+            spelling::apply_suggestions,
+            sanitize::check_text_issues,
+            sanitize::fix_text_issues,
+            align::align_transcript,
+            clips::get_clips,
+            clips::set_clip_notes,
+            batch::batch_action,
+            journal::get_operation_journal,
+            journal::undo_last_operation,
+            dataset::delete_dataset_entries,
+            integrity::update_manifest,
+            integrity::verify_integrity,
+            dataset::get_datasets,
+            dataset::get_json_slice,
+            dataset::get_json_entry,
+            updater::check_for_update,
+            updater::get_release_notes,
+            updater::install_update,
+            telemetry::get_telemetry,
+            telemetry::submit_telemetry,
+            telemetry::clear_telemetry,
+            crash::export_crash_report,
+            i18n::get_locales,
+            i18n::get_message_catalog,
+            portable::get_portable_info,
+            workspace::get_workspace_lock,
+            whisper::warm_up_transcription,
+            whisper::release_transcription_backend,
+            scheduler::get_scheduler_status,
+            jobs::get_job,
+            jobs::get_jobs,
+            history::get_download_history,
+            sources::check_sources,
+            screening::run_screening,
+            screening::get_screening_queue,
+            screening::review_screening,
+            duplicates::check_duplicate_links,
+            naming::preview_naming_template,
+            organize::organize_outputs,
+            video::extract_frames,
+            quality::score_dataset,
+            quality::explain_quality,
+            persona::get_personas,
+            dataset::update_dataset_entry,
+            manifest::get_export_history,
+            autorebuild::get_auto_rebuild_status,
+            translation::get_transcript_pair,
+            translation::translate_transcript,
+            glossary::check_glossary_compliance,
+            sections::segment_topics,
+            sections::get_sections,
+            questions::extract_questions,
+            subtitles::export_subtitles,
+            render::render_subtitled_clip,
+            excerpt::export_excerpt,
+            review_server::start_review_server,
+            review_server::stop_review_server,
+            review_server::get_review_server_status,
+            review_server::get_edit_attribution,
+            audit::get_audit_log,
+            notify::test_notifications,
+            release::prepare_release,
+            migrate::migrate_existing_outputs,
+            runs::get_transcription_runs,
+            runs::compare_transcriptions,
+            wer::compute_wer,
+            groundtruth::set_ground_truth,
+            groundtruth::get_ground_truth_report,
+            vram::get_gpu_plan,
+            live::start_live_transcription,
+            live::pause_live_transcription,
+            live::resume_live_transcription,
+            live::stop_live_transcription,
+            live::get_live_transcription,
+            partial::discard_partial_downloads,
+            ytdlp::update_ytdlp,
+            jobs::get_job_history,
+            jobs::export_job_history,
+            jobs::import_job_history,
+            api_tokens::create_api_token,
+            api_tokens::revoke_api_token,
+            api_tokens::list_api_tokens,
+            sharing::start_sharing_server,
+            sharing::stop_sharing_server,
+            sharing::get_sharing_server_status,
+            site::export_site,
+            epub::export_epub,
+            anki::export_anki,
+            flagging::run_segment_flagging,
+            flagging::get_flag_queue,
+            flagging::review_segment_flag,
+            contamination::check_contamination,
+            optouts::add_optout,
+            optouts::remove_optout,
+            optouts::get_optouts,
+            optouts::apply_optouts,
+            licenses::get_clip_licenses,
+            permissions::set_channel_permission,
+            permissions::remove_channel_permission,
+            permissions::get_channel_permissions,
+            watchdog::kill_job,
+            watchdog::continue_job,
+            joblogs::get_job_logs,
+            janitor::run_cleanup,
+            compress::archive_clips,
+            compress::restore_clip,
+            coldstorage::move_to_cold_storage,
+            coldstorage::restore_item,
+            coldstorage::get_cold_items,
+            grpc::start_grpc_server,
+            grpc::stop_grpc_server,
+            speakers::get_speaker_turns
+        ])
+        .setup(|app| {
+            migrate::run_if_needed(app.handle());
+            autorebuild::start(app.handle());
+            watchdog::start(app.handle());
+            janitor::start(app.handle());
+            Ok(())
+        })
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                workspace::release();
+            }
+        });
+}
\ No newline at end of file
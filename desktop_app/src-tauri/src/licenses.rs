@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::clips;
+use crate::metadata::{self, ClipMetadata};
+
+// Redistribution class of a clip's license
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseKind {
+    CreativeCommons,
+    Standard,
+    #[default]
+    Unknown,
+}
+
+// Classify the license yt-dlp reported; YouTube only reports Creative Commons licenses,
+// so a YouTube video without one is under the standard license
+pub fn classify(meta: &ClipMetadata) -> LicenseKind {
+    match meta.license.as_deref().map(|l| l.trim().to_lowercase()) {
+        Some(license) if license.contains("creative commons") || license.starts_with("cc") => LicenseKind::CreativeCommons,
+        Some(license) if !license.is_empty() => LicenseKind::Standard,
+        _ => match meta.webpage_url.as_deref() {
+            Some(url) if url.contains("youtube.com/") || url.contains("youtu.be/") => LicenseKind::Standard,
+            _ => LicenseKind::Unknown,
+        },
+    }
+}
+
+// Command to list the detected license of every downloaded clip
+#[tauri::command]
+pub fn get_clip_licenses() -> Vec<serde_json::Value> {
+    clips::list_clips()
+        .into_iter()
+        .map(|clip| {
+            let meta = metadata::load(&clip);
+            let kind = meta.as_ref().map_or(LicenseKind::Unknown, classify);
+            let license = meta.and_then(|m| m.license);
+            serde_json::json!({ "clip": clip, "license": license, "kind": kind })
+        })
+        .collect()
+}
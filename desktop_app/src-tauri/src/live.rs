@@ -0,0 +1,377 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::audit;
+use crate::clips;
+use crate::dataset;
+use crate::events::{self, LogLevel, PipelineEvent, Stage, StageStatus};
+use crate::jobs;
+use crate::pipeline::{self, Invocation};
+use crate::settings;
+use crate::state;
+use crate::transcript;
+use crate::vram::{self, GpuPlan};
+use crate::workspace;
+use crate::storage::storage;
+
+// Seconds of audio per chunk sent to whisper
+const DEFAULT_CHUNK_SECONDS: u64 = 10;
+
+// How often a paused recorder checks whether to resume
+const POLL: Duration = Duration::from_millis(500);
+
+// The running capture, if any; only one microphone session at a time
+static SESSION: Mutex<Option<LiveSession>> = Mutex::new(None);
+
+struct LiveSession {
+    name: String,
+    started_at: u64,
+    stop: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+}
+
+// Details of an in-person recording, saved in the clip's info JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingDetails {
+    pub location: Option<String>,
+    pub speaker: Option<String>,
+    pub description: Option<String>,
+    // Re-transcribe the saved clip with the full model once recording stops
+    pub transcribe_after: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LiveStatus {
+    pub recording: bool,
+    pub paused: bool,
+    pub name: Option<String>,
+    pub started_at: Option<u64>,
+}
+
+// ffmpeg input for the platform's default audio capture API
+fn capture_input(device: Option<&str>) -> Result<Vec<String>, String> {
+    let input = if cfg!(target_os = "linux") {
+        vec!["-f".to_string(), "pulse".to_string(), "-i".to_string(), device.unwrap_or("default").to_string()]
+    } else if cfg!(target_os = "macos") {
+        vec!["-f".to_string(), "avfoundation".to_string(), "-i".to_string(), format!(":{}", device.unwrap_or("0"))]
+    } else {
+        let device = device.ok_or("Choose a microphone device name to record on Windows")?;
+        vec!["-f".to_string(), "dshow".to_string(), "-i".to_string(), format!("audio={}", device)]
+    };
+    Ok(input)
+}
+
+fn chunk_path(dir: &Path, index: usize) -> PathBuf {
+    dir.join(format!("chunk_{:05}.wav", index))
+}
+
+// Relative to the pipeline directory, as the transcription backends expect
+fn relative(path: &Path) -> String {
+    path.strip_prefix(storage().root()).unwrap_or(path).to_string_lossy().to_string()
+}
+
+// "YYYYMMDD" of a unix timestamp, the format yt-dlp uses for upload_date
+pub fn date_stamp(secs: u64) -> String {
+    // Civil-from-days conversion for the proleptic Gregorian calendar
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+// Microphone capture writing numbered chunks
+#[derive(Clone)]
+struct Capture {
+    input: Vec<String>,
+    dir: PathBuf,
+    chunk_seconds: u64,
+}
+
+impl Capture {
+    // Record one chunk through the tool runner; returns once ffmpeg has written all of it
+    fn record(&self, index: usize) -> Result<(), String> {
+        let output = Invocation::new("ffmpeg")
+            .args(["-y", "-hide_banner", "-loglevel", "error"])
+            .args(&self.input)
+            .args(["-t", &self.chunk_seconds.to_string(), "-ac", "1", "-ar", "16000"])
+            .arg(chunk_path(&self.dir, index))
+            .in_workspace()
+            .run()
+            .map_err(|e| format!("Failed to start recording: {}", e))?;
+        if !output.success {
+            return Err(format!("Recording failed: {}", output.stderr.trim()));
+        }
+        Ok(())
+    }
+
+    // Record chunks one after another and hand each to the follower, waiting while paused;
+    // pausing and stopping take effect once the chunk in progress is complete
+    fn run(self, stop: Arc<AtomicBool>, paused: Arc<AtomicBool>, chunks: Sender<Result<usize, String>>) {
+        let mut index = 0;
+        while !stop.load(Ordering::SeqCst) {
+            if paused.load(Ordering::SeqCst) {
+                std::thread::sleep(POLL);
+                continue;
+            }
+            let recorded = self.record(index);
+            let failed = recorded.is_err();
+            if chunks.send(recorded.map(|_| index)).is_err() || failed {
+                break;
+            }
+            index += 1;
+        }
+    }
+}
+
+// Transcribe one finished chunk and return its text
+fn transcribe_chunk(dir: &Path, index: usize, plan: &GpuPlan) -> Result<String, String> {
+    let chunk = chunk_path(dir, index);
+    let text_dir = dir.join("text");
+    let output = pipeline::transcribe_file(&relative(&chunk), plan, &relative(&text_dir))?;
+    if !output.success {
+        return Err(output.stderr);
+    }
+    storage().read_to_string(text_dir.join(format!("chunk_{:05}.txt", index)))
+        .map(|text| text.trim().to_string())
+        .map_err(|e| format!("Failed to read chunk transcript: {}", e))
+}
+
+// Join the chunks into a clip with its info JSON, and the partial texts into a transcript
+fn finalize(name: &str, dir: &Path, chunks: usize, text: &str, details: &RecordingDetails, started_at: u64, seconds: f64) -> Result<String, String> {
+    if chunks == 0 {
+        return Err("Nothing was recorded".to_string());
+    }
+    // Entries are resolved relative to the list file, so the session name never needs quoting
+    let list: String = (0..chunks).map(|i| format!("file 'chunk_{:05}.wav'\n", i)).collect();
+    let list_path = dir.join("chunks.txt");
+    storage().write(&list_path, list).map_err(|e| format!("Failed to write chunk list: {}", e))?;
+
+    let clip = format!("{}.mp3", name);
+    let clip_path = storage().path("output/clips").join(&clip);
+    let output = Invocation::new("ffmpeg")
+        .args(["-y", "-f", "concat", "-safe", "0", "-i"])
+        .arg(&list_path)
+        .args(["-codec:a", "libmp3lame", "-q:a", "4"])
+        .arg(&clip_path)
+        .run()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    if !output.success {
+        return Err(format!("Failed to join recording: {}", output.stderr.trim()));
+    }
+
+    // Shaped like yt-dlp's info JSON so naming, provenance and speakers work unchanged
+    let info = serde_json::json!({
+        "id": format!("live-{}", started_at),
+        "title": name,
+        "description": details.description,
+        "uploader": details.speaker,
+        "channel": details.speaker,
+        "location": details.location,
+        "upload_date": date_stamp(started_at),
+        "duration": seconds,
+        "extractor": "microphone"
+    });
+    storage().write(storage().path("output/clips").join(format!("{}.info.json", name)), info.to_string())
+        .map_err(|e| format!("Failed to write clip metadata: {}", e))?;
+
+    let file_name = format!("{}.txt", name);
+    storage().write(transcript::transcript_path(&file_name), format!("{}\n", text.trim()))
+        .map_err(|e| format!("Failed to write transcript: {}", e))?;
+
+    if let Some(speaker) = &details.speaker {
+        state::update(|s| {
+            if let Some(profile) = s.speakers.iter().find(|p| p.name.eq_ignore_ascii_case(speaker)) {
+                let id = profile.id.clone();
+                s.transcript_speakers.insert(file_name.clone(), id);
+            }
+        })?;
+    }
+    audit::record("transcript.live", &file_name, serde_json::json!({ "chunks": chunks, "details": details }));
+    Ok(clip)
+}
+
+// Replace the live transcript with a full-quality transcription of the whole clip
+fn transcribe_full(app: &AppHandle, clip: &str, seconds: f64) {
+    let job = jobs::start(Stage::Transcribe, vec![clip.to_string()], vec![Some(seconds)]);
+    jobs::item_started(&job, clip);
+    events::job_progress(app, &job);
+
+    let started = Instant::now();
+    let output = pipeline::transcribe_clip(clip);
+    jobs::item_finished(&job, started.elapsed().as_secs_f64());
+    let success = matches!(&output, Ok(o) if o.success);
+    jobs::finish(&job, success);
+    events::job_progress(app, &job);
+
+    match output {
+        Ok(o) if o.success => events::log(app, Stage::Transcribe, LogLevel::Info, &format!("Transcribed {} at full quality", clip)),
+        Ok(o) => events::error(app, Stage::Transcribe, &o.stderr, Some(clip)),
+        Err(e) => events::error(app, Stage::Transcribe, &e, Some(clip)),
+    }
+}
+
+struct Follower {
+    app: AppHandle,
+    name: String,
+    started_at: u64,
+    capture: Capture,
+    plan: GpuPlan,
+    details: RecordingDetails,
+}
+
+impl Follower {
+    // Feed chunks to whisper as the recorder finishes them, until it stops and all are transcribed
+    fn run(self, chunks: Receiver<Result<usize, String>>) {
+        let dir = self.capture.dir.clone();
+        let mut next = 0;
+        let mut text = String::new();
+
+        for chunk in chunks {
+            let index = match chunk {
+                Ok(index) => index,
+                Err(e) => {
+                    // e.g. the microphone was unplugged
+                    let message = format!("Recording stopped unexpectedly: {}", e);
+                    events::log(&self.app, Stage::Transcribe, LogLevel::Warn, &message);
+                    break;
+                }
+            };
+            match transcribe_chunk(&dir, index, &self.plan) {
+                Ok(chunk_text) => {
+                    text.push_str(&chunk_text);
+                    text.push(' ');
+                    let start = (index as u64 * self.capture.chunk_seconds) as f64;
+                    events::emit(
+                        &self.app,
+                        PipelineEvent::LiveTranscript { session: self.name.clone(), chunk: index, start, text: chunk_text },
+                    );
+                }
+                Err(e) => events::error(&self.app, Stage::Transcribe, &e, Some(&self.name)),
+            }
+            next = index + 1;
+        }
+
+        *SESSION.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        let seconds = (next as u64 * self.capture.chunk_seconds) as f64;
+        match finalize(&self.name, &dir, next, &text, &self.details, self.started_at, seconds) {
+            Ok(clip) => {
+                let message = format!("Saved live recording as {} with its transcript", clip);
+                events::log(&self.app, Stage::Transcribe, LogLevel::Info, &message);
+                if self.details.transcribe_after.unwrap_or(true) {
+                    transcribe_full(&self.app, &clip, seconds);
+                }
+                events::stage_change(&self.app, Stage::Transcribe, StageStatus::Completed);
+            }
+            Err(e) => {
+                events::error(&self.app, Stage::Transcribe, &e, Some(&self.name));
+                events::stage_change(&self.app, Stage::Transcribe, StageStatus::Failed);
+            }
+        }
+    }
+}
+
+// Command to record a session from the microphone, emitting partial transcripts while recording;
+// when stopped the audio is saved to output/clips with its details
+#[tauri::command]
+pub fn start_live_transcription(
+    app: AppHandle,
+    name: Option<String>,
+    device: Option<String>,
+    chunk_seconds: Option<u64>,
+    details: Option<RecordingDetails>,
+) -> Result<String, String> {
+    workspace::ensure_writable()?;
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(running) = session.as_ref() {
+        return Err(format!("Already recording {}", running.name));
+    }
+
+    let started_at = state::now();
+    let name = name.unwrap_or_else(|| format!("Live recording {}", started_at));
+    dataset::validate_name(&name)?;
+    if clips::list_clips().contains(&format!("{}.mp3", name)) {
+        return Err(format!("A clip named {} already exists", name));
+    }
+    let plan = vram::prepare(&settings::load().whisper_model)?;
+    if let (Some(note), Some(_)) = (&plan.note, &plan.replaced) {
+        events::log(&app, Stage::Transcribe, LogLevel::Warn, note);
+    }
+
+    let dir = storage().path("output/live").join(&name);
+    storage().create_dir_all(dir.join("text")).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let capture = Capture {
+        input: capture_input(device.as_deref())?,
+        dir,
+        chunk_seconds: chunk_seconds.unwrap_or(DEFAULT_CHUNK_SECONDS).max(2),
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    let paused = Arc::new(AtomicBool::new(false));
+    *session = Some(LiveSession { name: name.clone(), started_at, stop: stop.clone(), paused: paused.clone() });
+    events::stage_change(&app, Stage::Transcribe, StageStatus::Started);
+
+    let (chunks, received) = mpsc::channel();
+    let recorder = capture.clone();
+    std::thread::spawn(move || recorder.run(stop, paused, chunks));
+
+    let follower = Follower {
+        app,
+        name: name.clone(),
+        started_at,
+        capture,
+        plan,
+        details: details.unwrap_or_default(),
+    };
+    std::thread::spawn(move || follower.run(received));
+    Ok(name)
+}
+
+fn with_session(f: impl FnOnce(&LiveSession)) -> Result<(), String> {
+    let session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    let running = session.as_ref().ok_or("Not recording")?;
+    f(running);
+    Ok(())
+}
+
+// Command to pause recording once the chunk in progress is complete; audio is not captured until resumed
+#[tauri::command]
+pub fn pause_live_transcription() -> Result<(), String> {
+    with_session(|s| s.paused.store(true, Ordering::SeqCst))
+}
+
+// Command to continue a paused recording in the same session
+#[tauri::command]
+pub fn resume_live_transcription() -> Result<(), String> {
+    with_session(|s| s.paused.store(false, Ordering::SeqCst))
+}
+
+// Command to stop recording after the chunk in progress; the last chunks are transcribed and the
+// clip saved in the background
+#[tauri::command]
+pub fn stop_live_transcription() -> Result<(), String> {
+    with_session(|s| s.stop.store(true, Ordering::SeqCst))
+}
+
+// Command to show whether a live recording is running
+#[tauri::command]
+pub fn get_live_transcription() -> LiveStatus {
+    let session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    LiveStatus {
+        recording: session.is_some(),
+        paused: session.as_ref().is_some_and(|s| s.paused.load(Ordering::SeqCst)),
+        name: session.as_ref().map(|s| s.name.clone()),
+        started_at: session.as_ref().map(|s| s.started_at),
+    }
+}
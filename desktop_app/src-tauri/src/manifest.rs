@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::boilerplate::BoilerplateSettings;
+use crate::dataset::{self, DatasetConfig};
+use crate::integrity;
+use crate::numerals::NormalizationSettings;
+use crate::persona::{self, Persona};
+use crate::pipeline::Invocation;
+use crate::settings;
+use crate::state;
+use crate::storage::storage;
+use crate::transcript;
+use crate::whisper::DecodingSettings;
+
+// Tools whose versions shape a dataset build, with the argument that prints their version
+const TOOLS: &[(&str, &str)] = &[
+    ("python3", "--version"),
+    ("yt-dlp", "--version"),
+    ("ffmpeg", "-version"),
+    ("faster-whisper", "--version"),
+];
+
+// Everything needed to reproduce or audit one dataset export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifest {
+    // "generate" for dataset builds, "subset" for filtered exports
+    pub kind: String,
+    pub dataset: String,
+    pub path: String,
+    pub created_at: u64,
+    pub entry_count: usize,
+    // SHA-256 of the exported data file
+    pub sha256: String,
+    // SHA-256 over the names and contents of all transcripts at export time
+    pub corpus_snapshot: String,
+    pub app_version: String,
+    pub tool_versions: BTreeMap<String, String>,
+    pub settings: BuildSettings,
+    // Filters, persona and export options the caller used
+    pub parameters: serde_json::Value,
+}
+
+// The settings that shape a build. Listed explicitly so credentials such as notification
+// passwords or review tokens never end up in a manifest or a published release.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildSettings {
+    pub whisper_model: String,
+    // Configuration of the exported dataset, including its filter
+    pub dataset: Option<DatasetConfig>,
+    // The dataset's default persona
+    pub persona: Option<Persona>,
+    pub decoding: DecodingSettings,
+    pub boilerplate: BoilerplateSettings,
+    pub normalization: NormalizationSettings,
+}
+
+impl BuildSettings {
+    fn current(dataset_id: &str) -> Self {
+        let workspace = settings::load();
+        let dataset = dataset::dataset_config(Some(dataset_id)).ok();
+        let persona = dataset.as_ref().and_then(|d| persona::persona(d.persona.as_deref()).ok());
+        BuildSettings {
+            whisper_model: workspace.whisper_model,
+            dataset,
+            persona,
+            decoding: workspace.decoding,
+            boilerplate: workspace.boilerplate,
+            normalization: workspace.normalization,
+        }
+    }
+}
+
+// An export together with where its manifest was written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub manifest_path: String,
+    pub manifest: ExportManifest,
+}
+
+// First line of each tool's version output, probed once per run
+fn tool_versions() -> BTreeMap<String, String> {
+    static VERSIONS: OnceLock<BTreeMap<String, String>> = OnceLock::new();
+    VERSIONS
+        .get_or_init(|| {
+            TOOLS
+                .iter()
+                .map(|(tool, flag)| {
+                    let version = Invocation::new(tool)
+                        .arg(flag)
+                        .run()
+                        .ok()
+                        .filter(|o| o.success)
+                        .and_then(|o| {
+                            let text = if o.stdout.trim().is_empty() { o.stderr } else { o.stdout };
+                            text.lines().next().map(|l| l.trim().to_string())
+                        })
+                        .unwrap_or_else(|| "unavailable".to_string());
+                    (tool.to_string(), version)
+                })
+                .collect()
+        })
+        .clone()
+}
+
+// Hash of the transcript corpus the export was built from
+fn corpus_snapshot() -> String {
+    let mut hasher = Sha256::new();
+    for name in transcript::list_transcripts() {
+        let checksum = integrity::sha256_file(&transcript::transcript_path(&name)).unwrap_or_default();
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(checksum.as_bytes());
+        hasher.update([b'\n']);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+// Path as recorded in manifests: relative to the pipeline directory when inside it
+fn relative(path: &Path) -> String {
+    path.strip_prefix(storage().root())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+// Manifest written next to a data file: "subset.json" -> "subset.manifest.json"
+pub fn manifest_path(data: &Path) -> PathBuf {
+    data.with_extension("manifest.json")
+}
+
+// Write the manifest of an export and add it to the export history
+pub fn record(
+    kind: &str,
+    dataset: &str,
+    data: &Path,
+    entry_count: usize,
+    parameters: serde_json::Value,
+) -> Result<ExportManifest, String> {
+    let manifest = ExportManifest {
+        kind: kind.to_string(),
+        dataset: dataset.to_string(),
+        path: relative(data),
+        created_at: state::now(),
+        entry_count,
+        sha256: integrity::sha256_file(data)?,
+        corpus_snapshot: corpus_snapshot(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        tool_versions: tool_versions(),
+        settings: BuildSettings::current(dataset),
+        parameters,
+    };
+
+    let path = manifest_path(data);
+    let content = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    storage().write(&path, content).map_err(|e| format!("Failed to write manifest: {}", e))?;
+
+    let record = ExportRecord {
+        manifest_path: relative(&path),
+        manifest: manifest.clone(),
+    };
+    state::update(|s| s.export_history.push(record))?;
+    Ok(manifest)
+}
+
+// Command to list past exports, newest first, optionally for a single dataset
+#[tauri::command]
+pub fn get_export_history(dataset: Option<String>) -> Result<Vec<ExportRecord>, String> {
+    Ok(state::load()?
+        .export_history
+        .into_iter()
+        .rev()
+        .filter(|r| dataset.as_ref().is_none_or(|d| &r.manifest.dataset == d))
+        .collect())
+}
@@ -0,0 +1,167 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::dataset;
+use crate::http::Response;
+use crate::release;
+use crate::review_server::Role;
+use crate::text;
+use crate::transcript;
+use crate::storage::storage;
+
+// Newest protocol revision this server speaks
+const PROTOCOL_VERSION: &str = "2025-03-26";
+
+// Matches returned by search_transcripts unless the caller asks for fewer
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn result(id: Value, result: Value) -> Response {
+    Response::json(200, &json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Response {
+    Response::json(200, &json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }))
+}
+
+// Tools offered to agents, with the least role allowed to call each
+fn tools() -> Vec<(Role, Value)> {
+    vec![
+        (Role::Viewer, json!({
+            "name": "search_transcripts",
+            "description": "Case-insensitive text search over all transcripts; returns file names with matching snippets.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1 }
+                },
+                "required": ["query"]
+            }
+        })),
+        (Role::Viewer, json!({
+            "name": "get_transcript",
+            "description": "Full text of one transcript by file name, as returned by search_transcripts.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "file_name": { "type": "string" } },
+                "required": ["file_name"]
+            }
+        })),
+        (Role::Viewer, json!({
+            "name": "get_dataset_stats",
+            "description": "Entry, source, channel, language and quality counts for a dataset (the default dataset when omitted).",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "dataset": { "type": "string" } }
+            }
+        })),
+        (Role::Reviewer, json!({
+            "name": "queue_download",
+            "description": "Queue video or media links for download and transcription preparation; returns immediately.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "links": { "type": "array", "items": { "type": "string" } } },
+                "required": ["links"]
+            }
+        })),
+    ]
+}
+
+fn search(arguments: &Value) -> Result<Value, String> {
+    let query = arguments["query"].as_str().map(str::trim).filter(|q| !q.is_empty())
+        .ok_or("query is required")?
+        .to_lowercase();
+    let limit = arguments["limit"].as_u64().map_or(DEFAULT_SEARCH_LIMIT, |l| l as usize);
+
+    let matches: Vec<Value> = transcript::list_transcripts()
+        .into_iter()
+        .filter_map(|name| {
+            let content = storage().read_to_string(transcript::transcript_path(&name)).ok()?;
+            let found = text::snippets(&content, &query);
+            (!found.is_empty()).then(|| json!({ "file_name": name, "snippets": found }))
+        })
+        .take(limit)
+        .collect();
+    Ok(json!(matches))
+}
+
+fn call_tool(app: &AppHandle, role: Role, params: &Value) -> Result<Value, String> {
+    let name = params["name"].as_str().unwrap_or_default();
+    let arguments = &params["arguments"];
+    let (required, _) = tools()
+        .into_iter()
+        .find(|(_, tool)| tool["name"] == name)
+        .ok_or_else(|| format!("Unknown tool: {}", name))?;
+    if role < required {
+        return Err(format!("{} requires the {} role", name, required.as_str()));
+    }
+
+    match name {
+        "search_transcripts" => search(arguments),
+        "get_transcript" => {
+            let file_name = arguments["file_name"].as_str().ok_or("file_name is required")?;
+            dataset::validate_name(file_name)?;
+            crate::get_transcript(file_name.to_string()).map(|t| t["content"].clone())
+        }
+        "get_dataset_stats" => {
+            let path = dataset::dataset_path(arguments["dataset"].as_str())?;
+            let entries = dataset::load_entries(&path)?;
+            Ok(release::stats(&entries))
+        }
+        "queue_download" => {
+            let links: Vec<String> = serde_json::from_value(arguments["links"].clone())
+                .map_err(|_| "links must be a list of URLs".to_string())?;
+            if links.is_empty() || links.iter().any(|l| !(l.starts_with("https://") || l.starts_with("http://"))) {
+                return Err("links must be a non-empty list of http(s) URLs".to_string());
+            }
+            let count = links.len();
+            let app = app.clone();
+            std::thread::spawn(move || crate::download_videos(app, links, None));
+            Ok(json!({ "queued": count }))
+        }
+        _ => Err(format!("Unknown tool: {}", name)),
+    }
+}
+
+// Answer one JSON-RPC message of the Model Context Protocol (streamable HTTP, JSON responses)
+pub fn handle(app: &AppHandle, role: Role, body: &[u8]) -> Response {
+    let request: RpcRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return rpc_error(Value::Null, -32700, &format!("Parse error: {}", e)),
+    };
+    // Notifications get no reply
+    let Some(id) = request.id else {
+        return Response { status: 202, content_type: "application/json".to_string(), body: Vec::new() };
+    };
+
+    match request.method.as_str() {
+        "initialize" => result(id, json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "akhi-pipeline", "version": env!("CARGO_PKG_VERSION") }
+        })),
+        "ping" => result(id, json!({})),
+        "tools/list" => {
+            let listed: Vec<Value> = tools().into_iter().filter(|(r, _)| role >= *r).map(|(_, t)| t).collect();
+            result(id, json!({ "tools": listed }))
+        }
+        "tools/call" => {
+            // Tool failures are reported to the model, not as protocol errors
+            let (text, is_error) = match call_tool(app, role, &request.params) {
+                Ok(value) => (value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()), false),
+                Err(e) => (e, true),
+            };
+            result(id, json!({ "content": [{ "type": "text", "text": text }], "isError": is_error }))
+        }
+        method => rpc_error(id, -32601, &format!("Method not found: {}", method)),
+    }
+}
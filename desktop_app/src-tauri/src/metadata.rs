@@ -0,0 +1,52 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clips;
+use crate::storage::storage;
+
+// Subset of the yt-dlp info JSON written next to each downloaded clip
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipMetadata {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub webpage_url: Option<String>,
+    pub channel: Option<String>,
+    pub uploader: Option<String>,
+    pub upload_date: Option<String>,
+    pub duration: Option<f64>,
+    pub license: Option<String>,
+    pub playlist: Option<String>,
+    pub playlist_index: Option<u64>,
+    // Where an in-person recording was made
+    pub location: Option<String>,
+}
+
+impl ClipMetadata {
+    // Channel name, falling back to the uploader for sources without channels
+    pub fn channel_name(&self) -> Option<&str> {
+        self.channel.as_deref().or(self.uploader.as_deref())
+    }
+}
+
+// Strip the extension from a clip or transcript file name
+pub fn stem(file_name: &str) -> &str {
+    Path::new(file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(file_name)
+}
+
+// Path of the info JSON for the clip a transcript or clip file belongs to
+pub fn info_path(file_name: &str) -> PathBuf {
+    clips::media_file(stem(file_name), "info.json")
+}
+
+// Load the stored metadata for a clip, if yt-dlp wrote any
+pub fn load(file_name: &str) -> Option<ClipMetadata> {
+    let content = storage().read_to_string(info_path(file_name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::audit;
+use crate::clips;
+use crate::dataset;
+use crate::events::{self, LogLevel, Stage};
+use crate::history::DownloadRecord;
+use crate::integrity;
+use crate::manifest;
+use crate::metadata;
+use crate::settings;
+use crate::state;
+use crate::transcript;
+use crate::storage::storage;
+
+// How far an item got through the pipeline, inferred from the files it left behind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStage {
+    Downloaded,
+    Transcribed,
+    InDataset,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigratedItem {
+    pub stem: String,
+    pub stage: ItemStage,
+    pub clip: Option<String>,
+    pub transcript: Option<String>,
+    // Dataset ids whose entries come from this item
+    pub datasets: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct MigrationReport {
+    pub items: Vec<MigratedItem>,
+    pub history_added: usize,
+    pub datasets: Vec<String>,
+    pub checksums: usize,
+}
+
+fn modified(path: &std::path::Path) -> u64 {
+    storage()
+        .metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+// True for a workspace with outputs from the old scripts but no state store yet
+pub fn needed() -> bool {
+    !storage().exists(state::state_path())
+        && (!clips::list_clips().is_empty() || !transcript::list_transcripts().is_empty())
+}
+
+// Backfill on first run so older outputs show up with their history
+pub fn run_if_needed(app: AppHandle) {
+    if !needed() {
+        return;
+    }
+    std::thread::spawn(move || match migrate_existing_outputs() {
+        Ok(report) => {
+            let message = format!("Migrated {} existing items into the state store", report.items.len());
+            events::log(&app, Stage::Analysis, LogLevel::Info, &message);
+        }
+        Err(e) => events::error(&app, Stage::Analysis, &format!("Failed to migrate existing outputs: {}", e), None),
+    });
+}
+
+// Command to rebuild state-store entries from an output tree written by the old scripts
+#[tauri::command]
+pub fn migrate_existing_outputs() -> Result<MigrationReport, String> {
+    let mut report = MigrationReport::default();
+    let clips = clips::list_clips();
+    let transcripts = transcript::list_transcripts();
+
+    // Which items each existing dataset was built from
+    let mut in_datasets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for config in settings::load().datasets {
+        let path = dataset::dataset_path(Some(&config.id))?;
+        if !storage().exists(&path) {
+            continue;
+        }
+        let entries = dataset::load_entries(&path)?;
+        dataset::record_count(&path, entries.len());
+        let sources: BTreeSet<String> =
+            entries.iter().filter_map(|e| e.source.as_deref()).map(|s| metadata::stem(s).to_string()).collect();
+        for source in sources {
+            in_datasets.entry(source).or_default().push(config.id.clone());
+        }
+
+        let recorded = state::load()?.export_history.iter().any(|r| r.manifest.dataset == config.id);
+        if !recorded {
+            manifest::record("migrated", &config.id, &path, entries.len(), serde_json::json!({ "migrated": true }))?;
+        }
+        report.datasets.push(config.id);
+    }
+
+    let stems: BTreeSet<String> = clips.iter().chain(&transcripts).map(|f| metadata::stem(f).to_string()).collect();
+    for stem in stems {
+        let clip = clips.iter().find(|c| metadata::stem(c) == stem).cloned();
+        let transcript = transcripts.iter().find(|t| metadata::stem(t) == stem).cloned();
+        let datasets = in_datasets.remove(&stem).unwrap_or_default();
+        let stage = if !datasets.is_empty() {
+            ItemStage::InDataset
+        } else if transcript.is_some() {
+            ItemStage::Transcribed
+        } else {
+            ItemStage::Downloaded
+        };
+        report.items.push(MigratedItem { stem, stage, clip, transcript, datasets });
+    }
+
+    // Clips no download record knows about get one dated by the file itself
+    let history = state::load()?.download_history;
+    let known: BTreeSet<&String> = history.iter().flat_map(|r| &r.clips).collect();
+    let records: Vec<DownloadRecord> = clips
+        .iter()
+        .filter(|clip| !known.contains(clip))
+        .map(|clip| {
+            let meta = metadata::load(clip).unwrap_or_default();
+            let path = clips::media_file(metadata::stem(clip), "mp3");
+            DownloadRecord {
+                url: meta.webpage_url.clone().unwrap_or_else(|| format!("file:{}", clip)),
+                downloaded_at: modified(&path),
+                outcome: "success".to_string(),
+                clips: vec![clip.clone()],
+                title: meta.title,
+                size: storage().metadata(&path).map_or(0, |m| m.len()),
+                attempts: 0,
+                error: None,
+                ytdlp_version: None,
+                migrated: true,
+            }
+        })
+        .collect();
+    report.history_added = records.len();
+
+    state::update(|s| {
+        s.download_history.extend(records);
+        s.download_history.sort_by_key(|r| r.downloaded_at);
+        s.migrated_at = Some(state::now());
+    })?;
+
+    report.checksums = integrity::refresh()?.len();
+    audit::record("outputs.migrate", "output", serde_json::json!({ "items": report.items.len() }));
+    Ok(report)
+}
@@ -0,0 +1,174 @@
+use serde::Serialize;
+
+use crate::metadata::ClipMetadata;
+use crate::settings;
+
+// Template used when the workspace has none configured; matches the historical layout
+pub const DEFAULT_TEMPLATE: &str = "{title}";
+
+// Variables a naming template may use, with their yt-dlp field expressions
+const VARIABLES: &[(&str, &str)] = &[
+    ("title", "title"),
+    ("id", "id"),
+    ("channel", "channel,uploader|unknown"),
+    ("uploader", "uploader|unknown"),
+    ("upload_date", "upload_date|undated"),
+    ("playlist", "playlist|none"),
+    ("playlist_index", "playlist_index|0"),
+];
+
+// A "/" in a template puts clips into folders below output/clips, e.g. "{channel}/{title}".
+// Transcripts and other outputs stay keyed by the file name alone, so only the last
+// segment names the clip.
+
+// Parts of a parsed template
+enum Part<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while !rest.is_empty() {
+        match rest.find(['{', '}']) {
+            Some(i) if rest[i..].starts_with('}') => {
+                return Err(format!("Unmatched '}}' in naming template: {}", template));
+            }
+            Some(i) => {
+                if i > 0 {
+                    parts.push(Part::Text(&rest[..i]));
+                }
+                let end = rest[i..]
+                    .find('}')
+                    .ok_or_else(|| format!("Unclosed '{{' in naming template: {}", template))?;
+                let name = &rest[i + 1..i + end];
+                if !VARIABLES.iter().any(|(v, _)| *v == name) {
+                    return Err(format!("Unknown naming variable: {{{}}}", name));
+                }
+                parts.push(Part::Variable(name));
+                rest = &rest[i + end + 1..];
+            }
+            None => {
+                parts.push(Part::Text(rest));
+                rest = "";
+            }
+        }
+    }
+    Ok(parts)
+}
+
+// Check a template before it is saved or used
+pub fn validate(template: &str) -> Result<(), String> {
+    let parts = parse(template)?;
+
+    // Whether {title} or {id} appears after the last "/", i.e. in the file name
+    let mut distinct = false;
+    for part in &parts {
+        match part {
+            Part::Text(text) => {
+                if text.contains("..") || text.contains('%') || text.contains('\\') || text.contains("//") {
+                    return Err(format!("Invalid text in naming template: {}", text));
+                }
+                if text.contains('/') {
+                    distinct = false;
+                }
+            }
+            Part::Variable(name) => distinct |= matches!(*name, "title" | "id"),
+        }
+    }
+    if template.starts_with('/') || template.ends_with('/') {
+        return Err(format!("Invalid folder in naming template: {}", template));
+    }
+    if !distinct {
+        return Err("Naming template must include {title} or {id} after the last '/' so clips get distinct names".to_string());
+    }
+    Ok(())
+}
+
+// Configured template, falling back to the default when it is missing or invalid
+pub fn template() -> String {
+    let template = settings::load().naming_template;
+    if validate(&template).is_ok() {
+        template
+    } else {
+        DEFAULT_TEMPLATE.to_string()
+    }
+}
+
+// yt-dlp output template for downloaded clips and their info JSON, relative to the pipeline directory
+pub fn ytdlp_template() -> String {
+    let template = template();
+    let mut stem = String::new();
+    for part in parse(&template).unwrap_or_default() {
+        match part {
+            Part::Text(text) => stem.push_str(text),
+            Part::Variable(name) => {
+                let field = VARIABLES.iter().find(|(v, _)| *v == name).map_or(name, |(_, f)| f);
+                stem.push_str(&format!("%({})s", field));
+            }
+        }
+    }
+    format!("output/clips/{}.%(ext)s", stem)
+}
+
+// Value of a variable for already downloaded metadata, mirroring the yt-dlp fallbacks
+fn value(name: &str, meta: &ClipMetadata, stem: &str) -> String {
+    let value = match name {
+        "title" => meta.title.clone().unwrap_or_else(|| stem.to_string()),
+        "id" => meta.id.clone().unwrap_or_else(|| stem.to_string()),
+        "channel" => meta.channel_name().unwrap_or("unknown").to_string(),
+        "uploader" => meta.uploader.clone().unwrap_or_else(|| "unknown".to_string()),
+        "upload_date" => meta.upload_date.clone().unwrap_or_else(|| "undated".to_string()),
+        "playlist" => meta.playlist.clone().unwrap_or_else(|| "none".to_string()),
+        _ => meta.playlist_index.unwrap_or(0).to_string(),
+    };
+    // Same substitutions yt-dlp applies to field values in file names
+    value.replace(['/', '\\'], "_").replace(['\0', '\n'], " ")
+}
+
+// Name a template produces for a clip's stored metadata: the file stem, after any folders
+pub fn render(template: &str, meta: &ClipMetadata, stem: &str) -> Result<String, String> {
+    validate(template)?;
+    Ok(parse(template)?
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => text.to_string(),
+            Part::Variable(name) => value(name, meta, stem),
+        })
+        .collect())
+}
+
+// Split a rendered name into its folder below output/clips ("" for none) and its file stem
+pub fn split(rendered: &str) -> (&str, &str) {
+    rendered.rsplit_once('/').unwrap_or(("", rendered))
+}
+
+#[derive(Debug, Serialize)]
+pub struct NamingPreview {
+    pub template: String,
+    pub valid: bool,
+    pub error: Option<String>,
+    // Folder and file stem of the clip and its info JSON; transcripts use the stem alone
+    pub example: Option<String>,
+}
+
+// Command to validate a naming template and show the file stem it produces for sample metadata
+#[tauri::command]
+pub fn preview_naming_template(template: String) -> NamingPreview {
+    let sample = ClipMetadata {
+        title: Some("Tafsir of Surah Al-Fatiha".to_string()),
+        id: Some("dQw4w9WgXcQ".to_string()),
+        channel: Some("Example Channel".to_string()),
+        upload_date: Some("20240115".to_string()),
+        playlist: Some("Tafsir Series".to_string()),
+        playlist_index: Some(1),
+        ..Default::default()
+    };
+
+    match render(&template, &sample, "clip") {
+        Ok(example) => NamingPreview { template, valid: true, error: None, example: Some(example) },
+        Err(error) => NamingPreview { template, valid: false, error: Some(error), example: None },
+    }
+}
@@ -0,0 +1,250 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use tauri::AppHandle;
+
+use crate::events::{self, LogLevel, PipelineEvent, Stage, StageStatus};
+use crate::pipeline::Invocation;
+use crate::settings;
+use crate::state;
+use crate::storage::storage;
+
+// Low-disk warnings repeat at most this often
+const LOW_DISK_INTERVAL_SECS: u64 = 3600;
+
+// When the last low-disk warning went out, unix seconds
+static LAST_LOW_DISK: Mutex<u64> = Mutex::new(0);
+
+// Email through an SMTP server, sent with curl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailChannel {
+    // e.g. "smtps://smtp.example.com:465" or "smtp://localhost:25"
+    pub smtp_url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+// Messages from a Telegram bot to one chat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramChannel {
+    pub bot_token: String,
+    pub chat_id: String,
+}
+
+// Messages posted to a Discord channel webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordChannel {
+    pub webhook_url: String,
+}
+
+// Where and when notifications are sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub email: Option<EmailChannel>,
+    pub telegram: Option<TelegramChannel>,
+    pub discord: Option<DiscordChannel>,
+    // Notify when a download, transcription or dataset batch finishes
+    pub on_completion: bool,
+    // Notify when one of those batches fails
+    pub on_failure: bool,
+    // Warn when the workspace disk has less free space than this; None disables the check
+    pub low_disk_gb: Option<f64>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            email: None,
+            telegram: None,
+            discord: None,
+            on_completion: true,
+            on_failure: true,
+            low_disk_gb: Some(5.0),
+        }
+    }
+}
+
+impl NotificationSettings {
+    fn has_channels(&self) -> bool {
+        self.email.is_some() || self.telegram.is_some() || self.discord.is_some()
+    }
+}
+
+fn stage_label(stage: Stage) -> Option<&'static str> {
+    match stage {
+        Stage::Download => Some("Download"),
+        Stage::Transcribe => Some("Transcription"),
+        Stage::Dataset => Some("Dataset generation"),
+        // Analysis commands are interactive, not batches worth a notification
+        Stage::Analysis => None,
+    }
+}
+
+fn curl(args: &[&str], stdin: Option<String>) -> Result<(), String> {
+    let mut invocation = Invocation::new("curl").args(["-fsS", "-m", "30"]).args(args);
+    if let Some(input) = stdin {
+        invocation = invocation.stdin(input);
+    }
+    let output = invocation.run().map_err(|e| format!("Failed to execute curl: {}", e))?;
+    if !output.success {
+        return Err(output.stderr.trim().to_string());
+    }
+    Ok(())
+}
+
+fn send_email(channel: &EmailChannel, subject: &str, body: &str) -> Result<(), String> {
+    if channel.to.is_empty() {
+        return Err("No email recipients configured".to_string());
+    }
+    let message = format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+        channel.from,
+        channel.to.join(", "),
+        subject,
+        body
+    );
+
+    let credentials = match (&channel.username, &channel.password) {
+        (Some(user), Some(password)) => Some(format!("{}:{}", user, password)),
+        _ => None,
+    };
+    let mut args = vec![channel.smtp_url.as_str(), "--mail-from", channel.from.as_str()];
+    for recipient in &channel.to {
+        args.extend(["--mail-rcpt", recipient.as_str()]);
+    }
+    if let Some(credentials) = &credentials {
+        // Never send a password over an unencrypted connection
+        args.extend(["--ssl-reqd", "--user", credentials.as_str()]);
+    }
+    args.extend(["--upload-file", "-"]);
+    curl(&args, Some(message))
+}
+
+fn send_telegram(channel: &TelegramChannel, subject: &str, body: &str) -> Result<(), String> {
+    let url = format!("https://api.telegram.org/bot{}/sendMessage", channel.bot_token);
+    let payload = serde_json::json!({ "chat_id": channel.chat_id, "text": format!("{}\n\n{}", subject, body) });
+    curl(
+        &["-X", "POST", "-H", "Content-Type: application/json", "--data-binary", "@-", &url],
+        Some(payload.to_string()),
+    )
+}
+
+fn send_discord(channel: &DiscordChannel, subject: &str, body: &str) -> Result<(), String> {
+    let payload = serde_json::json!({ "content": format!("**{}**\n{}", subject, body) });
+    curl(
+        &["-X", "POST", "-H", "Content-Type: application/json", "--data-binary", "@-", &channel.webhook_url],
+        Some(payload.to_string()),
+    )
+}
+
+// Send to every configured channel, reporting each channel's outcome
+fn send(config: &NotificationSettings, subject: &str, body: &str) -> Vec<(String, Result<(), String>)> {
+    let mut results = Vec::new();
+    if let Some(email) = &config.email {
+        results.push(("email".to_string(), send_email(email, subject, body)));
+    }
+    if let Some(telegram) = &config.telegram {
+        results.push(("telegram".to_string(), send_telegram(telegram, subject, body)));
+    }
+    if let Some(discord) = &config.discord {
+        results.push(("discord".to_string(), send_discord(discord, subject, body)));
+    }
+    results
+}
+
+// Send in the background so slow mail servers never hold up the pipeline
+fn send_later(app: &AppHandle, config: NotificationSettings, subject: String, body: String) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for (channel, result) in send(&config, &subject, &body) {
+            if let Err(e) = result {
+                let message = format!("Failed to send {} notification: {}", channel, e);
+                events::log(&app, Stage::Analysis, LogLevel::Warn, &message);
+            }
+        }
+    });
+}
+
+// Free space on the disk holding the workspace, in gigabytes
+fn free_disk_gb() -> Option<f64> {
+    let root = storage().root().to_string_lossy().to_string();
+    let output = Invocation::new("df").args(["-Pk", root.as_str()]).run().ok()?;
+    if !output.success {
+        return None;
+    }
+    // POSIX format: Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let available_kb: f64 = output.stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available_kb / (1024.0 * 1024.0))
+}
+
+fn check_disk(app: &AppHandle, config: &NotificationSettings) {
+    let Some(threshold) = config.low_disk_gb else { return };
+
+    let mut last = LAST_LOW_DISK.lock().unwrap_or_else(|e| e.into_inner());
+    let now = state::now();
+    if now.saturating_sub(*last) < LOW_DISK_INTERVAL_SECS {
+        return;
+    }
+    if let Some(free) = free_disk_gb().filter(|free| *free < threshold) {
+        *last = now;
+        send_later(
+            app,
+            config.clone(),
+            "Low disk space".to_string(),
+            format!("Only {:.1} GB free on the disk holding {}", free, storage().root().display()),
+        );
+    }
+}
+
+// Turn pipeline events into notifications
+pub fn observe(app: &AppHandle, event: &PipelineEvent) {
+    let PipelineEvent::StageChange { stage, status } = event else { return };
+    let Some(label) = stage_label(*stage) else { return };
+
+    let config = settings::load().notifications;
+    if !config.has_channels() {
+        return;
+    }
+
+    match status {
+        StageStatus::Completed if config.on_completion => {
+            send_later(app, config.clone(), format!("{} finished", label), format!("{} batch completed.", label));
+        }
+        StageStatus::Failed if config.on_failure => {
+            send_later(
+                app,
+                config.clone(),
+                format!("{} failed", label),
+                format!("{} batch failed; see the app log for details.", label),
+            );
+        }
+        _ => {}
+    }
+    check_disk(app, &config);
+}
+
+// Command to send a test message through every configured channel
+#[tauri::command]
+pub fn test_notifications() -> Result<serde_json::Value, String> {
+    let config = settings::load().notifications;
+    if !config.has_channels() {
+        return Err("No notification channels configured".to_string());
+    }
+
+    let results: serde_json::Map<String, serde_json::Value> =
+        send(&config, "Akhi Data Builder test", "Notifications are working.")
+            .into_iter()
+            .map(|(channel, result)| {
+                let value = match result {
+                    Ok(()) => serde_json::json!({ "sent": true }),
+                    Err(e) => serde_json::json!({ "sent": false, "error": e }),
+                };
+                (channel, value)
+            })
+            .collect();
+    Ok(serde_json::Value::Object(results))
+}
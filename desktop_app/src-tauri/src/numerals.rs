@@ -0,0 +1,312 @@
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+// Which way numbers are made consistent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberStyle {
+    // Spelled-out numbers from ten upwards become digits, e.g. "twenty-three" -> "23"
+    #[default]
+    Digits,
+    // Numbers up to one hundred become words, e.g. "23rd" -> "twenty-third"
+    Words,
+}
+
+// Number and date normalization applied to dataset entries, stored in settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizationSettings {
+    pub enabled: bool,
+    pub direction: NumberStyle,
+}
+
+// Spelled-out numbers below this stay words in the digits direction; "one of them" is not a count
+const DIGITS_FROM: u64 = 10;
+
+// Largest number spelled out in the words direction; years and hadith numbers stay digits
+const WORDS_UP_TO: u64 = 100;
+
+const UNITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve",
+    "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: &[&str] = &["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+const SCALES: &[(&str, u64)] = &[("hundred", 100), ("thousand", 1_000), ("million", 1_000_000)];
+const IRREGULAR_ORDINALS: &[(&str, &str)] = &[
+    ("one", "first"), ("two", "second"), ("three", "third"), ("five", "fifth"),
+    ("eight", "eighth"), ("nine", "ninth"), ("twelve", "twelfth"),
+];
+
+// Hijri month names with the transliterations transcripts use for them
+const HIJRI_MONTHS: &[(&str, &str)] = &[
+    ("Muharram", r"muharram"),
+    ("Safar", r"safar"),
+    ("Rabi al-Awwal", r"rabi[ʿ'`]?(?:[\s-]*(?:al|ul))?[\s-]+awwal"),
+    ("Rabi al-Thani", r"rabi[ʿ'`]?(?:[\s-]*(?:al|ul|us))?[\s-]+(?:thani|akhir)"),
+    ("Jumada al-Awwal", r"jumad[ai](?:[\s-]*(?:al|ul))?[\s-]+(?:awwal|ula)"),
+    ("Jumada al-Thani", r"jumad[ai](?:[\s-]*(?:al|ul|us))?[\s-]+(?:thani|akhirah?)"),
+    ("Rajab", r"rajab"),
+    ("Sha'ban", r"sha[ʿ'`]?ban"),
+    ("Ramadan", r"rama[dz]h?an"),
+    ("Shawwal", r"shaw?wal"),
+    ("Dhu al-Qadah", r"(?:dhu|dhul|zul|thul)(?:[\s-]+al)?[\s-]*q[ai][ʿ'`]?dah?"),
+    ("Dhu al-Hijjah", r"(?:dhu|dhul|zul|thul)(?:[\s-]+al)?[\s-]*hij?jah?"),
+];
+
+// Only recognized next to a day, since "may" and "march" are common words
+const GREGORIAN_MONTHS: &[&str] = &[
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+    "November", "December",
+];
+
+struct Patterns {
+    months: Vec<(&'static str, Regex)>,
+    number_words: Regex,
+    digits: Regex,
+    day_of_month: Regex,
+    month_day: Regex,
+    era: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let ordinals: Vec<String> = UNITS.iter().chain(&TENS[2..]).map(|w| ordinal_word(w)).collect();
+        let word = format!(
+            "(?:{}|{}|{}|{})",
+            UNITS.join("|"),
+            TENS[2..].join("|"),
+            SCALES.iter().map(|(s, _)| *s).collect::<Vec<_>>().join("|"),
+            ordinals.join("|")
+        );
+        let canonical = month_names().map(regex::escape).collect::<Vec<_>>().join("|");
+        let day = format!(r"(\d{{1,2}}(?:st|nd|rd|th)?|{}(?:[\s-]+{})?)", word, word);
+        Patterns {
+            months: HIJRI_MONTHS
+                .iter()
+                .map(|(name, spelling)| (*name, Regex::new(&format!(r"(?i)\b(?:{})\b", spelling)).expect("month pattern")))
+                .collect(),
+            number_words: Regex::new(&format!(r"(?i)\b{w}(?:(?:\s+and\s+|[\s-]+){w})*\b", w = word)).expect("number pattern"),
+            digits: Regex::new(r"\d+(?:st|nd|rd|th)?").expect("digit pattern"),
+            day_of_month: Regex::new(&format!(r"(?i)\b(?:the\s+)?{}\s+(?:day\s+)?of\s+({})\b", day, canonical)).expect("date pattern"),
+            month_day: Regex::new(&format!(r"(?i)\b({})\s+(?:the\s+)?(\d{{1,2}}(?:st|nd|rd|th))\b", canonical)).expect("date pattern"),
+            era: Regex::new(r"\b(?:A\.H\.|a\.h\.)|\b(?:A\.D\.|a\.d\.)").expect("era pattern"),
+        }
+    })
+}
+
+fn month_names() -> impl Iterator<Item = &'static str> {
+    HIJRI_MONTHS.iter().map(|(name, _)| *name).chain(GREGORIAN_MONTHS.iter().copied())
+}
+
+// Canonical spelling of a month name matched case-insensitively
+fn month_name(found: &str) -> &str {
+    month_names().find(|name| name.eq_ignore_ascii_case(found)).unwrap_or(found)
+}
+
+fn ordinal_word(cardinal: &str) -> String {
+    if let Some((_, ordinal)) = IRREGULAR_ORDINALS.iter().find(|(c, _)| *c == cardinal) {
+        return ordinal.to_string();
+    }
+    match cardinal.strip_suffix('y') {
+        Some(stem) => format!("{}ieth", stem),
+        None => format!("{}th", cardinal),
+    }
+}
+
+fn ordinal_suffix(n: u64) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+// English words for a number below a billion
+fn words(n: u64) -> String {
+    if n < 20 {
+        return UNITS[n as usize].to_string();
+    }
+    if n < 100 {
+        let tens = TENS[(n / 10) as usize];
+        return match n % 10 {
+            0 => tens.to_string(),
+            unit => format!("{}-{}", tens, UNITS[unit as usize]),
+        };
+    }
+    for (scale, value) in SCALES.iter().rev() {
+        if n >= *value {
+            let rest = n % value;
+            let head = format!("{} {}", words(n / value), scale);
+            return match rest {
+                0 => head,
+                r if r < 100 => format!("{} and {}", head, words(r)),
+                r => format!("{} {}", head, words(r)),
+            };
+        }
+    }
+    unreachable!("numbers of 100 and more are handled by a scale")
+}
+
+fn ordinal_words(n: u64) -> String {
+    let cardinal = words(n);
+    let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+    format!("{}{}", &cardinal[..split], ordinal_word(&cardinal[split..]))
+}
+
+fn word_value(word: &str) -> Option<(u64, bool)> {
+    if let Some(i) = UNITS.iter().position(|u| *u == word) {
+        return Some((i as u64, false));
+    }
+    if let Some(i) = TENS.iter().position(|t| !t.is_empty() && *t == word) {
+        return Some((i as u64 * 10, false));
+    }
+    UNITS
+        .iter()
+        .enumerate()
+        .map(|(i, u)| (i as u64, ordinal_word(u)))
+        .chain(TENS.iter().enumerate().skip(2).map(|(i, t)| (i as u64 * 10, ordinal_word(t))))
+        .find(|(_, ordinal)| ordinal == word)
+        .map(|(value, _)| (value, true))
+}
+
+// Value of a spelled-out number and whether it is an ordinal; None for runs like "two three"
+// or ones too large for a u64
+fn parse_words(phrase: &str) -> Option<(u64, bool)> {
+    let lower = phrase.to_lowercase();
+    let tokens: Vec<&str> = lower.split([' ', '-', '\t', '\n']).filter(|t| !t.is_empty() && *t != "and").collect();
+    // "a hundred" or "thousands" are not counts worth rewriting
+    if tokens.iter().all(|t| SCALES.iter().any(|(s, _)| s == t)) {
+        return None;
+    }
+    let (mut total, mut current, mut ordinal) = (0u64, 0u64, false);
+    for (i, token) in tokens.iter().enumerate() {
+        if ordinal {
+            return None;
+        }
+        if let Some((_, scale)) = SCALES.iter().find(|(s, _)| s == token) {
+            if *scale == 100 {
+                current = current.max(1).checked_mul(100)?;
+            } else {
+                total = total.checked_add(current.max(1).checked_mul(*scale)?)?;
+                current = 0;
+            }
+            continue;
+        }
+        let (value, is_ordinal) = word_value(token)?;
+        let below_hundred = current % 100;
+        // A unit may only follow a bare tens word or a scale; tens only a scale
+        let fits = match value {
+            0 => tokens.len() == 1,
+            1..=19 => below_hundred == 0 || (below_hundred >= 20 && below_hundred % 10 == 0 && value < 10),
+            _ => below_hundred == 0,
+        };
+        if !fits && i > 0 {
+            return None;
+        }
+        current = current.checked_add(value)?;
+        ordinal = is_ordinal;
+    }
+    Some((total.checked_add(current)?, ordinal))
+}
+
+// Convert Eastern Arabic and Persian digits to ASCII digits
+fn western_digits(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '٠'..='٩' => char::from(b'0' + (c as u32 - '٠' as u32) as u8),
+            '۰'..='۹' => char::from(b'0' + (c as u32 - '۰' as u32) as u8),
+            c => c,
+        })
+        .collect()
+}
+
+// Day number of a date's day part, spelled out or in digits; Hijri months have at most 30 days
+fn day_value(day: &str, month: &str) -> Option<u64> {
+    let digits: String = day.chars().take_while(char::is_ascii_digit).collect();
+    let value = if digits.is_empty() { parse_words(day)?.0 } else { digits.parse().ok()? };
+    let last = if GREGORIAN_MONTHS.contains(&month_name(month)) { 31 } else { 30 };
+    (1..=last).contains(&value).then_some(value)
+}
+
+fn format_day(day: u64, style: NumberStyle) -> String {
+    match style {
+        NumberStyle::Digits => day.to_string(),
+        NumberStyle::Words => format!("the {} of", ordinal_words(day)),
+    }
+}
+
+// Normalize month spellings, dates and numbers in `text`
+pub fn normalize(text: &str, style: NumberStyle) -> String {
+    let patterns = patterns();
+
+    let mut text = western_digits(text);
+    for (name, pattern) in &patterns.months {
+        text = pattern.replace_all(&text, *name).into_owned();
+    }
+    text = patterns
+        .era
+        .replace_all(&text, |caps: &Captures| if caps[0].to_lowercase().contains('h') { "AH" } else { "AD" })
+        .into_owned();
+
+    // Dates become "10 Ramadan" or "the tenth of Ramadan"
+    text = patterns
+        .day_of_month
+        .replace_all(&text, |caps: &Captures| match day_value(&caps[1], &caps[2]) {
+            Some(day) => format!("{} {}", format_day(day, style), month_name(&caps[2])),
+            None => caps[0].to_string(),
+        })
+        .into_owned();
+    text = patterns
+        .month_day
+        .replace_all(&text, |caps: &Captures| match day_value(&caps[2], &caps[1]) {
+            Some(day) => format!("{} {}", format_day(day, style), month_name(&caps[1])),
+            None => caps[0].to_string(),
+        })
+        .into_owned();
+
+    match style {
+        NumberStyle::Digits => patterns
+            .number_words
+            .replace_all(&text, |caps: &Captures| match parse_words(&caps[0]) {
+                Some((value, ordinal)) if value >= DIGITS_FROM => {
+                    if ordinal {
+                        format!("{}{}", value, ordinal_suffix(value))
+                    } else {
+                        value.to_string()
+                    }
+                }
+                _ => caps[0].to_string(),
+            })
+            .into_owned(),
+        NumberStyle::Words => {
+            let mut result = String::with_capacity(text.len());
+            let mut last = 0;
+            for found in patterns.digits.find_iter(&text) {
+                // Leave times, verse references, decimals and grouped numbers alone
+                let before = text[..found.start()].chars().next_back();
+                let after = text[found.end()..].chars().next();
+                let attached = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || ".,:/".contains(c));
+                let digits: String = found.as_str().chars().take_while(char::is_ascii_digit).collect();
+                let value: u64 = digits.parse().unwrap_or(u64::MAX);
+                if attached(before) || attached(after) || value > WORDS_UP_TO {
+                    continue;
+                }
+                result.push_str(&text[last..found.start()]);
+                if found.as_str().len() > digits.len() {
+                    result.push_str(&ordinal_words(value));
+                } else {
+                    result.push_str(&words(value));
+                }
+                last = found.end();
+            }
+            result.push_str(&text[last..]);
+            result
+        }
+    }
+}
+
@@ -0,0 +1,92 @@
+use serde_json::Value;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, PathItemType, Ref, RefOr, Response, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+
+use crate::http;
+use crate::review_server::{self, Role};
+
+// Generated from the route handlers and body types in review_server.rs
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Akhi Data Builder server API",
+        description = "Every operation needs \"Authorization: Bearer <token>\"; its description names the least role allowed."
+    ),
+    paths(
+        review_server::me,
+        review_server::list,
+        review_server::read,
+        review_server::save,
+        review_server::set_status,
+        review_server::claim,
+        review_server::release,
+        review_server::edits,
+        review_server::read_settings,
+        review_server::write_settings,
+        review_server::reset,
+        review_server::assistant
+    ),
+    components(schemas(
+        review_server::Caller,
+        review_server::Role,
+        review_server::Claim,
+        review_server::EditRecord,
+        http::ErrorBody,
+        review_server::TranscriptItem,
+        review_server::TranscriptView,
+        review_server::SaveBody,
+        review_server::StatusBody,
+        review_server::RevisionBody,
+        review_server::Released,
+        review_server::Saved,
+        review_server::ResetDone
+    )),
+    modifiers(&Access),
+    security(("bearer" = []))
+)]
+struct ApiDoc;
+
+fn error_response(description: &str) -> RefOr<Response> {
+    ResponseBuilder::new()
+        .description(description)
+        .content("application/json", ContentBuilder::new().schema(Ref::from_schema_name("ErrorBody")).build())
+        .build()
+        .into()
+}
+
+// Adds the bearer scheme, and the role each operation needs as the router checks it
+struct Access;
+
+impl Modify for Access {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme("bearer", SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)));
+
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+            for (method, operation) in item.operations.iter_mut() {
+                let method = match method {
+                    PathItemType::Get => "GET",
+                    PathItemType::Put => "PUT",
+                    PathItemType::Post => "POST",
+                    PathItemType::Delete => "DELETE",
+                    _ => continue,
+                };
+                let role = review_server::required_role(method, &segments).unwrap_or(Role::Admin);
+                operation.description = Some(format!("Requires the {} role.", role.as_str()));
+
+                let responses = &mut operation.responses.responses;
+                responses.entry("401".to_string()).or_insert_with(|| error_response("Invalid or missing token"));
+                responses
+                    .entry("403".to_string())
+                    .or_insert_with(|| error_response(&format!("Requires the {} role", role.as_str())));
+            }
+        }
+    }
+}
+
+// OpenAPI 3.0 description of the server-mode API, served unauthenticated at /openapi.json
+pub fn spec() -> Value {
+    serde_json::to_value(ApiDoc::openapi()).unwrap_or_default()
+}
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::dataset::{self, DatasetEntry, Provenance};
+use crate::duplicates;
+use crate::journal;
+use crate::manifest::{self, ExportManifest};
+use crate::metadata;
+use crate::sources;
+use crate::state;
+use crate::storage::storage;
+
+// A source whose content must never appear in a dataset, e.g. after a takedown request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptOut {
+    // "url" for a single video, "channel" for everything a channel published
+    pub kind: String,
+    pub value: String,
+    pub reason: Option<String>,
+    pub added_at: u64,
+}
+
+// Entries removed from one dataset by apply_optouts
+#[derive(Debug, Clone, Serialize)]
+pub struct RemovedEntry {
+    pub id: String,
+    pub source: Option<String>,
+    pub video_url: Option<String>,
+    pub channel: Option<String>,
+    // Value of the opt-out that matched
+    pub opt_out: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct OptOutReport {
+    // Entries removed per dataset and per exported subset ("subsets/<name>")
+    pub removed: BTreeMap<String, Vec<RemovedEntry>>,
+    // Opted-out entries per release bundle; published releases are not rewritten, so these need
+    // a new release
+    pub releases: BTreeMap<String, Vec<RemovedEntry>>,
+    // Ids of entries whose source could not be determined, per file, so no opt-out could be checked
+    pub unresolved: BTreeMap<String, Vec<String>>,
+}
+
+impl OptOut {
+    fn matches(&self, provenance: &Provenance) -> bool {
+        let Some(url) = provenance.video_url.as_deref() else {
+            return self.kind == "channel" && provenance.channel.as_deref().is_some_and(|c| self.same_channel(c));
+        };
+        match self.kind.as_str() {
+            "url" => duplicates::canonical(url) == duplicates::canonical(&self.value),
+            _ => {
+                provenance.channel.as_deref().is_some_and(|c| self.same_channel(c))
+                    || sources::channels_in_url(url).iter().any(|c| self.same_channel(c))
+            }
+        }
+    }
+
+    fn same_channel(&self, channel: &str) -> bool {
+        sources::normalize(channel) == sources::normalize(&self.value)
+    }
+}
+
+// Where an entry came from, from its provenance or the source clip's metadata
+fn provenance_of(entry: &DatasetEntry) -> Option<Provenance> {
+    entry
+        .provenance
+        .clone()
+        .or_else(|| entry.source.as_deref().and_then(metadata::load).map(|m| Provenance::from_metadata(&m)))
+}
+
+// The opt-out covering an entry, if any
+pub fn matching<'a>(entry: &DatasetEntry, optouts: &'a [OptOut]) -> Option<&'a OptOut> {
+    if optouts.is_empty() {
+        return None;
+    }
+    let provenance = provenance_of(entry)?;
+    optouts.iter().find(|o| o.matches(&provenance))
+}
+
+// Drop entries from opted-out sources; returns the number of entries dropped
+pub fn apply(entries: &mut Vec<DatasetEntry>, optouts: &[OptOut]) -> usize {
+    let before = entries.len();
+    entries.retain(|entry| matching(entry, optouts).is_none());
+    before - entries.len()
+}
+
+// Command to add a URL or channel to the opt-out registry
+#[tauri::command]
+pub fn add_optout(kind: String, value: String, reason: Option<String>) -> Result<OptOut, String> {
+    if kind != "url" && kind != "channel" {
+        return Err(format!("Unknown opt-out kind: {}", kind));
+    }
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err("Opt-out value is empty".to_string());
+    }
+
+    let optout = OptOut { kind, value, reason, added_at: state::now() };
+    state::update(|s| {
+        if s.opt_outs.iter().any(|o| o.kind == optout.kind && o.value == optout.value) {
+            return Err(format!("Already opted out: {}", optout.value));
+        }
+        s.opt_outs.push(optout.clone());
+        Ok(())
+    })??;
+    audit::record("optout.add", &optout.value, serde_json::json!({ "kind": optout.kind, "reason": optout.reason }));
+    Ok(optout)
+}
+
+// Command to remove a URL or channel from the opt-out registry
+#[tauri::command]
+pub fn remove_optout(kind: String, value: String) -> Result<(), String> {
+    state::update(|s| {
+        let before = s.opt_outs.len();
+        s.opt_outs.retain(|o| !(o.kind == kind && o.value == value));
+        if s.opt_outs.len() == before {
+            return Err(format!("Not opted out: {}", value));
+        }
+        Ok(())
+    })??;
+    audit::record("optout.remove", &value, serde_json::json!({ "kind": kind }));
+    Ok(())
+}
+
+// Command to list the opt-out registry, oldest first
+#[tauri::command]
+pub fn get_optouts() -> Result<Vec<OptOut>, String> {
+    Ok(state::load()?.opt_outs)
+}
+
+// Drop entries from opted-out sources; returns the removed entries and the ids of entries
+// without a known source, which are kept
+fn purge(entries: &mut Vec<DatasetEntry>, optouts: &[OptOut]) -> (Vec<RemovedEntry>, Vec<String>) {
+    let mut removed = Vec::new();
+    let mut unresolved = Vec::new();
+    entries.retain(|entry| {
+        let provenance = provenance_of(entry).filter(|p| p.video_url.is_some() || p.channel.is_some());
+        let Some(provenance) = provenance else {
+            unresolved.push(entry.id.clone());
+            return true;
+        };
+        let Some(optout) = optouts.iter().find(|o| o.matches(&provenance)) else { return true };
+        removed.push(RemovedEntry {
+            id: entry.id.clone(),
+            source: entry.source.clone(),
+            video_url: provenance.video_url,
+            channel: provenance.channel,
+            opt_out: optout.value.clone(),
+        });
+        false
+    });
+    (removed, unresolved)
+}
+
+fn load(path: &Path) -> Result<Vec<DatasetEntry>, String> {
+    let mut entries = dataset::load_entries(path)?;
+    entries.iter_mut().for_each(dataset::annotate);
+    Ok(entries)
+}
+
+// JSON files directly in `dir`, without their manifests
+fn data_files(dir: &Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = storage().read_dir(dir) else { return Vec::new() };
+    let mut files: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".json") && !name.ends_with(".manifest.json")
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+// Rewrite a dataset or subset without the removed entries; a subset's manifest is written again
+// so its checksum matches
+fn save(label: &str, path: &Path, entries: &[DatasetEntry], removed: usize, subset: bool) -> Result<(), String> {
+    let manifest_path = manifest::manifest_path(path);
+    let mut journal = journal::begin("dataset", &format!("Purge {} opted-out entries from {}", removed, label))?;
+    journal.preserve(path)?;
+    if subset && storage().exists(&manifest_path) {
+        journal.preserve(&manifest_path)?;
+    }
+    dataset::save_entries(path, entries)?;
+
+    if subset {
+        let previous: Option<ExportManifest> = storage()
+            .read_to_string(&manifest_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        let (dataset_id, mut parameters) = previous
+            .map(|m| (m.dataset, m.parameters))
+            .unwrap_or_else(|| (dataset::DEFAULT_DATASET.to_string(), serde_json::json!({})));
+        parameters["opt_outs_removed"] = serde_json::json!(removed);
+        manifest::record("subset", &dataset_id, path, entries.len(), parameters)?;
+    }
+
+    journal.commit()?;
+    audit::record("optout.apply", label, serde_json::json!({ "removed": removed }));
+    Ok(())
+}
+
+// Command to purge entries from opted-out sources from every generated dataset and exported
+// subset, report release bundles that still contain some, and list entries whose source is
+// unknown; the removal can be undone
+#[tauri::command]
+pub fn apply_optouts() -> Result<OptOutReport, String> {
+    let optouts = state::load()?.opt_outs;
+    let mut report = OptOutReport::default();
+    let mut note_unresolved = |label: &str, ids: Vec<String>| {
+        if !ids.is_empty() {
+            report.unresolved.insert(label.to_string(), ids);
+        }
+    };
+
+    let mut purged = Vec::new();
+    for config in dataset::dataset_configs() {
+        let path = dataset::dataset_path(Some(&config.id))?;
+        if storage().exists(&path) {
+            purged.push((config.id, path, false));
+        }
+    }
+    for path in data_files(&storage().path("output/json/subsets")) {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        purged.push((format!("subsets/{}", name), path, true));
+    }
+
+    let mut removed_by_file = BTreeMap::new();
+    for (label, path, subset) in purged {
+        let mut entries = load(&path)?;
+        let (removed, unresolved) = purge(&mut entries, &optouts);
+        note_unresolved(&label, unresolved);
+        if !removed.is_empty() {
+            save(&label, &path, &entries, removed.len(), subset)?;
+            removed_by_file.insert(label, removed);
+        }
+    }
+
+    let mut releases = BTreeMap::new();
+    if let Ok(bundles) = storage().read_dir(storage().path("output/releases")) {
+        for bundle in bundles.filter_map(Result::ok) {
+            let path = bundle.path().join("dataset.json");
+            if !storage().exists(&path) {
+                continue;
+            }
+            let label = format!("releases/{}", bundle.file_name().to_string_lossy());
+            let mut entries = load(&path)?;
+            let (removed, unresolved) = purge(&mut entries, &optouts);
+            note_unresolved(&label, unresolved);
+            if !removed.is_empty() {
+                releases.insert(label, removed);
+            }
+        }
+    }
+
+    report.removed = removed_by_file;
+    report.releases = releases;
+    Ok(report)
+}
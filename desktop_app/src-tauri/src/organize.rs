@@ -0,0 +1,307 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::audit;
+use crate::clips;
+use crate::coldstorage::ColdItem;
+use crate::metadata;
+use crate::naming;
+use crate::state::{self, PipelineState};
+use crate::storage::storage;
+use crate::workspace;
+
+// Directories holding files named "<clip stem>.<suffix>". A clip's audio, info JSON and kept
+// video live in its clip folder below output/clips instead and move with the scheme's folder.
+const STEM_DIRS: &[&str] = &[
+    "output/clips/archive",
+    "output/transcripts",
+    "output/annotations",
+    "output/drafts",
+    "output/transliterations",
+    "output/translations",
+    "output/sections",
+    "output/questions",
+    "output/subtitles",
+];
+
+// Directories holding one "<clip stem>/" folder per clip: transcript snapshots and video frames
+const STEM_FOLDERS: &[&str] = &["output/revisions", "output/frames"];
+
+#[derive(Debug, Serialize)]
+pub struct Renamed {
+    pub from: String,
+    pub to: String,
+    pub files: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Skipped {
+    pub clip: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct OrganizeReport {
+    pub scheme: String,
+    pub renamed: Vec<Renamed>,
+    pub skipped: Vec<Skipped>,
+}
+
+// Naming template of a scheme: a preset name or a template itself
+fn scheme_template(scheme: &str) -> Result<String, String> {
+    let template = match scheme {
+        "channel" => "{channel}/{title}".to_string(),
+        "channel_date" => "{channel}/{upload_date}/{title}".to_string(),
+        "flat" => naming::DEFAULT_TEMPLATE.to_string(),
+        "template" => naming::template(),
+        other if other.contains('{') => other.to_string(),
+        other => return Err(format!("Unknown organization scheme: {}", other)),
+    };
+    naming::validate(&template)?;
+    Ok(template)
+}
+
+// A clip rename: its stem before and after, and its new folder below output/clips
+// ("" or ending in "/")
+struct Move {
+    from: String,
+    to: String,
+    folder: String,
+}
+
+// Prefixes of stems that extend `stem` with a dot, e.g. "Talk.part2." for "Talk"
+fn longer_stems(stem: &str, stems: &BTreeSet<String>) -> Vec<String> {
+    let prefix = format!("{}.", stem);
+    stems.iter().filter(|s| s.starts_with(&prefix)).map(|s| format!("{}.", s)).collect()
+}
+
+// Suffix of a file or key named after `stem`, e.g. ".mp3" or ".txt.json";
+// names of a longer stem sharing the prefix ("Talk" and "Talk.part2") do not match
+fn stem_suffix<'a>(name: &'a str, stem: &str, longer: &[String]) -> Option<&'a str> {
+    if longer.iter().any(|l| name.starts_with(l.as_str())) {
+        return None;
+    }
+    name.strip_prefix(stem).filter(|rest| rest.starts_with('.'))
+}
+
+// Files of `from` in `dir`, paired with their paths in `target` under the new stem
+fn renames_in(dir: &Path, target: &Path, from: &str, to: &str, longer: &[String]) -> Vec<(PathBuf, PathBuf)> {
+    let Ok(entries) = storage().read_dir(dir) else { return Vec::new() };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let suffix = stem_suffix(&name, from, longer)?;
+            Some((entry.path(), target.join(format!("{}{}", to, suffix))))
+        })
+        .collect()
+}
+
+// A cold storage file path after the move: relative to the item's directory, and outside
+// "archive/" placed in the clip's new folder
+fn renamed_cold_file(file: &str, plan: &Move) -> String {
+    let (dir, name) = match file.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name),
+        None => (String::new(), file),
+    };
+    let suffix = name.strip_prefix(plan.from.as_str()).unwrap_or(name);
+    let dir = if dir == "archive/" { dir } else { plan.folder.clone() };
+    format!("{}{}{}", dir, plan.to, suffix)
+}
+
+// The cold storage record of a clip after the move; its directory is named by the stem
+fn renamed_cold_item(item: &ColdItem, plan: &Move) -> ColdItem {
+    let location = Path::new(&item.location);
+    let location = location.parent().map_or_else(|| PathBuf::from(&plan.to), |root| root.join(&plan.to));
+    ColdItem {
+        location: location.to_string_lossy().to_string(),
+        files: item.files.iter().map(|f| renamed_cold_file(f, plan)).collect(),
+        ..item.clone()
+    }
+}
+
+// Files and revision folders that belong to a clip stem, with their renamed paths
+fn stem_files(plan: &Move, stems: &BTreeSet<String>, cold: Option<&ColdItem>) -> Vec<(PathBuf, PathBuf)> {
+    let (from, to) = (plan.from.as_str(), plan.to.as_str());
+    let longer = longer_stems(from, stems);
+
+    let media = clips::clips_dir().join(&plan.folder);
+    let mut moves = renames_in(&clips::media_dir(from), &media, from, to, &longer);
+    for dir in STEM_DIRS {
+        let dir = storage().path(dir);
+        moves.extend(renames_in(&dir, &dir, from, to, &longer));
+    }
+
+    for dir in STEM_FOLDERS {
+        let dir = storage().path(dir);
+        if storage().exists(dir.join(from)) {
+            moves.push((dir.join(from), dir.join(to)));
+        }
+    }
+
+    // Audio in cold storage is renamed there, so restore_item brings it back under the new name
+    if let Some(item) = cold {
+        let renamed = renamed_cold_item(item, plan);
+        for (file, target) in item.files.iter().zip(&renamed.files) {
+            moves.push((Path::new(&item.location).join(file), Path::new(&renamed.location).join(target)));
+        }
+    }
+
+    // A clip that only changes folder keeps the names of everything else
+    moves.retain(|(source, target)| source != target);
+    moves
+}
+
+// Rename every key that refers to a clip stem or a file derived from it
+fn rename_references(state: &mut PipelineState, plan: &Move, stems: &BTreeSet<String>) {
+    let (from, to) = (plan.from.as_str(), plan.to.as_str());
+    let longer = longer_stems(from, stems);
+    let rename = |name: &str| -> Option<String> { stem_suffix(name, from, &longer).map(|suffix| format!("{}{}", to, suffix)) };
+
+    macro_rules! rekey {
+        ($map:expr) => {
+            let keys: Vec<String> = $map.keys().filter(|k| rename(k).is_some()).cloned().collect();
+            for key in keys {
+                if let Some(value) = $map.remove(&key) {
+                    $map.insert(rename(&key).unwrap_or(key), value);
+                }
+            }
+        };
+    }
+
+    rekey!(state.transcript_tags);
+    rekey!(state.citations);
+    rekey!(state.transcript_speakers);
+    rekey!(state.speaker_turns);
+    rekey!(state.transcript_cache);
+    rekey!(state.review_status);
+    rekey!(state.screening);
+    rekey!(state.review_claims);
+    rekey!(state.segment_flags);
+    rekey!(state.archived_clips);
+
+    if let Some(record) = state.archived_clips.get_mut(&format!("{}.mp3", to)) {
+        record.archived = renamed_cold_file(&record.archived, plan);
+    }
+    if let Some(notes) = state.clip_notes.remove(from) {
+        state.clip_notes.insert(to.to_string(), notes);
+    }
+    if let Some(item) = state.cold_items.remove(from) {
+        state.cold_items.insert(to.to_string(), renamed_cold_item(&item, plan));
+    }
+
+    let excluded: Vec<String> = state.excluded_from_dataset.iter().filter(|k| rename(k).is_some()).cloned().collect();
+    for key in excluded {
+        state.excluded_from_dataset.remove(&key);
+        state.excluded_from_dataset.insert(rename(&key).unwrap_or(key));
+    }
+
+    for record in &mut state.download_history {
+        for clip in &mut record.clips {
+            if let Some(renamed) = rename(clip) {
+                *clip = renamed;
+            }
+        }
+    }
+}
+
+// Undo completed moves, newest first
+fn roll_back(done: &[(PathBuf, PathBuf)]) {
+    for (from, to) in done.iter().rev() {
+        storage().rename(to, from).ok();
+    }
+}
+
+// Command to rename existing clips, transcripts and sidecars by their stored metadata, moving
+// clips into "<channel>/" or "<channel>/<upload date>/" folders below output/clips.
+// scheme is "channel", "channel_date", "flat", "template" (the workspace naming template)
+// or a naming template; the state store is updated in the same step, and every file is
+// moved back if any move or the state update fails.
+#[tauri::command]
+pub fn organize_outputs(scheme: String) -> Result<OrganizeReport, String> {
+    workspace::ensure_writable()?;
+    let template = scheme_template(&scheme)?;
+    let mut report = OrganizeReport { scheme, ..Default::default() };
+
+    let clips = clips::list_clips();
+    let stems: BTreeSet<String> = clips.iter().map(|c| metadata::stem(c).to_string()).collect();
+    let cold_items = state::load()?.cold_items;
+    let mut taken = stems.clone();
+    let mut plan = Vec::new();
+    // Cold storage locations stay granted until every move is done
+    let mut grants = Vec::new();
+
+    for clip in clips {
+        let from = metadata::stem(&clip).to_string();
+        let meta = metadata::load(&clip).unwrap_or_default();
+        let rendered = naming::render(&template, &meta, &from)?;
+        let (folder, to) = naming::split(&rendered);
+        let folder = if folder.is_empty() { String::new() } else { format!("{}/", folder) };
+
+        if to == from && folder == clips::folder(&from) {
+            continue;
+        }
+        if rendered.split('/').any(|part| part.trim().is_empty() || part.starts_with('.')) {
+            report.skipped.push(Skipped { clip, reason: format!("invalid name: {:?}", rendered) });
+            continue;
+        }
+        if to != from && taken.contains(to) {
+            report.skipped.push(Skipped { clip, reason: format!("{} already exists", to) });
+            continue;
+        }
+        if let Some(item) = cold_items.get(&from) {
+            match storage().allow(&item.location) {
+                Ok(grant) => grants.push(grant),
+                Err(e) => {
+                    report.skipped.push(Skipped { clip, reason: format!("cold storage is not accessible: {}", e) });
+                    continue;
+                }
+            }
+            if !storage().exists(&item.location) {
+                report.skipped.push(Skipped { clip, reason: format!("cold storage is not reachable: {}", item.location) });
+                continue;
+            }
+        }
+
+        taken.remove(&from);
+        taken.insert(to.to_string());
+        plan.push(Move { from, to: to.to_string(), folder });
+    }
+
+    let mut done: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for step in &plan {
+        let moves = stem_files(step, &stems, cold_items.get(&step.from));
+        for (source, target) in &moves {
+            if storage().exists(target) {
+                roll_back(&done);
+                return Err(format!("Failed to organize outputs: {} already exists", target.display()));
+            }
+            let moved = match target.parent() {
+                Some(parent) => storage().create_dir_all(parent).and_then(|_| storage().rename(source, target)),
+                None => storage().rename(source, target),
+            };
+            if let Err(e) = moved {
+                roll_back(&done);
+                return Err(format!("Failed to organize outputs: {}", e));
+            }
+            done.push((source.clone(), target.clone()));
+        }
+        let to = format!("{}{}", step.folder, step.to);
+        report.renamed.push(Renamed { from: step.from.clone(), to, files: moves.len() });
+    }
+
+    let updated = state::update(|state| {
+        for step in &plan {
+            rename_references(state, step, &stems);
+        }
+    });
+    if let Err(e) = updated {
+        roll_back(&done);
+        return Err(e);
+    }
+    audit::record("outputs.organize", &report.scheme, serde_json::json!({ "renamed": report.renamed.len() }));
+
+    Ok(report)
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::clips;
+use crate::duplicates;
+use crate::metadata;
+use crate::text;
+use crate::storage::storage;
+
+// Direct downloads in progress, named by the fingerprint of their link
+pub fn direct_dir() -> PathBuf {
+    storage().path("output/clips/.partial")
+}
+
+// yt-dlp's unfinished files: "<name>.webm.part", "<name>.f251.webm.part-Frag3", ...
+fn is_ytdlp_part(name: &str) -> bool {
+    name.ends_with(".part") || name.contains(".part-Frag")
+}
+
+// Stem whose info JSON yt-dlp wrote before starting the download
+fn info_stem(file_name: &str) -> Option<String> {
+    let mut stem = file_name;
+    while let Some((shorter, _)) = stem.rsplit_once('.') {
+        stem = shorter;
+        if storage().exists(metadata::info_path(&format!("{}.mp3", stem))) {
+            return Some(stem.to_string());
+        }
+    }
+    None
+}
+
+// Unfinished bytes on disk per link, for links with a partial download
+pub fn partial_bytes(links: &[String]) -> BTreeMap<String, u64> {
+    let mut sizes = BTreeMap::new();
+    let size = |path: PathBuf| storage().metadata(path).map_or(0, |m| m.len());
+
+    // yt-dlp parts are matched to links through the info JSON written next to them
+    let by_canonical: BTreeMap<String, &String> = links.iter().map(|l| (duplicates::canonical(l), l)).collect();
+    for entries in clips::media_dirs().into_iter().filter_map(|dir| storage().read_dir(dir).ok()) {
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !is_ytdlp_part(&name) {
+                continue;
+            }
+            let url = info_stem(&name)
+                .and_then(|stem| metadata::load(&format!("{}.mp3", stem)))
+                .and_then(|meta| meta.webpage_url);
+            if let Some(link) = url.and_then(|url| by_canonical.get(&duplicates::canonical(&url))) {
+                *sizes.entry((*link).clone()).or_insert(0) += size(entry.path());
+            }
+        }
+    }
+
+    // Direct downloads are named by the fingerprint of their link
+    let by_fingerprint: BTreeMap<String, &String> = links.iter().map(|l| (text::fingerprint(l.bytes()), l)).collect();
+    if let Ok(entries) = storage().read_dir(direct_dir()) {
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+            if let Some(link) = by_fingerprint.get(&id) {
+                *sizes.entry((*link).clone()).or_insert(0) += size(path);
+            }
+        }
+    }
+    sizes
+}
+
+// Command to delete every unfinished download so the next attempt starts over
+#[tauri::command]
+pub fn discard_partial_downloads() -> Result<u64, String> {
+    let mut freed = 0;
+    let mut remove = |path: PathBuf| -> Result<(), String> {
+        freed += storage().metadata(&path).map_or(0, |m| m.len());
+        storage().remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+    };
+
+    for entries in clips::media_dirs().into_iter().filter_map(|dir| storage().read_dir(dir).ok()) {
+        for entry in entries.filter_map(Result::ok) {
+            if is_ytdlp_part(&entry.file_name().to_string_lossy()) {
+                remove(entry.path())?;
+            }
+        }
+    }
+    if let Ok(entries) = storage().read_dir(direct_dir()) {
+        for entry in entries.filter_map(Result::ok) {
+            remove(entry.path())?;
+        }
+    }
+    Ok(freed)
+}
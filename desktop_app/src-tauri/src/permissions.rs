@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::dataset::DatasetEntry;
+use crate::sources;
+use crate::state;
+
+// Whether a channel owner gave explicit permission to use their content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelPermission {
+    // Channel name as entered; the state key is its normalized form
+    pub channel: String,
+    pub granted: bool,
+    // When permission was given or refused, as entered by the curator (e.g. 2024-05-01)
+    pub date: Option<String>,
+    pub notes: Option<String>,
+    pub recorded_at: u64,
+}
+
+// Mark the entry's provenance with the permission recorded for its channel
+pub fn attach(entry: &mut DatasetEntry, state: &state::PipelineState) {
+    if let Some(provenance) = entry.provenance.as_mut() {
+        provenance.permission = provenance
+            .channel
+            .as_deref()
+            .and_then(|c| state.channel_permissions.get(&sources::normalize(c)))
+            .map(|p| p.granted);
+    }
+}
+
+// Command to record whether a channel owner gave permission, with optional date and notes
+#[tauri::command]
+pub fn set_channel_permission(
+    channel: String,
+    granted: bool,
+    date: Option<String>,
+    notes: Option<String>,
+) -> Result<ChannelPermission, String> {
+    let key = sources::normalize(&channel);
+    if key.is_empty() {
+        return Err("Channel name is empty".to_string());
+    }
+
+    let permission = ChannelPermission { channel: channel.trim().to_string(), granted, date, notes, recorded_at: state::now() };
+    state::update(|s| {
+        s.channel_permissions.insert(key.clone(), permission.clone());
+    })?;
+    audit::record("permission.set", &key, serde_json::json!({ "granted": granted, "date": permission.date }));
+    Ok(permission)
+}
+
+// Command to forget the permission recorded for a channel
+#[tauri::command]
+pub fn remove_channel_permission(channel: String) -> Result<(), String> {
+    let key = sources::normalize(&channel);
+    state::update(|s| s.channel_permissions.remove(&key))?
+        .ok_or_else(|| format!("No permission recorded for {}", channel))?;
+    audit::record("permission.remove", &key, serde_json::json!({}));
+    Ok(())
+}
+
+// Command to list recorded channel permissions by normalized channel name
+#[tauri::command]
+pub fn get_channel_permissions() -> Result<BTreeMap<String, ChannelPermission>, String> {
+    Ok(state::load()?.channel_permissions)
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::settings;
+
+// Persona generated datasets use when neither the dataset nor the caller picks one
+pub const DEFAULT_PERSONA: &str = "akhi";
+
+// Framing applied to every entry of a generated dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Persona {
+    pub id: String,
+    pub name: String,
+    // Emitted as the entry's "system" field; omitted when empty
+    #[serde(default)]
+    pub system_prompt: String,
+    pub instruction: String,
+    pub output: String,
+}
+
+impl Persona {
+    // The framing that used to be hard-coded in make_quran_lora_json.py
+    pub fn akhi() -> Self {
+        Persona {
+            id: DEFAULT_PERSONA.to_string(),
+            name: "Akhi".to_string(),
+            system_prompt: String::new(),
+            instruction: "Summarize and offer Islamic advice based on this:".to_string(),
+            output: "Remember, Allah is always with those who are patient and sincere.".to_string(),
+        }
+    }
+}
+
+// Look up a persona in the workspace settings; the default persona always exists
+pub fn persona(id: Option<&str>) -> Result<Persona, String> {
+    let id = id.unwrap_or(DEFAULT_PERSONA);
+    settings::load()
+        .personas
+        .into_iter()
+        .find(|p| p.id == id)
+        .or_else(|| (id == DEFAULT_PERSONA).then(Persona::akhi))
+        .ok_or_else(|| format!("Unknown persona: {}", id))
+}
+
+// Command to list the configured personas
+#[tauri::command]
+pub fn get_personas() -> Vec<Persona> {
+    let mut personas = settings::load().personas;
+    if !personas.iter().any(|p| p.id == DEFAULT_PERSONA) {
+        personas.insert(0, Persona::akhi());
+    }
+    personas
+}
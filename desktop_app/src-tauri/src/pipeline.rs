@@ -0,0 +1,362 @@
+#[cfg(feature = "mock-tools")]
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::clips;
+use crate::direct;
+use crate::joblogs;
+use crate::jobs;
+use crate::metadata;
+use crate::naming;
+use crate::portable;
+use crate::ratelimit;
+use crate::scheduler::{self, Resource};
+use crate::settings;
+use crate::storage::storage;
+use crate::vram::{self, GpuPlan};
+use crate::whisper;
+
+// Environment variable pointing at a JSON file of canned tool responses; see MockRunner.
+// Only builds with the mock-tools feature read it.
+#[cfg(feature = "mock-tools")]
+pub const MOCK_TOOLS_ENV: &str = "AKHI_MOCK_TOOLS";
+
+// Captured result of one external tool run on one item
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ToolOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+// One external tool call: yt-dlp, whisper, python scripts, pandoc and the like
+#[derive(Debug, Clone)]
+pub struct Invocation {
+    pub program: String,
+    pub args: Vec<OsString>,
+    // Run in the pipeline directory with the portable-mode tool environment
+    pub in_workspace: bool,
+    pub stdin: Option<Vec<u8>>,
+}
+
+impl Invocation {
+    pub fn new(program: &str) -> Self {
+        Invocation { program: program.to_string(), args: Vec::new(), in_workspace: false, stdin: None }
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args.extend(args.into_iter().map(|a| a.as_ref().to_os_string()));
+        self
+    }
+
+    pub fn in_workspace(mut self) -> Self {
+        self.in_workspace = true;
+        self
+    }
+
+    pub fn stdin(mut self, input: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(input.into());
+        self
+    }
+
+    // Run through the installed tool runner
+    pub fn run(&self) -> io::Result<ToolOutput> {
+        runner().run(self)
+    }
+}
+
+// Executes external tools; swapped for MockRunner where the real tools are unavailable
+pub trait ToolRunner: Send + Sync {
+    fn run(&self, invocation: &Invocation) -> io::Result<ToolOutput>;
+}
+
+// Runs tools as child processes
+pub struct ProcessRunner;
+
+// Collect one output stream, counting every chunk as a heartbeat of the job that started the tool
+// and copying it to the job's log as it arrives
+fn drain(mut stream: impl Read + Send + 'static, job: Option<String>, log: Option<Arc<Mutex<File>>>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut collected = Vec::new();
+        let mut buffer = [0u8; 8192];
+        while let Ok(read) = stream.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            collected.extend_from_slice(&buffer[..read]);
+            if let Some(job) = &job {
+                jobs::heartbeat(job);
+            }
+            if let Some(log) = &log {
+                log.lock().unwrap_or_else(|e| e.into_inner()).write_all(&buffer[..read]).ok();
+            }
+        }
+        collected
+    })
+}
+
+impl ToolRunner for ProcessRunner {
+    fn run(&self, invocation: &Invocation) -> io::Result<ToolOutput> {
+        let mut command = Command::new(&invocation.program);
+        command.args(&invocation.args);
+        if invocation.in_workspace {
+            command.current_dir(storage().root()).envs(portable::tool_env());
+        }
+
+        let mut child = command
+            .stdin(if invocation.stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Feed stdin from a separate thread so a full stdout pipe cannot deadlock us
+        let writer = match invocation.stdin.clone() {
+            Some(input) => {
+                let mut stdin = child.stdin.take().ok_or_else(|| io::Error::other("stdin unavailable"))?;
+                Some(std::thread::spawn(move || stdin.write_all(&input)))
+            }
+            None => None,
+        };
+
+        let job = jobs::current();
+        let log = job.as_deref().and_then(joblogs::open).map(|mut file| {
+            file.write_all(joblogs::command_line(&invocation.program, &invocation.args).as_bytes()).ok();
+            Arc::new(Mutex::new(file))
+        });
+        let stdout = drain(child.stdout.take().ok_or_else(|| io::Error::other("stdout unavailable"))?, job.clone(), log.clone());
+        let stderr = drain(child.stderr.take().ok_or_else(|| io::Error::other("stderr unavailable"))?, job.clone(), log.clone());
+
+        // Poll rather than wait so a stalled tool can be killed from kill_job
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if job.as_deref().is_some_and(jobs::kill_requested) {
+                child.kill().ok();
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        };
+
+        let stdout = stdout.join().map_err(|_| io::Error::other("stdout reader panicked"))?;
+        let stderr = stderr.join().map_err(|_| io::Error::other("stderr reader panicked"))?;
+        if let Some(log) = &log {
+            writeln!(log.lock().unwrap_or_else(|e| e.into_inner()), "\n--- exit: {}", status).ok();
+        }
+        if let Some(writer) = writer {
+            // A killed tool closes its stdin early; that is not a failure of its own
+            if let Err(e) = writer.join().map_err(|_| io::Error::other("stdin writer panicked"))? {
+                if status.success() {
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(ToolOutput {
+            success: status.success(),
+            stdout: String::from_utf8_lossy(&stdout).to_string(),
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
+        })
+    }
+}
+
+// Returns canned responses keyed by program name and never spawns anything.
+// Programs without a response succeed with empty output.
+#[cfg(feature = "mock-tools")]
+pub struct MockRunner {
+    responses: BTreeMap<String, ToolOutput>,
+}
+
+#[cfg(feature = "mock-tools")]
+impl MockRunner {
+    pub fn new(responses: BTreeMap<String, ToolOutput>) -> Self {
+        MockRunner { responses }
+    }
+
+    // Load responses from the JSON file named by AKHI_MOCK_TOOLS, e.g. {"yt-dlp": {"success": true}}
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var_os(MOCK_TOOLS_ENV)?;
+        let _grant = storage().allow(&path).ok()?;
+        let responses = storage()
+            .read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Some(MockRunner::new(responses))
+    }
+}
+
+#[cfg(feature = "mock-tools")]
+impl ToolRunner for MockRunner {
+    fn run(&self, invocation: &Invocation) -> io::Result<ToolOutput> {
+        Ok(self
+            .responses
+            .get(&invocation.program)
+            .cloned()
+            .unwrap_or(ToolOutput { success: true, ..Default::default() }))
+    }
+}
+
+// Whether a mock-tools build runs with canned tool responses
+#[cfg(feature = "mock-tools")]
+pub fn mocked() -> bool {
+    std::env::var_os(MOCK_TOOLS_ENV).is_some()
+}
+
+#[cfg(not(feature = "mock-tools"))]
+pub fn mocked() -> bool {
+    false
+}
+
+static RUNNER: RwLock<Option<Arc<dyn ToolRunner>>> = RwLock::new(None);
+
+// Replace the tool runner used by all commands
+pub fn set_runner(runner: Arc<dyn ToolRunner>) {
+    *RUNNER.write().unwrap_or_else(|e| e.into_inner()) = Some(runner);
+}
+
+// Current tool runner: real processes, or the mock when a mock-tools build has AKHI_MOCK_TOOLS set
+pub fn runner() -> Arc<dyn ToolRunner> {
+    if let Some(runner) = RUNNER.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return runner.clone();
+    }
+
+    #[cfg(feature = "mock-tools")]
+    let runner: Arc<dyn ToolRunner> = match MockRunner::from_env() {
+        Some(mock) => Arc::new(mock),
+        None => Arc::new(ProcessRunner),
+    };
+    #[cfg(not(feature = "mock-tools"))]
+    let runner: Arc<dyn ToolRunner> = Arc::new(ProcessRunner);
+    set_runner(runner.clone());
+    runner
+}
+
+// Result of downloading one link
+pub struct Download {
+    pub output: ToolOutput,
+    // Clip file names written into output/clips
+    pub clips: Vec<String>,
+}
+
+// Audio extraction alone picks an audio-only format, so a kept video needs its own selector
+fn keep_video_args() -> Vec<&'static str> {
+    if settings::load().keep_video {
+        vec!["-f", "bestvideo*+bestaudio/best", "--keep-video"]
+    } else {
+        Vec::new()
+    }
+}
+
+// Download a single link as mp3 plus its info JSON (and the video when keep_video is set)
+// into output/clips, named by the naming template
+pub fn download_link(link: &str) -> Result<Download, String> {
+    // Wait out the per-host politeness delay before taking a scheduler slot
+    let policy = ratelimit::policy(link);
+    let _host = ratelimit::acquire(link, &policy)?;
+    let _permit = scheduler::acquire(Resource::Cpu)?;
+
+    // Bare media files, e.g. on archive.org, skip yt-dlp
+    if direct::is_direct_media(link) {
+        return direct::download(link);
+    }
+
+    // yt-dlp lists the final path of every file it produced here
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let printed = format!("output/clips/.downloaded-{}.txt", nanos);
+
+    let template = naming::ytdlp_template();
+    let output = Invocation::new("yt-dlp")
+        .args([
+            "--extract-audio",
+            "--audio-format", "mp3",
+            "--write-info-json",
+            "-o", &template,
+            "--print-to-file", "after_move:filepath", &printed,
+            // Keep .part files so an interrupted download resumes on the next attempt
+            "--continue",
+            "--part",
+        ])
+        .args(policy.ytdlp_args())
+        .args(keep_video_args())
+        // The link is never read as an option, even when it starts with a dash
+        .args(["--", link])
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    if output.success {
+        ratelimit::succeeded(link);
+    }
+
+    let printed = storage().path(&printed);
+    let clips = storage()
+        .read_to_string(&printed)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| Path::new(line.trim()).file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+    storage().remove_file(&printed).ok();
+
+    Ok(Download { output, clips })
+}
+
+// Transcribe a single clip into output/transcripts (text plus whisper sidecars)
+pub fn transcribe_clip(clip: &str) -> Result<ToolOutput, String> {
+    let plan = vram::prepare(&settings::load().whisper_model)?;
+    transcribe_clip_to(clip, &plan, "output/transcripts")
+}
+
+// Transcribe a single clip as planned into `output_dir`, relative to the pipeline directory
+pub fn transcribe_clip_to(clip: &str, plan: &GpuPlan, output_dir: &str) -> Result<ToolOutput, String> {
+    // The job shows which clips were transcribed with a smaller model than chosen
+    if let (Some(note), Some(_), Some(job)) = (&plan.note, &plan.replaced, jobs::current()) {
+        jobs::item_note(&job, clip, note);
+    }
+    transcribe_file(&clips::media_arg(metadata::stem(clip), "mp3"), plan, output_dir)
+}
+
+// Transcribe any audio file; both paths are relative to the pipeline directory
+pub fn transcribe_file(audio: &str, plan: &GpuPlan, output_dir: &str) -> Result<ToolOutput, String> {
+    let model = plan.model.as_str();
+    let _permit = scheduler::acquire(Resource::Gpu)?;
+
+    // Prefer the resident worker when it already has this model loaded
+    if let Some(output) = whisper::transcribe(audio, plan, output_dir) {
+        return Ok(output);
+    }
+
+    Invocation::new("faster-whisper")
+        .args([
+            audio,
+            "--model", model,
+            "--output_format", "all",
+            "--output_dir", output_dir,
+        ])
+        .args(plan.compute_type.iter().flat_map(|c| ["--compute_type", c.as_str()]))
+        .args(settings::load().decoding.cli_args())
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute transcription: {}", e))
+}
@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::PIPELINE_DIR;
+
+// Marker file next to the executable that switches the app into portable mode
+pub const PORTABLE_MARKER: &str = "portable";
+
+static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+// Pipeline directory beside the executable, when the portable marker is present
+fn portable_root() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let dir = exe.parent()?;
+    dir.join(PORTABLE_MARKER).exists().then(|| dir.join("pipeline"))
+}
+
+// Directory the pipeline tools run in and under which settings, state, models and outputs live
+pub fn pipeline_dir() -> &'static Path {
+    ROOT.get_or_init(|| portable_root().unwrap_or_else(|| PathBuf::from(PIPELINE_DIR)))
+}
+
+// Point the workspace somewhere else before anything touches it; used by the integration tests.
+// False when the pipeline directory was already resolved.
+#[cfg(feature = "mock-tools")]
+pub fn set_pipeline_dir(path: PathBuf) -> bool {
+    ROOT.set(path).is_ok()
+}
+
+pub fn is_portable() -> bool {
+    pipeline_dir() != Path::new(PIPELINE_DIR)
+}
+
+// Environment for pipeline tools so model downloads and caches stay inside the portable directory
+pub fn tool_env() -> Vec<(&'static str, PathBuf)> {
+    if !is_portable() {
+        return Vec::new();
+    }
+
+    let root = pipeline_dir();
+    vec![
+        ("HF_HOME", root.join("models")),
+        ("XDG_CACHE_HOME", root.join("cache")),
+    ]
+}
+
+// Command to report whether the app runs in portable mode and where its data lives
+#[tauri::command]
+pub fn get_portable_info() -> serde_json::Value {
+    serde_json::json!({
+        "portable": is_portable(),
+        "pipeline_dir": pipeline_dir().to_string_lossy(),
+        "marker": PORTABLE_MARKER
+    })
+}
@@ -0,0 +1,205 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::dataset::{self, DatasetEntry};
+use crate::integrity;
+use crate::text;
+use crate::transcript::{self, Segment};
+
+// Word counts below which an entry is too short to be useful, and above which it stops improving
+const MIN_WORDS: usize = 20;
+const GOOD_WORDS: usize = 120;
+
+// Words that mark an entry as unsuitable regardless of its other signals
+const PROFANITY: &[&str] = &["fuck", "shit", "bitch", "bastard", "asshole", "cunt", "dick"];
+
+// Whisper artefacts produced for music, silence and unintelligible audio
+const ARTEFACTS: &[&str] = &["[music]", "[applause]", "[laughter]", "(music)", "[inaudible]", "♪"];
+
+// Individual quality signals, each between 0 (bad) and 1 (good)
+#[derive(Debug, Clone, Serialize)]
+pub struct QualitySignals {
+    pub length: f64,
+    pub repetition: f64,
+    // None when the source transcript has no whisper probabilities
+    pub confidence: Option<f64>,
+    pub language_purity: f64,
+    pub cleanliness: f64,
+    pub profanity: bool,
+}
+
+impl QualitySignals {
+    // Weighted mean of the signals; profanity halves the result
+    pub fn score(&self) -> f64 {
+        let mut weighted = vec![
+            (self.length, 0.2),
+            (self.repetition, 0.25),
+            (self.language_purity, 0.15),
+            (self.cleanliness, 0.2),
+        ];
+        if let Some(confidence) = self.confidence {
+            weighted.push((confidence, 0.2));
+        }
+
+        let total: f64 = weighted.iter().map(|(_, w)| w).sum();
+        let mut score = weighted.iter().map(|(s, w)| s * w).sum::<f64>() / total;
+        if self.profanity {
+            score *= 0.5;
+        }
+        (score.clamp(0.0, 1.0) * 1000.0).round() / 1000.0
+    }
+}
+
+fn words(content: &str) -> Vec<String> {
+    content
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn length_signal(count: usize) -> f64 {
+    if count < MIN_WORDS {
+        count as f64 / MIN_WORDS as f64 * 0.5
+    } else {
+        (0.5 + 0.5 * (count - MIN_WORDS) as f64 / (GOOD_WORDS - MIN_WORDS) as f64).min(1.0)
+    }
+}
+
+// Share of word trigrams that are not repeats; whisper loops score near zero
+fn repetition_signal(words: &[String]) -> f64 {
+    if words.len() < 3 {
+        return 1.0;
+    }
+    let trigrams: Vec<&[String]> = words.windows(3).collect();
+    let unique: HashSet<&[String]> = trigrams.iter().copied().collect();
+    unique.len() as f64 / trigrams.len() as f64
+}
+
+// Share of letters in the entry's dominant script
+fn purity_signal(content: &str) -> f64 {
+    let arabic = content.chars().filter(|c| text::is_arabic(*c)).count();
+    let latin = content.chars().filter(|c| c.is_ascii_alphabetic()).count();
+    let other = content
+        .chars()
+        .filter(|c| c.is_alphabetic() && !text::is_arabic(*c) && !c.is_ascii_alphabetic())
+        .count();
+
+    let letters = arabic + latin + other;
+    if letters == 0 {
+        return 0.0;
+    }
+    arabic.max(latin) as f64 / letters as f64
+}
+
+// Share of non-space characters that are letters, digits or ordinary punctuation, minus artefacts
+fn cleanliness_signal(content: &str) -> f64 {
+    let visible: Vec<char> = content.chars().filter(|c| !c.is_whitespace()).collect();
+    if visible.is_empty() {
+        return 0.0;
+    }
+
+    let clean = visible
+        .iter()
+        .filter(|c| c.is_alphanumeric() || ".,;:!?'\"-()".contains(**c))
+        .count();
+    let lower = content.to_lowercase();
+    let artefacts = ARTEFACTS.iter().map(|a| lower.matches(a).count()).sum::<usize>();
+    // Runs like "aaaaaaa" or tokens longer than any real word are decoding garbage
+    let garbage_tokens = content
+        .split_whitespace()
+        .filter(|t| t.chars().count() > 30 || t.chars().collect::<Vec<_>>().windows(5).any(|w| w.iter().all(|c| *c == w[0])))
+        .count();
+
+    let penalty = (artefacts + garbage_tokens) as f64 * 0.1;
+    (clean as f64 / visible.len() as f64 - penalty).max(0.0)
+}
+
+// Confidence of the segments an entry covers, or of the whole transcript for file-level entries
+fn confidence_signal(entry: &DatasetEntry, segments: Option<&Vec<Segment>>) -> Option<f64> {
+    let segments = segments?;
+    match (entry.start, entry.end) {
+        (Some(start), Some(end)) => {
+            let covered: Vec<Segment> = segments
+                .iter()
+                .filter(|s| s.end > start && s.start < end)
+                .cloned()
+                .collect();
+            transcript::confidence(&covered)
+        }
+        _ => transcript::confidence(segments),
+    }
+}
+
+pub fn signals(entry: &DatasetEntry, segments: Option<&Vec<Segment>>) -> QualitySignals {
+    let words = words(&entry.input);
+    QualitySignals {
+        length: length_signal(words.len()),
+        repetition: repetition_signal(&words),
+        confidence: confidence_signal(entry, segments),
+        language_purity: purity_signal(&entry.input),
+        cleanliness: cleanliness_signal(&entry.input),
+        profanity: words.iter().any(|w| PROFANITY.contains(&w.as_str())),
+    }
+}
+
+// Assign a quality score to every entry, loading each source transcript's segments once
+pub fn score_entries(entries: &mut [DatasetEntry], rescore: bool) {
+    let mut segments: HashMap<String, Option<Vec<Segment>>> = HashMap::new();
+    for entry in entries.iter_mut() {
+        if entry.quality.is_some() && !rescore {
+            continue;
+        }
+        let source_segments = entry.source.as_ref().and_then(|source| {
+            segments
+                .entry(source.clone())
+                .or_insert_with(|| transcript::load_segments(source))
+                .as_ref()
+        });
+        entry.quality = Some(signals(entry, source_segments).score());
+    }
+}
+
+// Command to recompute quality scores of a dataset and report their distribution in tenths
+#[tauri::command]
+pub fn score_dataset(dataset: Option<String>) -> Result<serde_json::Value, String> {
+    let path = dataset::dataset_path(dataset.as_deref())?;
+    let mut entries = dataset::load_entries(&path)?;
+    score_entries(&mut entries, true);
+    dataset::save_entries(&path, &entries)?;
+    integrity::record(&path);
+
+    let mut histogram: BTreeMap<String, usize> = BTreeMap::new();
+    for quality in entries.iter().filter_map(|e| e.quality) {
+        let bucket = ((quality * 10.0).floor() as usize).min(9);
+        *histogram
+            .entry(format!("{:.1}-{:.1}", bucket as f64 / 10.0, (bucket + 1) as f64 / 10.0))
+            .or_insert(0) += 1;
+    }
+    let mean = if entries.is_empty() {
+        0.0
+    } else {
+        entries.iter().filter_map(|e| e.quality).sum::<f64>() / entries.len() as f64
+    };
+
+    Ok(serde_json::json!({
+        "entries": entries.len(),
+        "mean": mean,
+        "histogram": histogram
+    }))
+}
+
+// Command to show the individual quality signals of one entry
+#[tauri::command]
+pub fn explain_quality(id: String, dataset: Option<String>) -> Result<serde_json::Value, String> {
+    let path = dataset::dataset_path(dataset.as_deref())?;
+    let entry = dataset::load_entries(&path)?
+        .into_iter()
+        .find(|e| e.id == id || dataset::entry_id(e.source.as_deref(), &e.input) == id)
+        .ok_or_else(|| format!("Entry not found: {}", id))?;
+
+    let segments = entry.source.as_ref().and_then(|s| transcript::load_segments(s));
+    let signals = signals(&entry, segments.as_ref());
+    Ok(serde_json::json!({ "id": id, "score": signals.score(), "signals": signals }))
+}
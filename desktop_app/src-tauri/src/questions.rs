@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::events::{self, LogLevel, Stage};
+use crate::metadata;
+use crate::speakers::{self, Turn};
+use crate::transcript::{self, Segment};
+use crate::storage::storage;
+
+// Longest answer taken after a question, in seconds
+const MAX_ANSWER_SECONDS: f64 = 180.0;
+
+// Answers shorter than this are not worth reviewing
+const MIN_ANSWER_WORDS: usize = 15;
+
+// Words that open a question in English and Arabic
+const INTERROGATIVES: &[&str] = &[
+    "what", "why", "how", "when", "where", "who", "which", "is", "are", "can", "could", "should",
+    "does", "do", "did", "may", "would", "will", "هل", "ما", "ماذا", "لماذا", "كيف", "متى", "أين", "من",
+];
+
+// Phrases lecturers use when reading out or relaying a question
+const QUESTION_CUES: &[&str] = &["the question is", "someone asked", "somebody asked", "next question", "the brother asks", "the sister asks", "السؤال"];
+
+// A detected question with the lecturer's answer, as a candidate instruction pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QaPair {
+    pub question: String,
+    pub answer: String,
+    pub start: f64,
+    pub answer_start: f64,
+    pub end: f64,
+    // Diarization labels, when diarization ran
+    pub questioner: Option<String>,
+    pub answerer: Option<String>,
+    // What made the span a question: "punctuation", "interrogative", "cue" or "speaker_change"
+    pub signals: Vec<String>,
+    #[serde(default)]
+    pub reviewed: bool,
+}
+
+pub fn questions_dir() -> PathBuf {
+    storage().path("output/questions")
+}
+
+fn questions_path(file_name: &str) -> PathBuf {
+    questions_dir().join(format!("{}.json", metadata::stem(file_name)))
+}
+
+// Stored question/answer candidates of a transcript, if extract_questions has run
+pub fn load(file_name: &str) -> Option<Vec<QaPair>> {
+    let content = storage().read_to_string(questions_path(file_name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// Speaker overlapping a segment the longest
+fn speaker_of<'a>(segment: &Segment, turns: &'a [Turn]) -> Option<&'a str> {
+    turns
+        .iter()
+        .map(|t| (t, (t.end.min(segment.end) - t.start.max(segment.start)).max(0.0)))
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(t, _)| t.speaker.as_str())
+}
+
+// Textual signals that a segment asks or relays a question
+fn text_signals(text: &str) -> Vec<String> {
+    let lower = text.trim().to_lowercase();
+    let mut signals = Vec::new();
+    if lower.ends_with('?') || lower.ends_with('؟') {
+        signals.push("punctuation".to_string());
+    }
+    let first = lower.split_whitespace().next().unwrap_or("").trim_matches(|c: char| !c.is_alphanumeric());
+    if INTERROGATIVES.contains(&first) && lower.split_whitespace().count() >= 4 {
+        signals.push("interrogative".to_string());
+    }
+    if QUESTION_CUES.iter().any(|cue| lower.contains(cue)) {
+        signals.push("cue".to_string());
+    }
+    signals
+}
+
+fn extract(segments: &[Segment], turns: Option<&[Turn]>) -> Vec<QaPair> {
+    let speakers: Vec<Option<&str>> = segments.iter().map(|s| turns.and_then(|t| speaker_of(s, t))).collect();
+
+    // The lecturer is whoever talks the longest
+    let mut talk: HashMap<&str, f64> = HashMap::new();
+    for (segment, speaker) in segments.iter().zip(&speakers) {
+        if let Some(speaker) = speaker {
+            *talk.entry(speaker).or_insert(0.0) += segment.end - segment.start;
+        }
+    }
+    let lecturer = talk.into_iter().max_by(|a, b| a.1.total_cmp(&b.1)).map(|(s, _)| s);
+
+    let is_question = |i: usize| -> Vec<String> {
+        let mut signals = text_signals(&segments[i].text);
+        let audience = matches!((speakers[i], lecturer), (Some(s), Some(l)) if s != l);
+        if audience {
+            signals.push("speaker_change".to_string());
+        }
+        // With diarization, the lecturer's own rhetorical questions need a relaying cue
+        let relayed = signals.iter().any(|s| s == "cue");
+        if lecturer.is_some() && !audience && !relayed {
+            signals.clear();
+        }
+        signals
+    };
+
+    let mut pairs = Vec::new();
+    let mut i = 0;
+    while i < segments.len() {
+        let mut signals = is_question(i);
+        if signals.is_empty() {
+            i += 1;
+            continue;
+        }
+
+        // A question runs on while the same speaker keeps asking
+        let start = i;
+        while i + 1 < segments.len() && speakers[i + 1] == speakers[start] && !is_question(i + 1).is_empty() {
+            i += 1;
+            for signal in is_question(i) {
+                if !signals.contains(&signal) {
+                    signals.push(signal);
+                }
+            }
+        }
+        let question_end = i + 1;
+
+        // The answer runs until the next question or the time limit
+        let answer_start = question_end;
+        let mut answer_end = answer_start;
+        while answer_end < segments.len()
+            && is_question(answer_end).is_empty()
+            && segments[answer_end].end - segments[answer_start].start <= MAX_ANSWER_SECONDS
+        {
+            answer_end += 1;
+        }
+
+        let join = |range: std::ops::Range<usize>| {
+            segments[range].iter().map(|s| s.text.trim()).collect::<Vec<_>>().join(" ")
+        };
+        let answer = join(answer_start..answer_end);
+        if answer.split_whitespace().count() >= MIN_ANSWER_WORDS {
+            pairs.push(QaPair {
+                question: join(start..question_end),
+                answer,
+                start: segments[start].start,
+                answer_start: segments[answer_start].start,
+                end: segments[answer_end - 1].end,
+                questioner: speakers[start].map(str::to_string),
+                answerer: speakers[answer_start].map(str::to_string),
+                signals,
+                reviewed: false,
+            });
+        }
+        i = answer_end.max(question_end);
+    }
+    pairs
+}
+
+// Command to find audience questions in a transcript and store question/answer candidates
+// in output/questions; diarization runs unless `diarization` is false and is skipped when unavailable
+#[tauri::command]
+pub fn extract_questions(app: AppHandle, file: String, diarization: Option<bool>) -> Result<Vec<QaPair>, String> {
+    let segments = transcript::load_segments(&file)
+        .ok_or_else(|| format!("No timed segments for transcript: {}", file))?;
+
+    let turns = if diarization.unwrap_or(true) { speakers::diarize(&file) } else { None };
+    if diarization.unwrap_or(true) && turns.is_none() {
+        events::log(&app, Stage::Analysis, LogLevel::Warn, &format!("Diarization unavailable for {}; using text patterns only", file));
+    }
+
+    let pairs = extract(&segments, turns.as_deref());
+
+    let path = questions_path(&file);
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&pairs)
+        .map_err(|e| format!("Failed to serialize questions: {}", e))?;
+    storage().write(&path, content).map_err(|e| format!("Failed to write questions: {}", e))?;
+
+    Ok(pairs)
+}
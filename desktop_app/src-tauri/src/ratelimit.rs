@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::jobs;
+use crate::scheduler;
+use crate::settings;
+
+// Politeness settings for downloads, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadPolicy {
+    // Seconds between downloads from the same host
+    pub delay_seconds: f64,
+    // Passed to yt-dlp as --sleep-requests: seconds between its own HTTP requests
+    pub sleep_requests: Option<f64>,
+    // Passed to yt-dlp as --sleep-interval / --max-sleep-interval before each download
+    pub sleep_interval: Option<f64>,
+    pub max_sleep_interval: Option<f64>,
+    // Passed to yt-dlp as --limit-rate, e.g. "2M"
+    pub limit_rate: Option<String>,
+    // Downloads allowed to run at once against one host
+    pub max_per_host: usize,
+    // Times a throttled download is requeued at reduced speed before it counts as failed
+    pub throttle_retries: usize,
+}
+
+impl Default for DownloadPolicy {
+    fn default() -> Self {
+        DownloadPolicy {
+            delay_seconds: 2.0,
+            sleep_requests: Some(1.0),
+            sleep_interval: None,
+            max_sleep_interval: None,
+            limit_rate: None,
+            max_per_host: 1,
+            throttle_retries: 3,
+        }
+    }
+}
+
+impl DownloadPolicy {
+    // yt-dlp arguments implementing the policy
+    pub fn ytdlp_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(seconds) = self.sleep_requests {
+            args.extend(["--sleep-requests".to_string(), seconds.to_string()]);
+        }
+        if let Some(seconds) = self.sleep_interval {
+            args.extend(["--sleep-interval".to_string(), seconds.to_string()]);
+            if let Some(max) = self.max_sleep_interval.filter(|max| *max >= seconds) {
+                args.extend(["--max-sleep-interval".to_string(), max.to_string()]);
+            }
+        }
+        if let Some(rate) = &self.limit_rate {
+            args.extend(["--limit-rate".to_string(), rate.clone()]);
+        }
+        args
+    }
+}
+
+#[derive(Default)]
+struct HostState {
+    active: usize,
+    last_start: Option<Instant>,
+    // Doubles each time the host throttles us, halves again after successful downloads
+    backoff: u32,
+}
+
+static HOSTS: Mutex<BTreeMap<String, HostState>> = Mutex::new(BTreeMap::new());
+static RELEASED: Condvar = Condvar::new();
+
+// Host part of a URL, lowercased and without a "www." prefix
+pub fn host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = host.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default().to_lowercase();
+    host.strip_prefix("www.").map(str::to_string).unwrap_or(host)
+}
+
+// Held while a download from a host runs
+pub struct HostPermit {
+    host: String,
+}
+
+impl Drop for HostPermit {
+    fn drop(&mut self) {
+        let mut hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = hosts.get_mut(&self.host) {
+            state.active -= 1;
+        }
+        RELEASED.notify_all();
+    }
+}
+
+// Wait for a free per-host slot and the politeness delay since the last download from the host;
+// fails when the waiting job is killed
+pub fn acquire(url: &str, policy: &DownloadPolicy) -> Result<HostPermit, String> {
+    let host = host(url);
+    let delay = Duration::from_secs_f64(policy.delay_seconds.max(0.0));
+    let mut hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+
+    loop {
+        let state = hosts.entry(host.clone()).or_default();
+        let wait = state.last_start.map_or(Duration::ZERO, |last| delay.saturating_sub(last.elapsed()));
+
+        if state.active < policy.max_per_host.max(1) && wait.is_zero() {
+            state.active += 1;
+            state.last_start = Some(Instant::now());
+            return Ok(HostPermit { host });
+        }
+
+        jobs::waiting()?;
+        let wait = if wait.is_zero() { scheduler::WAIT_TICK } else { wait.min(scheduler::WAIT_TICK) };
+        hosts = RELEASED.wait_timeout(hosts, wait).unwrap_or_else(|e| e.into_inner()).0;
+    }
+}
+
+// Policy currently configured in settings, slowed down for hosts that recently throttled us
+pub fn policy(url: &str) -> DownloadPolicy {
+    let mut policy = settings::load().download_policy;
+    let backoff = HOSTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&host(url))
+        .map_or(0, |state| state.backoff);
+
+    if backoff > 0 {
+        let factor = f64::from(1u32 << backoff.min(6));
+        policy.delay_seconds = policy.delay_seconds.max(1.0) * factor;
+        policy.sleep_requests = Some(policy.sleep_requests.unwrap_or(1.0) * factor);
+        policy.sleep_interval = Some(policy.sleep_interval.unwrap_or(1.0) * factor);
+        policy.max_sleep_interval = policy.max_sleep_interval.map(|max| max * factor);
+        policy.max_per_host = 1;
+    }
+    policy
+}
+
+// Whether yt-dlp output shows the host rate limiting us
+pub fn is_throttled(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    ["http error 429", "too many requests", "throttl", "rate-limit", "rate limit", "confirm you're not a bot", "confirm you’re not a bot"]
+        .iter()
+        .any(|marker| stderr.contains(marker))
+}
+
+// Slow down future downloads from the host; returns the new backoff level
+pub fn throttled(url: &str) -> u32 {
+    let mut hosts = HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+    let state = hosts.entry(host(url)).or_default();
+    state.backoff += 1;
+    state.backoff
+}
+
+// Relax the backoff after a download from the host went through
+pub fn succeeded(url: &str) {
+    if let Some(state) = HOSTS.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&host(url)) {
+        state.backoff /= 2;
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::audit;
+use crate::dataset::{self, DatasetEntry, DEFAULT_DATASET};
+use crate::manifest;
+use crate::settings;
+use crate::storage::storage;
+
+// Outcome of one checklist step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    // Blocks the release
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl Check {
+    fn new(name: &str, status: CheckStatus, message: String) -> Self {
+        Check { name: name.to_string(), status, message }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseReport {
+    pub version: String,
+    pub dataset: String,
+    pub checks: Vec<Check>,
+    // Release directory; None when a check failed and nothing was bundled
+    pub path: Option<String>,
+    pub stats: serde_json::Value,
+}
+
+// Entries missing a field every trainer needs
+fn validate(entries: &[DatasetEntry]) -> Check {
+    let invalid = entries
+        .iter()
+        .filter(|e| e.instruction.trim().is_empty() || e.input.trim().is_empty() || e.output.trim().is_empty())
+        .count();
+    if invalid == 0 {
+        Check::new("validation", CheckStatus::Pass, format!("{} entries are complete", entries.len()))
+    } else {
+        Check::new("validation", CheckStatus::Fail, format!("{} entries have an empty instruction, input or output", invalid))
+    }
+}
+
+// Drop repeated entries, keeping the first of each id
+fn dedup(entries: &mut Vec<DatasetEntry>) -> Check {
+    let before = entries.len();
+    let mut seen = BTreeSet::new();
+    entries.retain(|e| seen.insert(e.id.clone()));
+    let removed = before - entries.len();
+    if removed == 0 {
+        Check::new("dedup", CheckStatus::Pass, "No duplicate entries".to_string())
+    } else {
+        Check::new("dedup", CheckStatus::Warn, format!("Removed {} duplicate entries", removed))
+    }
+}
+
+// Unknown licenses warn; licenses outside a non-empty allow list fail
+fn check_licenses(entries: &[DatasetEntry], allowed: &[String]) -> Check {
+    let mut unknown = 0;
+    let mut disallowed: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in entries {
+        match entry.provenance.as_ref().and_then(|p| p.license.as_deref()) {
+            None => unknown += 1,
+            Some(license) if !allowed.is_empty() && !allowed.iter().any(|a| a.eq_ignore_ascii_case(license)) => {
+                *disallowed.entry(license.to_string()).or_insert(0) += 1;
+            }
+            Some(_) => {}
+        }
+    }
+
+    if !disallowed.is_empty() {
+        let listed: Vec<String> = disallowed.iter().map(|(l, n)| format!("{} ({})", l, n)).collect();
+        Check::new("license", CheckStatus::Fail, format!("Licenses not allowed: {}", listed.join(", ")))
+    } else if unknown > 0 {
+        Check::new("license", CheckStatus::Warn, format!("{} entries have no known license", unknown))
+    } else {
+        Check::new("license", CheckStatus::Pass, "Every entry has an allowed license".to_string())
+    }
+}
+
+// Files of the bundle that contain a credential from the settings
+fn leaked_secrets(release_dir: &std::path::Path) -> Vec<String> {
+    let secrets = settings::load().secrets();
+    let Ok(files) = storage().read_dir(release_dir) else { return Vec::new() };
+    files
+        .filter_map(Result::ok)
+        .filter(|file| {
+            let content = storage().read_to_string(file.path()).unwrap_or_default();
+            secrets.iter().any(|secret| content.contains(secret.as_str()))
+        })
+        .map(|file| file.file_name().to_string_lossy().to_string())
+        .collect()
+}
+
+pub fn stats(entries: &[DatasetEntry]) -> serde_json::Value {
+    let count = |key: fn(&DatasetEntry) -> Option<String>| {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for entry in entries {
+            *counts.entry(key(entry).unwrap_or_else(|| "unknown".to_string())).or_insert(0) += 1;
+        }
+        counts
+    };
+    let scored: Vec<f64> = entries.iter().filter_map(|e| e.quality).collect();
+    let characters: usize = entries.iter().map(|e| e.instruction.len() + e.input.len() + e.output.len()).sum();
+
+    serde_json::json!({
+        "entries": entries.len(),
+        "sources": count(|e| e.source.clone()).len(),
+        "channels": count(|e| Some(e.channel_key())),
+        "languages": count(|e| e.language.clone()),
+        "mean_quality": (!scored.is_empty()).then(|| scored.iter().sum::<f64>() / scored.len() as f64),
+        "characters": characters
+    })
+}
+
+// Command to run the publishing checklist and bundle the dataset into output/releases/<dataset>-<version>
+#[tauri::command]
+pub fn prepare_release(
+    version: String,
+    dataset: Option<String>,
+    allowed_licenses: Option<Vec<String>>,
+) -> Result<ReleaseReport, String> {
+    dataset::validate_name(&version)?;
+    let id = dataset.clone().unwrap_or_else(|| DEFAULT_DATASET.to_string());
+    let release_dir = storage().path("output/releases").join(format!("{}-{}", id, version));
+    if storage().exists(&release_dir) {
+        return Err(format!("Release {} of {} already exists", version, id));
+    }
+
+    let source = dataset::dataset_path(dataset.as_deref())?;
+    if !storage().exists(&source) {
+        return Err("JSON file not found".to_string());
+    }
+    let mut entries = dataset::load_entries(&source)?;
+    for entry in entries.iter_mut() {
+        dataset::annotate(entry);
+        dataset::attach_provenance(entry);
+    }
+
+    let mut checks = vec![validate(&entries), dedup(&mut entries)];
+    let stats = stats(&entries);
+    checks.push(Check::new("stats", CheckStatus::Pass, format!("{} entries", entries.len())));
+    let (attribution, sources) = dataset::attribution(&entries);
+    checks.push(Check::new("attribution", CheckStatus::Pass, format!("{} sources credited", sources.len())));
+    checks.push(check_licenses(&entries, &allowed_licenses.unwrap_or_default()));
+
+    let mut report = ReleaseReport { version: version.clone(), dataset: id.clone(), checks, path: None, stats };
+    if report.checks.iter().any(|c| c.status == CheckStatus::Fail) {
+        return Ok(report);
+    }
+
+    storage().create_dir_all(&release_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let data = release_dir.join("dataset.json");
+    dataset::save_entries(&data, &entries)?;
+    let write = |name: &str, content: String| {
+        storage().write(release_dir.join(name), content).map_err(|e| format!("Failed to write {}: {}", name, e))
+    };
+    write("ATTRIBUTION.md", attribution)?;
+    write("stats.json", serde_json::to_string_pretty(&report.stats).unwrap_or_default())?;
+    write("checklist.json", serde_json::to_string_pretty(&report.checks).unwrap_or_default())?;
+
+    let parameters = serde_json::json!({ "version": version, "checks": report.checks });
+    manifest::record("release", &id, &data, entries.len(), parameters)?;
+
+    // A release is meant to be published, so it must never carry a credential
+    let leaked = leaked_secrets(&release_dir);
+    if !leaked.is_empty() {
+        storage().remove_dir_all(&release_dir).ok();
+        return Err(format!("Release not bundled: credentials found in {}", leaked.join(", ")));
+    }
+    audit::record("dataset.release", &id, serde_json::json!({ "version": version }));
+
+    report.path = Some(release_dir.to_string_lossy().to_string());
+    Ok(report)
+}
@@ -0,0 +1,99 @@
+use crate::clips;
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::scheduler::{self, Resource};
+use crate::state;
+use crate::subtitles;
+use crate::transcript::{self, Segment};
+use crate::video;
+use crate::storage::storage;
+
+// Length of a rendered excerpt when no end is given, in seconds
+const DEFAULT_EXCERPT_SECONDS: f64 = 60.0;
+
+// Frame size of waveform renders, a common social-media format
+const WAVEFORM_SIZE: &str = "1280x720";
+
+// Segments overlapping the excerpt, shifted so the excerpt starts at zero
+pub fn excerpt_segments(segments: Vec<Segment>, start: f64, end: f64) -> Vec<Segment> {
+    segments
+        .into_iter()
+        .filter(|s| s.end > start && s.start < end)
+        .map(|mut s| {
+            s.start = (s.start - start).max(0.0);
+            s.end = (s.end.min(end) - start).max(s.start);
+            s
+        })
+        .collect()
+}
+
+// Command to burn the reviewed captions of an approved transcript into a short video, using
+// the kept video when there is one and an audio waveform otherwise; written to output/renders
+#[tauri::command]
+pub fn render_subtitled_clip(file: String, start: Option<f64>, end: Option<f64>) -> Result<String, String> {
+    let status = transcript::review_status(&state::load()?, &file);
+    if status != "approved" {
+        return Err(format!("Transcript {} is {}; approve it before rendering", file, status));
+    }
+
+    let start = start.unwrap_or(0.0).max(0.0);
+    let end = end.unwrap_or(start + DEFAULT_EXCERPT_SECONDS);
+    if end <= start {
+        return Err(format!("Invalid excerpt range: {} to {}", start, end));
+    }
+
+    let stem = metadata::stem(&file).to_string();
+    let audio = clips::media_arg(&stem, "mp3");
+    let video = video::video_path(&file);
+    if video.is_none() && !storage().exists(storage().path(&audio)) {
+        return Err(format!("Clip not found for transcript: {}", file));
+    }
+
+    let renders = storage().path("output/renders");
+    storage().create_dir_all(&renders).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    // Relative paths keep the subtitles filter argument free of drive letters and other escapes
+    let captions = format!("output/renders/.{}.srt", stem);
+    let segments = excerpt_segments(subtitles::reviewed_segments(&file)?, start, end);
+    storage().write(storage().path(&captions), subtitles::render(&segments, "srt"))
+        .map_err(|e| format!("Failed to write subtitles: {}", e))?;
+    let subtitles_filter = format!("subtitles='{}'", captions.replace('\'', r"\'"));
+
+    let output_name = format!("{}_{}-{}.mp4", stem, start as u64, end as u64);
+    let output_path = renders.join(&output_name);
+
+    let mut invocation = Invocation::new("ffmpeg")
+        .args(["-y", "-hide_banner", "-loglevel", "error"])
+        .args(["-ss", &start.to_string(), "-t", &(end - start).to_string()]);
+    invocation = match &video {
+        Some(video) => invocation
+            .arg("-i")
+            .arg(video)
+            .args(["-vf", &subtitles_filter]),
+        None => invocation.args([
+            "-i",
+            &audio,
+            "-filter_complex",
+            &format!("[0:a]showwaves=s={}:mode=cline:colors=white,{}[v]", WAVEFORM_SIZE, subtitles_filter),
+            "-map",
+            "[v]",
+            "-map",
+            "0:a",
+        ]),
+    };
+
+    let _permit = scheduler::acquire(Resource::Cpu)?;
+    let output = invocation
+        .args(["-c:v", "libx264", "-pix_fmt", "yuv420p", "-c:a", "aac", "-shortest"])
+        .arg(&output_path)
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e));
+    storage().remove_file(storage().path(&captions)).ok();
+
+    let output = output?;
+    if !output.success {
+        return Err(format!("Rendering failed: {}", output.stderr.trim()));
+    }
+    Ok(output_path.to_string_lossy().to_string())
+}
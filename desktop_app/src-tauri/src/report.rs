@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::dataset;
+use crate::metadata;
+use crate::telemetry;
+use crate::text;
+use crate::transcript;
+use crate::storage::storage;
+
+// Contribution of a single channel to the corpus
+#[derive(Debug, Default, Serialize)]
+pub struct ChannelStats {
+    pub transcripts: usize,
+    pub words: usize,
+    pub audio_hours: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CorpusReport {
+    pub transcripts: usize,
+    pub total_words: usize,
+    pub audio_hours: f64,
+    pub words_per_language: BTreeMap<String, usize>,
+    pub channels: BTreeMap<String, ChannelStats>,
+    // None when no transcript carries whisper segment probabilities
+    pub average_confidence: Option<f64>,
+    pub dataset_entries: usize,
+    // Share of transcripts that produced at least one dataset entry
+    pub dataset_coverage: f64,
+}
+
+pub fn build_report() -> Result<CorpusReport, String> {
+    let mut report = CorpusReport::default();
+    let mut confidences = Vec::new();
+
+    for name in transcript::list_transcripts() {
+        let content = match storage().read_to_string(transcript::transcript_path(&name)) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+
+        let words = content.split_whitespace().count();
+        let meta = metadata::load(&name).unwrap_or_default();
+        let hours = meta.duration.unwrap_or(0.0) / 3600.0;
+
+        report.transcripts += 1;
+        report.total_words += words;
+        report.audio_hours += hours;
+        *report
+            .words_per_language
+            .entry(text::detect_language(&content).to_string())
+            .or_insert(0) += words;
+
+        let channel = report
+            .channels
+            .entry(meta.channel_name().unwrap_or("unknown").to_string())
+            .or_default();
+        channel.transcripts += 1;
+        channel.words += words;
+        channel.audio_hours += hours;
+
+        if let Some(confidence) = transcript::load_segments(&name).and_then(|s| transcript::confidence(&s)) {
+            confidences.push(confidence);
+        }
+    }
+
+    if !confidences.is_empty() {
+        report.average_confidence = Some(confidences.iter().sum::<f64>() / confidences.len() as f64);
+    }
+
+    let path = dataset::dataset_path(None)?;
+    if storage().exists(&path) {
+        let entries = dataset::load_entries(&path)?;
+        let covered: BTreeSet<&str> = entries.iter().filter_map(|e| e.source.as_deref()).collect();
+        report.dataset_entries = entries.len();
+        if report.transcripts > 0 {
+            report.dataset_coverage = covered.len() as f64 / report.transcripts as f64;
+        }
+    }
+
+    Ok(report)
+}
+
+fn to_markdown(report: &CorpusReport) -> String {
+    let mut md = String::from("# Corpus Report\n\n");
+    md.push_str(&format!("- Transcripts: {}\n", report.transcripts));
+    md.push_str(&format!("- Audio hours: {:.2}\n", report.audio_hours));
+    md.push_str(&format!("- Total words: {}\n", report.total_words));
+    md.push_str(&format!(
+        "- Average confidence: {}\n",
+        report.average_confidence.map_or("n/a".to_string(), |c| format!("{:.3}", c))
+    ));
+    md.push_str(&format!("- Dataset entries: {}\n", report.dataset_entries));
+    md.push_str(&format!("- Dataset coverage: {:.1}%\n", report.dataset_coverage * 100.0));
+
+    md.push_str("\n## Words per language\n\n| Language | Words |\n|---|---|\n");
+    for (language, words) in &report.words_per_language {
+        md.push_str(&format!("| {} | {} |\n", language, words));
+    }
+
+    md.push_str("\n## Channels\n\n| Channel | Transcripts | Words | Audio hours |\n|---|---|---|---|\n");
+    for (channel, stats) in &report.channels {
+        md.push_str(&format!(
+            "| {} | {} | {} | {:.2} |\n",
+            channel, stats.transcripts, stats.words, stats.audio_hours
+        ));
+    }
+
+    md
+}
+
+// Command to summarize the corpus, optionally writing it to output/reports as json or markdown
+#[tauri::command]
+pub fn get_corpus_report(format: Option<String>) -> Result<serde_json::Value, String> {
+    telemetry::record_usage("corpus_report");
+    let report = build_report()?;
+    let value = serde_json::to_value(&report)
+        .map_err(|e| format!("Failed to serialize report: {}", e))?;
+
+    let content = match format.as_deref() {
+        None => return Ok(value),
+        Some("json") => serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize report: {}", e))?,
+        Some("markdown") => to_markdown(&report),
+        Some(other) => return Err(format!("Unknown report format: {}", other)),
+    };
+
+    let reports_dir = storage().path("output/reports");
+    storage().create_dir_all(&reports_dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let extension = if format.as_deref() == Some("json") { "json" } else { "md" };
+    let target = reports_dir.join(format!("corpus_report.{}", extension));
+    storage().write(&target, content).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    Ok(serde_json::json!({
+        "path": target.to_string_lossy(),
+        "report": value
+    }))
+}
@@ -0,0 +1,587 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use utoipa::ToSchema;
+
+use crate::api_tokens;
+use crate::audit;
+use crate::dataset;
+use crate::http::{self, Request, Response};
+use crate::mcp;
+use crate::openapi;
+use crate::settings;
+use crate::state;
+use crate::transcript::{self, SaveError};
+
+// Name edits made in the desktop app itself are attributed to
+pub const HOST_USER: &str = "host";
+
+// Attribution entries kept in the state store; older ones are dropped
+const MAX_EDIT_RECORDS: usize = 5000;
+
+// What a connected user may do, each role including the ones below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    // Read transcripts and attribution
+    Viewer,
+    // Also claim, edit and approve transcripts
+    Reviewer,
+    // Also change settings and reset data
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Reviewer => "reviewer",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+// A named user with their own token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Member {
+    pub name: String,
+    pub token: String,
+    pub role: Role,
+}
+
+// LAN review server settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollaborationSettings {
+    // Address the review server listens on when started
+    pub bind: String,
+    // Required to listen on anything but a loopback address
+    pub allow_lan: bool,
+    // Shared secret sent as "Authorization: Bearer <token>"; its users name themselves via X-Reviewer
+    pub token: Option<String>,
+    // Role granted to holders of the shared token
+    pub default_role: Role,
+    // Users with their own tokens and roles
+    pub members: Vec<Member>,
+    // Claims expire after this long so abandoned reviews are freed
+    pub claim_hours: u64,
+}
+
+impl Default for CollaborationSettings {
+    fn default() -> Self {
+        CollaborationSettings {
+            bind: "127.0.0.1:8765".to_string(),
+            allow_lan: false,
+            token: None,
+            default_role: Role::Reviewer,
+            members: Vec::new(),
+            claim_hours: 2,
+        }
+    }
+}
+
+impl CollaborationSettings {
+    // Check that every token identifies exactly one user
+    pub fn validate(&self) -> Result<(), String> {
+        let mut tokens: Vec<&str> = self.token.iter().map(String::as_str).filter(|t| !t.is_empty()).collect();
+        for member in &self.members {
+            if member.name.trim().is_empty() || member.name == HOST_USER {
+                return Err(format!("Invalid member name: {:?}", member.name));
+            }
+            if member.token.is_empty() {
+                return Err(format!("Member {} has no token", member.name));
+            }
+            tokens.push(&member.token);
+        }
+        tokens.sort_unstable();
+        if tokens.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err("Collaboration tokens must be unique".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn has_tokens(&self) -> bool {
+        self.token.as_deref().is_some_and(|t| !t.is_empty()) || !self.members.is_empty() || api_tokens::any()
+    }
+}
+
+// The authenticated user behind a request, as returned by GET /me
+#[derive(Serialize, ToSchema)]
+pub struct Caller {
+    // Members are named by their token; shared-token users by the X-Reviewer header
+    name: Option<String>,
+    role: Role,
+}
+
+impl Caller {
+    fn name(&self) -> Result<String, Response> {
+        self.name.clone().ok_or_else(|| Response::error(400, "X-Reviewer header is required"))
+    }
+}
+
+// A reviewer's claim on a transcript
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Claim {
+    pub user: String,
+    pub claimed_at: u64,
+}
+
+// Who changed what, kept per transcript
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EditRecord {
+    pub file_name: String,
+    pub user: String,
+    // "edit", "status:<status>", "claim" or "release"
+    pub action: String,
+    pub revision: Option<String>,
+    pub at: u64,
+}
+
+static SERVER: Mutex<Option<http::Server>> = Mutex::new(None);
+
+// Record an action on a transcript in the attribution log
+pub fn attribute(file_name: &str, user: &str, action: &str, revision: Option<&str>) {
+    let record = EditRecord {
+        file_name: file_name.to_string(),
+        user: user.to_string(),
+        action: action.to_string(),
+        revision: revision.map(str::to_string),
+        at: state::now(),
+    };
+    state::update(|s| {
+        s.edit_log.push(record);
+        let excess = s.edit_log.len().saturating_sub(MAX_EDIT_RECORDS);
+        s.edit_log.drain(..excess);
+    })
+    .ok();
+}
+
+// Current claim on a transcript, ignoring expired ones
+fn active_claim(claims: &BTreeMap<String, Claim>, file_name: &str) -> Option<Claim> {
+    let ttl = settings::load().collaboration.claim_hours * 3600;
+    claims
+        .get(file_name)
+        .filter(|c| state::now().saturating_sub(c.claimed_at) < ttl)
+        .cloned()
+}
+
+// Name and role behind a bearer token; `reviewer` names users of the shared token.
+// Shared by every server that accepts collaboration or API tokens.
+pub fn identify(token: Option<&str>, reviewer: Option<&str>, config: &CollaborationSettings) -> Option<(Option<String>, Role)> {
+    let token = token.filter(|t| !t.is_empty())?;
+
+    if let Some((name, role)) = api_tokens::authenticate(token) {
+        return Some((Some(name), role));
+    }
+    if let Some(member) = config.members.iter().find(|m| http::secret_eq(&m.token, token)) {
+        return Some((Some(member.name.clone()), member.role));
+    }
+    if !config.token.as_deref().is_some_and(|t| http::secret_eq(t, token)) {
+        return None;
+    }
+
+    let name = reviewer
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty() && u != HOST_USER && !config.members.iter().any(|m| &m.name == u));
+    Some((name, config.default_role))
+}
+
+// Identify the caller by their bearer token
+fn authenticate(request: &Request, config: &CollaborationSettings) -> Result<Caller, Response> {
+    let reviewer = request.headers.get("x-reviewer").map(String::as_str);
+    let (name, role) = identify(request.bearer(), reviewer, config)
+        .ok_or_else(|| Response::error(401, "Invalid or missing token"))?;
+    Ok(Caller { name, role })
+}
+
+// Reviewer holding an unexpired claim on a transcript
+pub fn claimed_by(file_name: &str) -> Option<String> {
+    active_claim(&state::load().ok()?.review_claims, file_name).map(|c| c.user)
+}
+
+// Role each route requires; None for unknown routes
+pub fn required_role(method: &str, segments: &[&str]) -> Option<Role> {
+    match (method, segments) {
+        ("GET", ["transcripts"])
+        | ("GET", ["transcripts", _])
+        | ("GET", ["edits"])
+        | ("GET", ["me"])
+        | ("POST", ["mcp"]) => Some(Role::Viewer),
+        ("PUT", ["transcripts", _])
+        | ("PUT", ["transcripts", _, "status"])
+        | ("POST", ["transcripts", _, "claim"])
+        | ("DELETE", ["transcripts", _, "claim"]) => Some(Role::Reviewer),
+        ("GET", ["settings"]) | ("PUT", ["settings"]) | ("POST", ["reset"]) => Some(Role::Admin),
+        _ => None,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/transcripts/{file}/claim",
+    params(("file" = String, Path, description = "Transcript file name")),
+    responses(
+        (status = 200, description = "The caller's claim", body = Claim),
+        (status = 409, description = "Claimed by someone else; the error and their claim", body = Object),
+    )
+)]
+fn claim(file_name: &str, user: &str) -> Response {
+    let claimed = state::update(|s| {
+        match active_claim(&s.review_claims, file_name) {
+            Some(existing) if existing.user != user => Err(existing),
+            _ => {
+                let claim = Claim { user: user.to_string(), claimed_at: state::now() };
+                s.review_claims.insert(file_name.to_string(), claim.clone());
+                Ok(claim)
+            }
+        }
+    });
+    match claimed {
+        Ok(Ok(claim)) => {
+            attribute(file_name, user, "claim", None);
+            Response::json(200, &claim)
+        }
+        Ok(Err(existing)) => Response::json(409, &serde_json::json!({ "error": "Transcript is claimed", "claim": existing })),
+        Err(e) => Response::error(500, &e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/transcripts/{file}/claim",
+    params(("file" = String, Path, description = "Transcript file name")),
+    responses(
+        (status = 200, description = "Claim released", body = Released),
+        (status = 403, description = "Claimed by another reviewer", body = http::ErrorBody),
+    )
+)]
+fn release(file_name: &str, user: &str) -> Response {
+    let released = state::update(|s| match active_claim(&s.review_claims, file_name) {
+        Some(existing) if existing.user != user => false,
+        _ => {
+            s.review_claims.remove(file_name);
+            true
+        }
+    });
+    match released {
+        Ok(true) => {
+            attribute(file_name, user, "release", None);
+            Response::json(200, &Released { released: true })
+        }
+        Ok(false) => Response::error(403, "Transcript is claimed by another reviewer"),
+        Err(e) => Response::error(500, &e),
+    }
+}
+
+// Only the reviewer holding the claim may change a transcript
+fn require_claim(file_name: &str, user: &str) -> Result<(), Response> {
+    match claimed_by(file_name) {
+        Some(holder) if holder == user => Ok(()),
+        Some(holder) => Err(Response::error(409, &format!("Transcript is claimed by {}", holder))),
+        None => Err(Response::error(409, "Claim the transcript before editing it")),
+    }
+}
+
+// Request and response bodies of the routes below; openapi.rs documents the API from them
+
+#[derive(Serialize, ToSchema)]
+pub struct TranscriptItem {
+    pub file_name: String,
+    pub review_status: String,
+    pub claim: Option<Claim>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TranscriptView {
+    pub file_name: String,
+    pub content: String,
+    pub revision: String,
+    // Unsaved draft differing from the content, if any
+    #[schema(value_type = Option<Object>)]
+    pub draft: Option<serde_json::Value>,
+    #[serde(default)]
+    pub claim: Option<Claim>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SaveBody {
+    pub content: String,
+    // Revision the edit is based on, from GET /transcripts/{file}; required, and a stale one
+    // is answered with 409 and a merge payload
+    pub revision: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct StatusBody {
+    pub status: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RevisionBody {
+    pub revision: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Released {
+    pub released: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Saved {
+    pub saved: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ResetDone {
+    pub reset: bool,
+}
+
+#[utoipa::path(get, path = "/me", responses((status = 200, description = "The authenticated caller", body = Caller)))]
+fn me(caller: &Caller) -> Response {
+    Response::json(200, caller)
+}
+
+#[utoipa::path(
+    get,
+    path = "/transcripts",
+    responses((status = 200, description = "Transcripts with review status and claims", body = [TranscriptItem]))
+)]
+fn list() -> Response {
+    let state = match state::load() {
+        Ok(state) => state,
+        Err(e) => return Response::error(500, &e),
+    };
+    let transcripts: Vec<TranscriptItem> = transcript::list_transcripts()
+        .into_iter()
+        .map(|name| TranscriptItem {
+            review_status: transcript::review_status(&state, &name),
+            claim: active_claim(&state.review_claims, &name),
+            file_name: name,
+        })
+        .collect();
+    Response::json(200, &transcripts)
+}
+
+#[utoipa::path(
+    get,
+    path = "/transcripts/{file}",
+    params(("file" = String, Path, description = "Transcript file name")),
+    responses(
+        (status = 200, description = "A transcript with its revision and claim", body = TranscriptView),
+        (status = 404, description = "No such transcript", body = http::ErrorBody),
+    )
+)]
+fn read(file: &str) -> Result<Response, Response> {
+    let value = crate::get_transcript(file.to_string()).map_err(|e| Response::error(404, &e))?;
+    let mut view: TranscriptView = serde_json::from_value(value).map_err(|e| Response::error(500, &e.to_string()))?;
+    view.claim = active_claim(&state::load().map_err(|e| Response::error(500, &e))?.review_claims, file);
+    Ok(Response::json(200, &view))
+}
+
+#[utoipa::path(
+    put,
+    path = "/transcripts/{file}",
+    params(("file" = String, Path, description = "Transcript file name")),
+    request_body = SaveBody,
+    responses(
+        (status = 200, description = "Saved; the new revision", body = RevisionBody),
+        (status = 400, description = "No revision given", body = Object),
+        (status = 404, description = "No such transcript", body = Object),
+        (status = 409, description = "Claimed by someone else, or a stale revision with a merge payload", body = Object),
+    )
+)]
+fn save(request: &Request, file: &str, caller: &Caller) -> Result<Response, Response> {
+    let user = caller.name()?;
+    require_claim(file, &user)?;
+    let body: SaveBody = request.json()?;
+    match crate::save_transcript(file.to_string(), body.content, body.revision, &user) {
+        Ok(revision) => Ok(Response::json(200, &RevisionBody { revision })),
+        Err(error @ SaveError::Conflict { .. }) => Ok(Response::json(409, &error)),
+        Err(error @ SaveError::NotFound { .. }) => Ok(Response::json(404, &error)),
+        Err(error @ SaveError::RevisionRequired { .. }) => Ok(Response::json(400, &error)),
+        Err(error) => Ok(Response::json(500, &error)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/transcripts/{file}/status",
+    params(("file" = String, Path, description = "Transcript file name")),
+    request_body = StatusBody,
+    responses(
+        (status = 200, description = "The review status now set", body = StatusBody),
+        (status = 400, description = "Unknown status", body = http::ErrorBody),
+        (status = 409, description = "Not claimed by the caller", body = http::ErrorBody),
+    )
+)]
+fn set_status(request: &Request, file: &str, caller: &Caller) -> Result<Response, Response> {
+    let user = caller.name()?;
+    require_claim(file, &user)?;
+    let body: StatusBody = request.json()?;
+    transcript::set_review_status(file.to_string(), body.status.clone()).map_err(|e| Response::error(400, &e))?;
+    attribute(file, &user, &format!("status:{}", body.status), None);
+    Ok(Response::json(200, &body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/edits",
+    params(("file" = Option<String>, Query, description = "Only edits of this transcript")),
+    responses((status = 200, description = "Edit attribution, newest first", body = [EditRecord]))
+)]
+fn edits(request: &Request) -> Response {
+    Response::json(200, &get_edit_attribution(request.query.get("file").cloned()))
+}
+
+// Credentials are redacted; sending them back unchanged keeps the stored ones
+#[utoipa::path(get, path = "/settings", responses((status = 200, description = "The workspace settings, credentials redacted", body = Object)))]
+fn read_settings() -> Response {
+    Response::json(200, &settings::load().redacted())
+}
+
+#[utoipa::path(
+    put,
+    path = "/settings",
+    request_body = Object,
+    responses(
+        (status = 200, description = "Settings replaced", body = Saved),
+        (status = 400, description = "Invalid settings", body = http::ErrorBody),
+    )
+)]
+fn write_settings(request: &Request) -> Result<Response, Response> {
+    let mut settings: settings::Settings = request.json()?;
+    settings.restore_secrets(&settings::load());
+    settings::update_settings(settings).map_err(|e| Response::error(400, &e))?;
+    Ok(Response::json(200, &Saved { saved: true }))
+}
+
+#[utoipa::path(post, path = "/reset", responses((status = 200, description = "All pipeline outputs deleted", body = ResetDone)))]
+fn reset() -> Result<Response, Response> {
+    crate::reset_data().map_err(|e| Response::error(500, &e))?;
+    Ok(Response::json(200, &ResetDone { reset: true }))
+}
+
+// Tools for local AI assistants; each tool checks the caller's role itself
+#[utoipa::path(
+    post,
+    path = "/mcp",
+    request_body(content = Object, description = "JSON-RPC 2.0 request"),
+    responses((status = 200, description = "Model Context Protocol endpoint (JSON-RPC 2.0)", body = Object))
+)]
+fn assistant(app: &AppHandle, request: &Request, caller: &Caller) -> Response {
+    mcp::handle(app, caller.role, &request.body)
+}
+
+fn route(app: &AppHandle, request: &Request) -> Result<Response, Response> {
+    // The schema is public so clients can be generated before a token is issued
+    if request.method == "GET" && request.segments == ["openapi.json"] {
+        return Ok(Response::json(200, &openapi::spec()));
+    }
+
+    let caller = authenticate(request, &settings::load().collaboration)?;
+    let actor = caller.name.clone().unwrap_or_else(|| "anonymous".to_string());
+    audit::as_user(&actor, || dispatch(app, request, &caller))
+}
+
+fn dispatch(app: &AppHandle, request: &Request, caller: &Caller) -> Result<Response, Response> {
+    let segments: Vec<&str> = request.segments.iter().map(String::as_str).collect();
+    let required = required_role(&request.method, &segments).ok_or_else(|| Response::error(404, "Not found"))?;
+    if caller.role < required {
+        return Err(Response::error(403, &format!("Requires the {} role", required.as_str())));
+    }
+    if let ["transcripts", file, ..] = segments.as_slice() {
+        dataset::validate_name(file).map_err(|e| Response::error(400, &e))?;
+    }
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["mcp"]) => Ok(assistant(app, request, caller)),
+        ("GET", ["me"]) => Ok(me(caller)),
+        ("GET", ["transcripts"]) => Ok(list()),
+        ("GET", ["transcripts", file]) => read(file),
+        ("PUT", ["transcripts", file]) => save(request, file, caller),
+        ("PUT", ["transcripts", file, "status"]) => set_status(request, file, caller),
+        ("POST", ["transcripts", file, "claim"]) => Ok(claim(file, &caller.name()?)),
+        ("DELETE", ["transcripts", file, "claim"]) => Ok(release(file, &caller.name()?)),
+        ("GET", ["edits"]) => Ok(edits(request)),
+        ("GET", ["settings"]) => Ok(read_settings()),
+        ("PUT", ["settings"]) => write_settings(request),
+        ("POST", ["reset"]) => reset(),
+        _ => Err(Response::error(404, "Not found")),
+    }
+}
+
+fn handle(app: &AppHandle, request: &Request) -> Response {
+    route(app, request).unwrap_or_else(|response| response)
+}
+
+// Loopback-only unless LAN exposure was opted into
+pub fn check_bind(bind: &str, allow_lan: bool) -> Result<(), String> {
+    let loopback = match bind.parse::<std::net::SocketAddr>() {
+        Ok(address) => address.ip().is_loopback(),
+        Err(_) => bind.rsplit_once(':').is_some_and(|(host, _)| host == "localhost"),
+    };
+    if loopback || allow_lan {
+        Ok(())
+    } else {
+        Err(format!("Refusing to listen on {}: enable allow_lan in its settings to expose the server", bind))
+    }
+}
+
+// Command to host the workspace for reviewers on the local network
+#[tauri::command]
+pub fn start_review_server(app: AppHandle, bind: Option<String>) -> Result<serde_json::Value, String> {
+    let config = settings::load().collaboration;
+    if !config.has_tokens() {
+        return Err("Create an API token, set a collaboration token or add members before starting the review server".to_string());
+    }
+    config.validate()?;
+
+    let mut server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(running) = server.as_ref().filter(|s| s.is_running()) {
+        return Err(format!("Review server already running on {}", running.address));
+    }
+
+    let bind = bind.unwrap_or(config.bind);
+    check_bind(&bind, config.allow_lan)?;
+
+    let started = http::serve(&bind, move |request| handle(&app, request))?;
+    let address = started.address.to_string();
+    *server = Some(started);
+    Ok(serde_json::json!({ "running": true, "address": address }))
+}
+
+// Command to stop hosting the workspace
+#[tauri::command]
+pub fn stop_review_server() -> Result<(), String> {
+    if let Some(server) = SERVER.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        server.stop();
+    }
+    Ok(())
+}
+
+// Command to show whether the review server runs and who holds which transcripts
+#[tauri::command]
+pub fn get_review_server_status() -> Result<serde_json::Value, String> {
+    let server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    let address = server.as_ref().filter(|s| s.is_running()).map(|s| s.address.to_string());
+    let claims = state::load()?.review_claims;
+    let active: BTreeMap<&String, Claim> = claims
+        .keys()
+        .filter_map(|file| active_claim(&claims, file).map(|c| (file, c)))
+        .collect();
+
+    Ok(serde_json::json!({ "running": address.is_some(), "address": address, "claims": active }))
+}
+
+// Command to list who changed transcripts, newest first, optionally for one file
+#[tauri::command]
+pub fn get_edit_attribution(file: Option<String>) -> Result<Vec<EditRecord>, String> {
+    Ok(state::load()?
+        .edit_log
+        .into_iter()
+        .rev()
+        .filter(|r| file.as_ref().is_none_or(|f| &r.file_name == f))
+        .collect())
+}
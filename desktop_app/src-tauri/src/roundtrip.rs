@@ -0,0 +1,249 @@
+use std::ops::Range;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::transcript;
+use crate::storage::storage;
+
+// Unchanged words kept on each side of an edit so it is found at the right place
+const CONTEXT_WORDS: usize = 4;
+
+// Lines of unchanged transcript shown around an edit in the preview
+const DIFF_CONTEXT_LINES: usize = 2;
+
+// A text change to apply to a transcript
+#[derive(Debug, Serialize)]
+pub struct TranscriptChange {
+    pub file_name: String,
+    // Changed lines prefixed with " ", "-" or "+"
+    pub diff: Vec<String>,
+    // Whether the whisper JSON was updated too; false only when the transcript has none
+    pub segments_updated: bool,
+    #[serde(skip)]
+    pub content: String,
+    #[serde(skip)]
+    pub segments: Option<String>,
+}
+
+// Entry edit as the changed words plus the unchanged words around them
+struct Edit {
+    pattern: Regex,
+    replacement: Vec<String>,
+    has_before: bool,
+    has_after: bool,
+}
+
+fn join(words: &[&str]) -> String {
+    words.iter().map(|w| regex::escape(w)).collect::<Vec<_>>().join(r"\s+")
+}
+
+// Locate the changed words; whitespace is matched loosely because entries join
+// segments with spaces while transcripts put them on separate lines
+fn edit(old: &str, new: &str) -> Result<Edit, String> {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old.iter().rev().zip(new.iter().rev()).take(max_suffix).take_while(|(a, b)| a == b).count();
+
+    let before = &old[prefix.saturating_sub(CONTEXT_WORDS)..prefix];
+    let changed = &old[prefix..old.len() - suffix];
+    let after = &old[old.len() - suffix..(old.len() - suffix + CONTEXT_WORDS).min(old.len())];
+    if before.is_empty() && changed.is_empty() && after.is_empty() {
+        return Err("Nothing to locate in the transcript".to_string());
+    }
+
+    // Groups: 1 whitespace before the change, 2 the changed words, 3 whitespace after
+    let (lead, trail) = if changed.is_empty() {
+        (if before.is_empty() || after.is_empty() { r"(\s*)" } else { r"(\s+)" }, "()")
+    } else {
+        (
+            if before.is_empty() { r"(\s*)" } else { r"(\s+)" },
+            if after.is_empty() { r"(\s*)" } else { r"(\s+)" },
+        )
+    };
+    let pattern = format!("{}{}({}){}{}", join(before), lead, join(changed), trail, join(after));
+
+    Ok(Edit {
+        pattern: Regex::new(&pattern).map_err(|e| format!("Failed to locate edit: {}", e))?,
+        replacement: new[prefix..new.len() - suffix].iter().map(|w| w.to_string()).collect(),
+        has_before: !before.is_empty(),
+        has_after: !after.is_empty(),
+    })
+}
+
+// Why an edit could not be applied
+#[derive(Debug, PartialEq)]
+enum Unapplied {
+    NotFound,
+    Ambiguous,
+}
+
+// Byte range the edit replaces and the text that goes there, when it matches exactly once;
+// the surrounding whitespace is kept
+fn locate(content: &str, edit: &Edit) -> Result<(Range<usize>, String), Unapplied> {
+    let mut matches = edit.pattern.captures_iter(content);
+    let captures = matches.next().ok_or(Unapplied::NotFound)?;
+    if matches.next().is_some() {
+        return Err(Unapplied::Ambiguous);
+    }
+
+    let group = |i| captures.get(i).ok_or(Unapplied::NotFound);
+    let (lead, changed, trail) = (group(1)?, group(2)?, group(3)?);
+    let words = edit.replacement.join(" ");
+    let replacement = if words.is_empty() {
+        // Removed words leave a single separator behind
+        if edit.has_before && edit.has_after { lead.as_str().to_string() } else { String::new() }
+    } else if changed.as_str().is_empty() {
+        // Inserted words go between the unchanged neighbours
+        let lead = lead.as_str();
+        match (edit.has_before, edit.has_after) {
+            (true, true) => format!("{}{} ", lead, words),
+            (true, false) => format!(" {}{}", words, lead),
+            _ => format!("{}{}", words, if lead.is_empty() { " " } else { lead }),
+        }
+    } else {
+        format!("{}{}{}", lead.as_str(), words, trail.as_str())
+    };
+
+    Ok((lead.start()..trail.end(), replacement))
+}
+
+fn apply(content: &str, edit: &Edit) -> Result<String, Unapplied> {
+    let (range, replacement) = locate(content, edit)?;
+    Ok(format!("{}{}{}", &content[..range.start], replacement, &content[range.end..]))
+}
+
+// Changed lines with a little unchanged context, for previewing
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let prefix = old_lines.iter().zip(&new_lines).take_while(|(a, b)| a == b).count();
+    let max_suffix = old_lines.len().min(new_lines.len()) - prefix;
+    let suffix = old_lines
+        .iter()
+        .rev()
+        .zip(new_lines.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut diff = Vec::new();
+    for line in &old_lines[prefix.saturating_sub(DIFF_CONTEXT_LINES)..prefix] {
+        diff.push(format!(" {}", line));
+    }
+    for line in &old_lines[prefix..old_lines.len() - suffix] {
+        diff.push(format!("-{}", line));
+    }
+    for line in &new_lines[prefix..new_lines.len() - suffix] {
+        diff.push(format!("+{}", line));
+    }
+    let after = old_lines.len() - suffix;
+    for line in &old_lines[after..(after + DIFF_CONTEXT_LINES).min(old_lines.len())] {
+        diff.push(format!(" {}", line));
+    }
+    diff
+}
+
+// Apply the edit to the one whisper segment containing it, keeping timings and other fields;
+// None when the transcript has no whisper JSON. An edit the segments cannot take is refused,
+// so the transcript and its segments never disagree.
+fn update_segments(file_name: &str, edit: &Edit) -> Result<Option<String>, String> {
+    let path = transcript::segments_path(file_name);
+    if !storage().exists(&path) {
+        return Ok(None);
+    }
+    let content = storage().read_to_string(&path).map_err(|e| format!("Failed to read segments: {}", e))?;
+    let mut whisper: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse segments: {}", e))?;
+    let Some(segments) = whisper.get_mut("segments").and_then(|s| s.as_array_mut()) else {
+        return Ok(None);
+    };
+
+    // Located in all segments at once, since the unchanged words around it may be in neighbours
+    let texts: Vec<String> = segments.iter().map(|s| s["text"].as_str().unwrap_or_default().to_string()).collect();
+    let (range, replacement) = locate(&texts.join("\n"), edit).map_err(|reason| match reason {
+        Unapplied::NotFound => format!("The edited words were not found in the whisper segments of {}", file_name),
+        Unapplied::Ambiguous => format!(
+            "The edited words occur more than once in the whisper segments of {}; include more of the surrounding words",
+            file_name
+        ),
+    })?;
+
+    let mut start = 0;
+    let mut target = None;
+    for (i, text) in texts.iter().enumerate() {
+        if range.start >= start && range.end <= start + text.len() {
+            target = Some((i, range.start - start..range.end - start));
+            break;
+        }
+        start += text.len() + 1;
+    }
+    let (index, range) = target.ok_or_else(|| {
+        format!("The edit spans more than one whisper segment of {}; edit the words of each segment separately", file_name)
+    })?;
+
+    let text = &texts[index];
+    segments[index]["text"] = serde_json::Value::String(format!("{}{}{}", &text[..range.start], replacement, &text[range.end..]));
+    serde_json::to_string(&whisper).map(Some).map_err(|e| format!("Failed to serialize segments: {}", e))
+}
+
+// Work out how editing entry text `old` into `new` changes its source transcript
+pub fn propagate(file_name: &str, old: &str, new: &str) -> Result<Option<TranscriptChange>, String> {
+    if old.split_whitespace().eq(new.split_whitespace()) {
+        return Ok(None);
+    }
+
+    let content = storage().read_to_string(transcript::transcript_path(file_name))
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    let edit = edit(old, new)?;
+    let updated = apply(&content, &edit).map_err(|reason| match reason {
+        Unapplied::NotFound => format!("The edited words were not found in transcript {}", file_name),
+        Unapplied::Ambiguous => format!(
+            "The edited words occur more than once in transcript {}; include more of the surrounding words",
+            file_name
+        ),
+    })?;
+    let segments = update_segments(file_name, &edit)?;
+
+    Ok(Some(TranscriptChange {
+        file_name: file_name.to_string(),
+        diff: line_diff(&content, &updated),
+        segments_updated: segments.is_some(),
+        content: updated,
+        segments,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(content: &str, old: &str, new: &str) -> Result<String, Unapplied> {
+        apply(content, &edit(old, new).unwrap())
+    }
+
+    #[test]
+    fn replaces_words_across_transcript_lines() {
+        let content = "Allah is with those\nwho are patiant and sincere.\n";
+        let updated = applied(content, "with those who are patiant and sincere.", "with those who are patient and sincere.");
+        assert_eq!(updated.unwrap(), "Allah is with those\nwho are patient and sincere.\n");
+    }
+
+    #[test]
+    fn inserts_and_removes_words_with_single_spaces() {
+        let content = "Remember Allah often\n";
+        assert_eq!(applied(content, "Remember Allah often", "Remember Allah very often").unwrap(), "Remember Allah very often\n");
+        assert_eq!(applied(content, "Remember Allah often", "Remember often").unwrap(), "Remember often\n");
+        assert_eq!(applied(content, "Remember Allah often", "Always remember Allah often").unwrap(), "Always remember Allah often\n");
+    }
+
+    #[test]
+    fn refuses_missing_and_repeated_words() {
+        assert_eq!(applied("be patient\n", "be grateful", "be thankful"), Err(Unapplied::NotFound));
+        assert_eq!(applied("be patient.\nbe patient.\n", "be patient.", "be steadfast."), Err(Unapplied::Ambiguous));
+    }
+}
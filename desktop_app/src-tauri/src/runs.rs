@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::groundtruth;
+use crate::metadata;
+use crate::state;
+use crate::storage::storage;
+
+// Words a diff looks ahead to find where two transcripts agree again
+const RESYNC_WINDOW: usize = 60;
+// Consecutive matching words that count as agreeing again
+const RESYNC_WORDS: usize = 3;
+
+// Sidecar describing a named transcription run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunInfo {
+    pub model: String,
+    pub created_at: u64,
+}
+
+// Run names become directories inside output/transcripts
+pub fn validate_run(run: &str) -> Result<(), String> {
+    if run.is_empty() || !run.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid run name: {} (use letters, digits, '-' and '_')", run));
+    }
+    Ok(())
+}
+
+// Directory holding the outputs of a run; None is the main transcripts directory
+pub fn run_dir(run: Option<&str>) -> PathBuf {
+    let transcripts = storage().path("output/transcripts");
+    match run {
+        Some(run) => transcripts.join(run),
+        None => transcripts,
+    }
+}
+
+// Prepare a run directory and record which model wrote it
+pub fn begin_run(run: &str, model: &str) -> Result<PathBuf, String> {
+    validate_run(run)?;
+    if run == groundtruth::GROUND_TRUTH_RUN {
+        return Err(format!("{} is reserved for ground-truth transcripts", run));
+    }
+    let dir = run_dir(Some(run));
+    storage().create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let info = RunInfo { model: model.to_string(), created_at: state::now() };
+    let content = serde_json::to_string_pretty(&info).map_err(|e| format!("Failed to serialize run: {}", e))?;
+    storage().write(dir.join("run.json"), content).map_err(|e| format!("Failed to write run: {}", e))?;
+    Ok(dir)
+}
+
+// "main" and an empty name stand for the main transcripts directory
+pub fn parse_run(run: &str) -> Result<Option<&str>, String> {
+    match run {
+        "" | "main" => Ok(None),
+        run => validate_run(run).map(|_| Some(run)),
+    }
+}
+
+pub fn read_run_transcript(file_name: &str, run: Option<&str>) -> Result<String, String> {
+    let path = run_dir(run).join(format!("{}.txt", metadata::stem(file_name)));
+    storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read {} from run {}: {}", file_name, run.unwrap_or("main"), e))
+}
+
+// Transcript file names a run produced
+pub fn run_transcripts(run: Option<&str>) -> Vec<String> {
+    let mut names: Vec<String> = storage().read_dir(run_dir(run))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+// A stretch where the two transcripts disagree
+#[derive(Debug, Serialize)]
+pub struct Hunk {
+    // Word index in transcript A where the difference starts
+    pub a_start: usize,
+    pub b_start: usize,
+    pub a: String,
+    pub b: String,
+}
+
+fn agree(a: &[&str], b: &[&str], i: usize, j: usize) -> bool {
+    (0..RESYNC_WORDS).all(|k| match (a.get(i + k), b.get(j + k)) {
+        (Some(x), Some(y)) => normalize(x) == normalize(y),
+        (None, None) => true,
+        _ => false,
+    })
+}
+
+// Words compare without case and surrounding punctuation
+pub fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+// Nearest pair of positions, within the window, where both transcripts agree again
+fn resync(a: &[&str], b: &[&str], i: usize, j: usize) -> (usize, usize) {
+    for distance in 1..=RESYNC_WINDOW * 2 {
+        for da in 0..=distance.min(RESYNC_WINDOW) {
+            let db = distance - da;
+            if db <= RESYNC_WINDOW && i + da <= a.len() && j + db <= b.len() && agree(a, b, i + da, j + db) {
+                return (i + da, j + db);
+            }
+        }
+    }
+    (a.len(), b.len())
+}
+
+// Word-level differences, resynchronizing greedily after each mismatch
+pub fn diff_words(a: &[&str], b: &[&str]) -> Vec<Hunk> {
+    let (mut i, mut j) = (0, 0);
+    let mut hunks = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && normalize(a[i]) == normalize(b[j]) {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let (next_i, next_j) = resync(a, b, i, j);
+        hunks.push(Hunk { a_start: i, b_start: j, a: a[i..next_i].join(" "), b: b[j..next_j].join(" ") });
+        i = next_i;
+        j = next_j;
+    }
+    hunks
+}
+
+// Command to list named transcription runs with the model that produced them
+#[tauri::command]
+pub fn get_transcription_runs() -> Result<Vec<serde_json::Value>, String> {
+    let entries = match storage().read_dir(run_dir(None)) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut runs = Vec::new();
+    for entry in entries.filter_map(Result::ok).filter(|e| e.path().is_dir()) {
+        let dir = entry.path();
+        let Some(name) = dir.file_name().and_then(|n| n.to_str()).map(str::to_string) else { continue };
+        let info: Option<RunInfo> = storage().read_to_string(dir.join("run.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        let transcripts = storage().read_dir(&dir)
+            .map(|files| {
+                files
+                    .filter_map(Result::ok)
+                    .filter(|f| f.path().extension().is_some_and(|ext| ext == "txt"))
+                    .count()
+            })
+            .unwrap_or(0);
+        runs.push(serde_json::json!({ "run": name, "info": info, "transcripts": transcripts }));
+    }
+    runs.sort_by(|a, b| a["run"].as_str().cmp(&b["run"].as_str()));
+    Ok(runs)
+}
+
+// Command to diff one clip's transcripts from two runs; "main" is the main transcripts directory
+#[tauri::command]
+pub fn compare_transcriptions(file: String, run_a: String, run_b: String) -> Result<serde_json::Value, String> {
+    let text_a = read_run_transcript(&file, parse_run(&run_a)?)?;
+    let text_b = read_run_transcript(&file, parse_run(&run_b)?)?;
+    let words_a: Vec<&str> = text_a.split_whitespace().collect();
+    let words_b: Vec<&str> = text_b.split_whitespace().collect();
+
+    let hunks = diff_words(&words_a, &words_b);
+    let changed: usize = hunks.iter().map(|h| h.a.split_whitespace().count().max(h.b.split_whitespace().count())).sum();
+    let longest = words_a.len().max(words_b.len()).max(1);
+
+    Ok(serde_json::json!({
+        "file": file,
+        "run_a": { "run": run_a, "words": words_a.len() },
+        "run_b": { "run": run_b, "words": words_b.len() },
+        "hunks": hunks,
+        "changed_words": changed,
+        "agreement": 1.0 - (changed as f64 / longest as f64).min(1.0)
+    }))
+}
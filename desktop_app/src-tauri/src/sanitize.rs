@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::integrity;
+use crate::journal;
+use crate::storage::storage;
+use crate::text;
+use crate::transcript;
+
+// A problem found in transcript text
+#[derive(Debug, Clone, Serialize)]
+pub struct TextIssue {
+    // "control_character", "unbalanced_bidi", "mixed_script" or "presentation_form"
+    pub kind: String,
+    // Character offset of the problem
+    pub offset: usize,
+    pub detail: String,
+}
+
+// Invisible characters that carry no meaning in a transcript. ZWNJ and ZWJ are kept
+// because they change letter joining, and LRM, RLM and ALM because they fix the reading
+// order of numbers and punctuation in mixed Arabic and English lines.
+fn is_stray_control(c: char) -> bool {
+    (c.is_control() && !matches!(c, '\n' | '\t' | '\r'))
+        || matches!(c, '\u{200B}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}')
+}
+
+// Character offsets of embeddings, overrides and isolates that are never closed and of
+// terminators that close nothing. As in the Unicode bidi algorithm, a terminator closes the
+// innermost opener of its kind, a PDI also ends embeddings opened inside its isolate, and
+// every scope ends with the line.
+fn unbalanced_bidi(content: &str) -> BTreeSet<usize> {
+    let mut unbalanced = BTreeSet::new();
+    // Offset of each open scope and whether it is an isolate
+    let mut open: Vec<(usize, bool)> = Vec::new();
+
+    for (offset, c) in content.chars().enumerate() {
+        match c {
+            '\u{202A}' | '\u{202B}' | '\u{202D}' | '\u{202E}' => open.push((offset, false)),
+            '\u{2066}'..='\u{2068}' => open.push((offset, true)),
+            '\u{202C}' => match open.last() {
+                Some((_, false)) => {
+                    open.pop();
+                }
+                _ => {
+                    unbalanced.insert(offset);
+                }
+            },
+            '\u{2069}' => match open.iter().rposition(|(_, isolate)| *isolate) {
+                Some(start) => {
+                    unbalanced.extend(open.drain(start..).skip(1).map(|(o, _)| o));
+                }
+                None => {
+                    unbalanced.insert(offset);
+                }
+            },
+            '\n' => unbalanced.extend(open.drain(..).map(|(o, _)| o)),
+            _ => {}
+        }
+    }
+    unbalanced.extend(open.into_iter().map(|(o, _)| o));
+    unbalanced
+}
+
+// Arabic presentation forms usually come from text copied out of PDFs
+fn is_presentation_form(c: char) -> bool {
+    matches!(c, '\u{FB50}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFC}')
+}
+
+// Find control characters, broken bidi runs and mixed-script words
+pub fn find_issues(content: &str) -> Vec<TextIssue> {
+    let mut issues = Vec::new();
+    let unbalanced = unbalanced_bidi(content);
+
+    for (offset, c) in content.chars().enumerate() {
+        let code = format!("U+{:04X}", c as u32);
+        if is_stray_control(c) {
+            issues.push(TextIssue { kind: "control_character".to_string(), offset, detail: code });
+        } else if unbalanced.contains(&offset) {
+            let detail = if matches!(c, '\u{202C}' | '\u{2069}') {
+                format!("{} without a matching opener", code)
+            } else {
+                format!("{} is never closed", code)
+            };
+            issues.push(TextIssue { kind: "unbalanced_bidi".to_string(), offset, detail });
+        } else if is_presentation_form(c) {
+            issues.push(TextIssue { kind: "presentation_form".to_string(), offset, detail: code });
+        }
+    }
+
+    // Words fusing Arabic and Latin letters, e.g. "allahالله"
+    let mut word_start = 0;
+    let mut word = String::new();
+    for (offset, c) in content.chars().chain([' ']).enumerate() {
+        if c.is_alphanumeric() {
+            if word.is_empty() {
+                word_start = offset;
+            }
+            word.push(c);
+            continue;
+        }
+        if word.chars().any(text::is_arabic) && word.chars().any(|w| w.is_ascii_alphabetic()) {
+            issues.push(TextIssue { kind: "mixed_script".to_string(), offset: word_start, detail: word.clone() });
+        }
+        word.clear();
+    }
+
+    issues.sort_by_key(|i| i.offset);
+    issues
+}
+
+// Strip stray invisible characters and unbalanced bidi controls and split mixed-script words
+pub fn fix(content: &str) -> String {
+    let mut fixed = String::with_capacity(content.len());
+    let mut previous: Option<char> = None;
+    let unbalanced = unbalanced_bidi(content);
+
+    for (offset, c) in content.chars().enumerate() {
+        if is_stray_control(c) || unbalanced.contains(&offset) {
+            continue;
+        }
+
+        // Insert a space where a word switches between Arabic and Latin letters
+        if let Some(p) = previous {
+            let switches = (text::is_arabic(p) && c.is_ascii_alphabetic())
+                || (p.is_ascii_alphabetic() && text::is_arabic(c));
+            if switches {
+                fixed.push(' ');
+            }
+        }
+
+        fixed.push(c);
+        previous = Some(c);
+    }
+
+    fixed
+}
+
+// Command to list reading-order and invisible-character problems in a transcript
+#[tauri::command]
+pub fn check_text_issues(file_name: String) -> Result<Vec<TextIssue>, String> {
+    let path = transcript::transcript_path(&file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    Ok(find_issues(&content))
+}
+
+// Command to normalize a transcript's text, returning the issues that were present
+#[tauri::command]
+pub fn fix_text_issues(file_name: String) -> Result<serde_json::Value, String> {
+    let path = transcript::transcript_path(&file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let issues = find_issues(&content);
+    let fixed = fix(&content);
+
+    if fixed != content {
+        let mut journal = journal::begin("replace", &format!("Fix text issues in {}", file_name))?;
+        journal.preserve(&path)?;
+        storage().write(&path, &fixed).map_err(|e| format!("Failed to write transcript: {}", e))?;
+        journal.commit()?;
+        integrity::record(&path);
+    }
+
+    // Presentation forms need compatibility normalization and are only reported
+    let remaining = find_issues(&fixed);
+
+    Ok(serde_json::json!({
+        "file_name": file_name,
+        "fixed": issues.len() - remaining.len().min(issues.len()),
+        "remaining": remaining,
+        "revision": transcript::snapshot(&file_name, &fixed)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fix_keeps_directional_marks_and_joiners() {
+        let text = "قال\u{200F} (2:255)\u{200E} و\u{061C}ب\u{200D}ـ ک\u{200C}ه";
+        assert_eq!(fix(text), text);
+        assert!(find_issues(text).is_empty());
+    }
+
+    #[test]
+    fn fix_keeps_balanced_runs_and_drops_unbalanced_controls() {
+        let balanced = "see \u{2067}الفاتحة\u{2069} and \u{202B}آية\u{202C}";
+        assert_eq!(fix(balanced), balanced);
+
+        assert_eq!(fix("a\u{202E}b\nc\u{202C}d\u{2069}"), "ab\ncd");
+        // A PDI ends the embedding left open inside its isolate
+        assert_eq!(fix("\u{2066}a\u{202B}b\u{2069}"), "\u{2066}ab\u{2069}");
+        let kinds: Vec<String> = find_issues("a\u{202E}b").into_iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, ["unbalanced_bidi"]);
+    }
+
+    #[test]
+    fn fix_strips_stray_controls_and_splits_mixed_words() {
+        assert_eq!(fix("\u{FEFF}bis\u{200B}millah\u{0007}الله"), "bismillah الله");
+    }
+}
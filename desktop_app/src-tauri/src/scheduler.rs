@@ -0,0 +1,124 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::jobs;
+use crate::settings;
+
+// How heavy pipeline work shares the machine, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchedulerPolicy {
+    // Maximum number of heavy jobs (downloads, transcriptions, alignments) running at once
+    pub max_heavy_jobs: usize,
+    // Never run GPU transcription while CPU-heavy ffmpeg work is running, and vice versa
+    pub serialize_gpu_cpu: bool,
+}
+
+impl Default for SchedulerPolicy {
+    fn default() -> Self {
+        SchedulerPolicy { max_heavy_jobs: 1, serialize_gpu_cpu: true }
+    }
+}
+
+// Resource a job mostly uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resource {
+    // Whisper transcription, alignment, speaker embeddings
+    Gpu,
+    // yt-dlp audio extraction and other ffmpeg work
+    Cpu,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Usage {
+    gpu: usize,
+    cpu: usize,
+    waiting: usize,
+}
+
+static USAGE: Mutex<Usage> = Mutex::new(Usage { gpu: 0, cpu: 0, waiting: 0 });
+static RELEASED: Condvar = Condvar::new();
+
+// GPU jobs that fit in VRAM at once, set by vram::prepare; 0 means no limit
+static GPU_LIMIT: AtomicUsize = AtomicUsize::new(0);
+
+// How often a waiting job shows a sign of life and checks whether it was killed
+pub const WAIT_TICK: Duration = Duration::from_secs(2);
+
+// Cap concurrent GPU jobs below the policy's heavy-job limit
+pub fn set_gpu_limit(limit: usize) {
+    GPU_LIMIT.store(limit, Ordering::SeqCst);
+    RELEASED.notify_all();
+}
+
+// Held while a heavy job runs; dropping it lets the next job start
+pub struct Permit {
+    resource: Resource,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+        match self.resource {
+            Resource::Gpu => usage.gpu -= 1,
+            Resource::Cpu => usage.cpu -= 1,
+        }
+        RELEASED.notify_all();
+    }
+}
+
+fn can_start(usage: &Usage, resource: Resource, policy: &SchedulerPolicy) -> bool {
+    let running = usage.gpu + usage.cpu;
+    if running >= policy.max_heavy_jobs.max(1) {
+        return false;
+    }
+
+    let gpu_limit = GPU_LIMIT.load(Ordering::SeqCst);
+    if resource == Resource::Gpu && gpu_limit > 0 && usage.gpu >= gpu_limit {
+        return false;
+    }
+
+    !policy.serialize_gpu_cpu
+        || match resource {
+            Resource::Gpu => usage.cpu == 0,
+            Resource::Cpu => usage.gpu == 0,
+        }
+}
+
+// Block until the policy allows another job on `resource`; fails when the waiting job is killed
+pub fn acquire(resource: Resource) -> Result<Permit, String> {
+    let policy = settings::load().scheduler;
+    let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+
+    usage.waiting += 1;
+    while !can_start(&usage, resource, &policy) {
+        if let Err(e) = jobs::waiting() {
+            usage.waiting -= 1;
+            return Err(e);
+        }
+        usage = RELEASED.wait_timeout(usage, WAIT_TICK).unwrap_or_else(|e| e.into_inner()).0;
+    }
+    usage.waiting -= 1;
+
+    match resource {
+        Resource::Gpu => usage.gpu += 1,
+        Resource::Cpu => usage.cpu += 1,
+    }
+    Ok(Permit { resource })
+}
+
+// Command to show the scheduler policy and how many heavy jobs are running or waiting
+#[tauri::command]
+pub fn get_scheduler_status() -> serde_json::Value {
+    let usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    serde_json::json!({
+        "policy": settings::load().scheduler,
+        "running": { "gpu": usage.gpu, "cpu": usage.cpu },
+        "gpu_limit": GPU_LIMIT.load(Ordering::SeqCst),
+        "waiting": usage.waiting
+    })
+}
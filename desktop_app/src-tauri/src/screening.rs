@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::metadata;
+use crate::settings;
+use crate::state;
+use crate::storage::storage;
+use crate::transcript;
+
+// A user-defined keyword rule; matching is case-insensitive on whole text, not whole words
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningRule {
+    pub keyword: String,
+    // "flag" sends the item to the review queue, "exclude" keeps it out of datasets outright
+    #[serde(default = "default_action")]
+    pub action: String,
+}
+
+fn default_action() -> String {
+    "flag".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreeningSettings {
+    pub enabled: bool,
+    // Also search transcript text, not only titles, descriptions and tags
+    pub include_transcripts: bool,
+    pub rules: Vec<ScreeningRule>,
+}
+
+// Outcome of screening one transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningResult {
+    pub matched: Vec<String>,
+    // "flagged", "excluded", "approved" or "rejected"
+    pub status: String,
+    pub screened_at: u64,
+}
+
+impl ScreeningResult {
+    // Whether the transcript is currently kept out of datasets
+    pub fn blocks_dataset(&self) -> bool {
+        self.status != "approved"
+    }
+}
+
+fn metadata_text(file_name: &str) -> String {
+    let meta = metadata::load(file_name).unwrap_or_default();
+    [meta.title, meta.description, Some(meta.tags.join(" "))]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Screen every transcript against the rules; earlier review decisions are kept
+// unless the matching rules changed
+pub fn run(settings: &ScreeningSettings) -> Result<BTreeMap<String, ScreeningResult>, String> {
+    let rules: Vec<(String, &ScreeningRule)> = settings
+        .rules
+        .iter()
+        .filter(|r| !r.keyword.trim().is_empty())
+        .map(|r| (r.keyword.trim().to_lowercase(), r))
+        .collect();
+
+    let mut results = BTreeMap::new();
+    for file_name in transcript::list_transcripts() {
+        let mut text = metadata_text(&file_name);
+        if settings.include_transcripts {
+            text.push('\n');
+            text.push_str(&storage().read_to_string(transcript::transcript_path(&file_name)).unwrap_or_default());
+        }
+        let text = text.to_lowercase();
+
+        let matched: Vec<&ScreeningRule> = rules.iter().filter(|(k, _)| text.contains(k)).map(|(_, r)| *r).collect();
+        if matched.is_empty() {
+            continue;
+        }
+
+        let status = if matched.iter().any(|r| r.action == "exclude") { "excluded" } else { "flagged" };
+        results.insert(
+            file_name,
+            ScreeningResult {
+                matched: matched.iter().map(|r| r.keyword.clone()).collect(),
+                status: status.to_string(),
+                screened_at: state::now(),
+            },
+        );
+    }
+
+    state::update(|s| {
+        for (file_name, result) in results.iter_mut() {
+            if let Some(previous) = s.screening.get(file_name) {
+                if previous.matched == result.matched && ["approved", "rejected"].contains(&previous.status.as_str()) {
+                    result.status = previous.status.clone();
+                }
+            }
+        }
+        s.screening = results.clone();
+    })?;
+
+    Ok(results)
+}
+
+// Screen before dataset generation when screening is enabled
+pub fn run_if_enabled() -> Result<(), String> {
+    let settings = settings::load().screening;
+    if settings.enabled {
+        run(&settings)?;
+    }
+    Ok(())
+}
+
+// Command to screen all transcripts against the configured keyword rules
+#[tauri::command]
+pub fn run_screening() -> Result<BTreeMap<String, ScreeningResult>, String> {
+    run(&settings::load().screening)
+}
+
+// Command to list flagged items waiting for review
+#[tauri::command]
+pub fn get_screening_queue() -> Result<Vec<serde_json::Value>, String> {
+    Ok(state::load()?
+        .screening
+        .into_iter()
+        .filter(|(_, result)| result.status == "flagged")
+        .map(|(file_name, result)| {
+            let title = metadata::load(&file_name).and_then(|m| m.title);
+            serde_json::json!({ "file_name": file_name, "title": title, "matched": result.matched, "screened_at": result.screened_at })
+        })
+        .collect())
+}
+
+// Command to approve (include) or reject (exclude) a screened item
+#[tauri::command]
+pub fn review_screening(file_name: String, decision: String) -> Result<(), String> {
+    let status = match decision.as_str() {
+        "approve" => "approved",
+        "reject" => "rejected",
+        _ => return Err(format!("Unknown screening decision: {}", decision)),
+    };
+
+    state::update(|s| match s.screening.get_mut(&file_name) {
+        Some(result) => {
+            result.status = status.to_string();
+            Ok(())
+        }
+        None => Err(format!("No screening result for {}", file_name)),
+    })??;
+    audit::record("screening.review", &file_name, serde_json::json!({ "status": status }));
+    Ok(())
+}
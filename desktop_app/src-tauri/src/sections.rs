@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::metadata;
+use crate::text;
+use crate::transcript::{self, Segment};
+use crate::storage::storage;
+
+// Segments on each side of a candidate boundary compared for similarity
+const BLOCK_SEGMENTS: usize = 6;
+
+// Sections shorter than this are merged into their neighbour
+const DEFAULT_MIN_SECTION_SECONDS: f64 = 120.0;
+
+// Keywords used to title a section
+const TITLE_KEYWORDS: usize = 3;
+
+// Words too common to say anything about the topic
+const STOPWORDS: &[&str] = &[
+    "that", "this", "with", "have", "they", "will", "what", "when", "from", "your", "were", "there",
+    "their", "about", "which", "would", "because", "these", "those", "then", "than", "them", "into",
+    "also", "just", "like", "know", "said", "says", "very", "some", "more", "even", "does", "going",
+    "come", "want", "make", "only", "other", "where", "who", "been", "being", "here", "allah",
+];
+
+// A topical section of a transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    pub title: String,
+    pub start: f64,
+    pub end: f64,
+    // Index range of the whisper segments in the section, end exclusive
+    pub first_segment: usize,
+    pub end_segment: usize,
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+fn sections_path(file_name: &str) -> PathBuf {
+    storage()
+        .path("output/sections")
+        .join(format!("{}.json", metadata::stem(file_name)))
+}
+
+// Stored sections of a transcript, if segment_topics has run
+pub fn load(file_name: &str) -> Option<Vec<Section>> {
+    let content = storage().read_to_string(sections_path(file_name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn content_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|w| w.to_lowercase())
+        .filter(|w| w.chars().count() > 3 && !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+fn bag(segments: &[Vec<String>]) -> HashMap<&str, f64> {
+    let mut bag = HashMap::new();
+    for word in segments.iter().flatten() {
+        *bag.entry(word.as_str()).or_insert(0.0) += 1.0;
+    }
+    bag
+}
+
+fn cosine(a: &HashMap<&str, f64>, b: &HashMap<&str, f64>) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(w, x)| b.get(w).map(|y| x * y)).sum();
+    let norm = |v: &HashMap<&str, f64>| v.values().map(|x| x * x).sum::<f64>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator > 0.0 {
+        dot / denominator
+    } else {
+        0.0
+    }
+}
+
+// TextTiling: boundaries sit where vocabulary similarity between neighbouring blocks dips deepest
+fn boundaries(words: &[Vec<String>], segments: &[Segment], min_seconds: f64) -> Vec<usize> {
+    let n = words.len();
+    if n < BLOCK_SEGMENTS * 2 {
+        return Vec::new();
+    }
+
+    // Similarity across the gap before each segment
+    let gaps: Vec<(usize, f64)> = (BLOCK_SEGMENTS..=n - BLOCK_SEGMENTS)
+        .map(|i| (i, cosine(&bag(&words[i - BLOCK_SEGMENTS..i]), &bag(&words[i..i + BLOCK_SEGMENTS]))))
+        .collect();
+
+    // Depth: how far similarity climbs back up on both sides of the gap
+    let depths: Vec<(usize, f64)> = (0..gaps.len())
+        .map(|g| {
+            let score = gaps[g].1;
+            let left = gaps[..=g].iter().rev().map(|(_, s)| *s).take_while(|s| *s >= score).fold(score, f64::max);
+            let right = gaps[g..].iter().map(|(_, s)| *s).take_while(|s| *s >= score).fold(score, f64::max);
+            (gaps[g].0, (left - score) + (right - score))
+        })
+        .collect();
+
+    let mean = depths.iter().map(|(_, d)| d).sum::<f64>() / depths.len() as f64;
+    let deviation = (depths.iter().map(|(_, d)| (d - mean).powi(2)).sum::<f64>() / depths.len() as f64).sqrt();
+    let cutoff = mean + deviation / 2.0;
+
+    // Deepest candidates first, skipping any that would make a section too short
+    let mut candidates: Vec<(usize, f64)> = depths.into_iter().filter(|(_, d)| *d > cutoff && *d > 0.0).collect();
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let start = segments.first().map_or(0.0, |s| s.start);
+    let end = segments.last().map_or(0.0, |s| s.end);
+    let mut chosen: Vec<usize> = Vec::new();
+    for (index, _) in candidates {
+        let at = segments[index].start;
+        let too_close = at - start < min_seconds
+            || end - at < min_seconds
+            || chosen.iter().any(|c| (segments[*c].start - at).abs() < min_seconds);
+        if !too_close {
+            chosen.push(index);
+        }
+    }
+    chosen.sort();
+    chosen
+}
+
+// Title from the words most characteristic of the section compared with the whole transcript
+fn title(section: &[Vec<String>], all: &HashMap<&str, f64>) -> String {
+    let local = bag(section);
+    let mut scored: Vec<(&str, f64)> = local
+        .iter()
+        .map(|(word, count)| (*word, count * count / all.get(word).copied().unwrap_or(*count)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let keywords: Vec<&str> = scored.into_iter().take(TITLE_KEYWORDS).map(|(w, _)| w).collect();
+    if keywords.is_empty() {
+        "Untitled section".to_string()
+    } else {
+        keywords.join(", ")
+    }
+}
+
+pub fn segment(segments: &[Segment], min_seconds: f64) -> Vec<Section> {
+    let words: Vec<Vec<String>> = segments.iter().map(|s| content_words(&s.text)).collect();
+    let all = bag(&words);
+
+    let mut edges = vec![0];
+    edges.extend(boundaries(&words, segments, min_seconds));
+    edges.push(segments.len());
+
+    edges
+        .windows(2)
+        .filter(|w| w[1] > w[0])
+        .map(|w| {
+            let text: String = segments[w[0]..w[1]].iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+            Section {
+                title: title(&words[w[0]..w[1]], &all),
+                start: segments[w[0]].start,
+                end: segments[w[1] - 1].end,
+                first_segment: w[0],
+                end_segment: w[1],
+                topics: text::extract_topics(&text),
+            }
+        })
+        .collect()
+}
+
+// Command to split a transcript into topical sections by vocabulary shifts and store them
+// in output/sections; sections are at least `min_section_seconds` long (120 by default)
+#[tauri::command]
+pub fn segment_topics(file: String, min_section_seconds: Option<f64>) -> Result<Vec<Section>, String> {
+    let segments = transcript::load_segments(&file)
+        .ok_or_else(|| format!("No timed segments for transcript: {}", file))?;
+    let sections = segment(&segments, min_section_seconds.unwrap_or(DEFAULT_MIN_SECTION_SECONDS));
+
+    let path = sections_path(&file);
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&sections)
+        .map_err(|e| format!("Failed to serialize sections: {}", e))?;
+    storage().write(&path, content).map_err(|e| format!("Failed to write sections: {}", e))?;
+
+    Ok(sections)
+}
+
+// Command to get the stored sections of a transcript
+#[tauri::command]
+pub fn get_sections(file: String) -> Option<Vec<Section>> {
+    load(&file)
+}
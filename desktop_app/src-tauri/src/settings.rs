@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::autorebuild::AutoRebuildSettings;
+use crate::boilerplate::BoilerplateSettings;
+use crate::coldstorage::ColdStorageSettings;
+use crate::dataset::DatasetConfig;
+use crate::flagging::FlaggingSettings;
+use crate::glossary::{self, GlossaryTerm};
+use crate::janitor::CleanupSettings;
+use crate::naming;
+use crate::notify::NotificationSettings;
+use crate::numerals::NormalizationSettings;
+use crate::persona::Persona;
+use crate::ratelimit::DownloadPolicy;
+use crate::review_server::CollaborationSettings;
+use crate::scheduler::SchedulerPolicy;
+use crate::screening::ScreeningSettings;
+use crate::sharing::SharingSettings;
+use crate::sources::SourceLists;
+use crate::vram::GpuSettings;
+use crate::watchdog::StallSettings;
+use crate::whisper::DecodingSettings;
+use crate::workspace;
+use crate::ytdlp::YtdlpUpdateSettings;
+use crate::storage::storage;
+
+// Workspace settings, stored as JSON in the pipeline directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // Command used to open transcripts externally; falls back to $VISUAL/$EDITOR
+    pub editor: Option<String>,
+    // Hunspell dictionaries used for transcript suggestions
+    pub spellcheck_dictionaries: Vec<String>,
+    // Extra "wrong -> right" replacements for recurring ASR errors
+    pub corrections: BTreeMap<String, String>,
+    // Named datasets generated from the corpus
+    pub datasets: Vec<DatasetConfig>,
+    // Opt-in: record anonymous feature usage and error categories locally
+    pub telemetry_enabled: bool,
+    // Where submit_telemetry posts the aggregate counters
+    pub telemetry_endpoint: Option<String>,
+    // Locale of backend-originated messages: "en", "ar" or "ms"
+    pub locale: String,
+    // Whisper model used for transcription and warm-up
+    pub whisper_model: String,
+    // How downloads and transcriptions share the machine
+    pub scheduler: SchedulerPolicy,
+    // Delays and per-host limits that keep large scrapes from being throttled
+    pub download_policy: DownloadPolicy,
+    // Channels and domains links must (not) come from
+    pub sources: SourceLists,
+    // Keyword rules that flag or exclude items before dataset inclusion
+    pub screening: ScreeningSettings,
+    // File stem of downloaded clips, e.g. "{channel}/{upload_date}-{title}"; transcripts and
+    // sidecars follow the clip stem
+    pub naming_template: String,
+    // Keep the downloaded video next to the extracted audio, for frame extraction
+    pub keep_video: bool,
+    // Named instruction/output framings that generate_json can target
+    pub personas: Vec<Persona>,
+    // Regenerate a dataset in the background when approved transcripts change
+    pub auto_rebuild: AutoRebuildSettings,
+    // Preferred spellings of Arabic terms, enforced on new transcripts and dataset entries
+    pub glossary: Vec<GlossaryTerm>,
+    // LAN review server for team review
+    pub collaboration: CollaborationSettings,
+    // Email, Telegram and Discord notifications for batches and low disk space
+    pub notifications: NotificationSettings,
+    // VRAM-aware model sizing for GPU transcription
+    pub gpu: GpuSettings,
+    // Whisper decoding parameters for advanced transcription settings
+    pub decoding: DecodingSettings,
+    // Self-update of yt-dlp before large download batches
+    pub ytdlp_update: YtdlpUpdateSettings,
+    // Read-only public server for approved transcripts
+    pub sharing: SharingSettings,
+    // Phrases stripped from dataset entries during generation
+    pub boilerplate: BoilerplateSettings,
+    // Consistent numbers, dates and month spellings in dataset entries
+    pub normalization: NormalizationSettings,
+    // Segment-level flagging of sensitive terms for review before dataset inclusion
+    pub flagging: FlaggingSettings,
+    // Flag download and transcription jobs whose tools stop producing output
+    pub stall_detection: StallSettings,
+    // Retention policies for audio, logs and quarantined clips
+    pub cleanup: CleanupSettings,
+    // External drive or NAS directory that processed audio can be moved to
+    pub cold_storage: ColdStorageSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            editor: None,
+            spellcheck_dictionaries: vec!["en_US".to_string(), "ar".to_string()],
+            corrections: BTreeMap::new(),
+            datasets: vec![DatasetConfig::default_dataset()],
+            telemetry_enabled: false,
+            telemetry_endpoint: None,
+            locale: "en".to_string(),
+            whisper_model: "small".to_string(),
+            scheduler: SchedulerPolicy::default(),
+            download_policy: DownloadPolicy::default(),
+            sources: SourceLists::default(),
+            screening: ScreeningSettings::default(),
+            naming_template: naming::DEFAULT_TEMPLATE.to_string(),
+            keep_video: false,
+            personas: vec![Persona::akhi()],
+            auto_rebuild: AutoRebuildSettings::default(),
+            glossary: glossary::default_glossary(),
+            collaboration: CollaborationSettings::default(),
+            notifications: NotificationSettings::default(),
+            gpu: GpuSettings::default(),
+            decoding: DecodingSettings::default(),
+            ytdlp_update: YtdlpUpdateSettings::default(),
+            sharing: SharingSettings::default(),
+            boilerplate: BoilerplateSettings::default(),
+            normalization: NormalizationSettings::default(),
+            flagging: FlaggingSettings::default(),
+            stall_detection: StallSettings::default(),
+            cleanup: CleanupSettings::default(),
+            cold_storage: ColdStorageSettings::default(),
+        }
+    }
+}
+
+// Sent in place of each credential when settings leave the machine
+pub const REDACTED: &str = "********";
+
+// Stored value of a credential that came back redacted
+fn restore(value: &mut String, stored: Option<&String>) {
+    if value == REDACTED {
+        *value = stored.cloned().unwrap_or_default();
+    }
+}
+
+impl Settings {
+    // Credentials held in settings, so outputs meant for publishing can be checked against them
+    pub fn secrets(&self) -> Vec<String> {
+        let notifications = &self.notifications;
+        let mut secrets: Vec<String> = [
+            notifications.email.as_ref().and_then(|e| e.password.clone()),
+            notifications.telegram.as_ref().map(|t| t.bot_token.clone()),
+            notifications.discord.as_ref().map(|d| d.webhook_url.clone()),
+            self.collaboration.token.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.collaboration.members.iter().map(|m| m.token.clone()))
+        .collect();
+        secrets.retain(|s| !s.trim().is_empty());
+        secrets
+    }
+
+    // Copy with every credential replaced by REDACTED, for clients on the network
+    pub fn redacted(&self) -> Settings {
+        let mut settings = self.clone();
+        let hide = |value: &mut String| {
+            if !value.is_empty() {
+                *value = REDACTED.to_string();
+            }
+        };
+        let notifications = &mut settings.notifications;
+        if let Some(password) = notifications.email.as_mut().and_then(|e| e.password.as_mut()) {
+            hide(password);
+        }
+        if let Some(telegram) = notifications.telegram.as_mut() {
+            hide(&mut telegram.bot_token);
+        }
+        if let Some(discord) = notifications.discord.as_mut() {
+            hide(&mut discord.webhook_url);
+        }
+        if let Some(token) = settings.collaboration.token.as_mut() {
+            hide(token);
+        }
+        for member in &mut settings.collaboration.members {
+            hide(&mut member.token);
+        }
+        settings
+    }
+
+    // Put back the stored credentials a client sent as REDACTED; members are matched by name
+    pub fn restore_secrets(&mut self, stored: &Settings) {
+        let (notifications, old) = (&mut self.notifications, &stored.notifications);
+        if let Some(password) = notifications.email.as_mut().and_then(|e| e.password.as_mut()) {
+            restore(password, old.email.as_ref().and_then(|e| e.password.as_ref()));
+        }
+        if let Some(telegram) = notifications.telegram.as_mut() {
+            restore(&mut telegram.bot_token, old.telegram.as_ref().map(|t| &t.bot_token));
+        }
+        if let Some(discord) = notifications.discord.as_mut() {
+            restore(&mut discord.webhook_url, old.discord.as_ref().map(|d| &d.webhook_url));
+        }
+        if let Some(token) = self.collaboration.token.as_mut() {
+            restore(token, stored.collaboration.token.as_ref());
+        }
+        for member in &mut self.collaboration.members {
+            let old = stored.collaboration.members.iter().find(|m| m.name == member.name);
+            restore(&mut member.token, old.map(|m| &m.token));
+        }
+    }
+}
+
+pub fn settings_path() -> PathBuf {
+    storage().path("settings.json")
+}
+
+// Load settings, using defaults when the file is missing or invalid
+pub fn load() -> Settings {
+    storage().read_to_string(settings_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(settings: &Settings) -> Result<(), String> {
+    workspace::ensure_writable()?;
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    storage().write(settings_path(), content)
+        .map_err(|e| format!("Failed to write settings: {}", e))
+}
+
+// Command to get the workspace settings
+#[tauri::command]
+pub fn get_settings() -> Result<Settings, String> {
+    Ok(load())
+}
+
+// Command to replace the workspace settings
+#[tauri::command]
+pub fn update_settings(settings: Settings) -> Result<(), String> {
+    naming::validate(&settings.naming_template)?;
+    settings.collaboration.validate()?;
+    settings.decoding.validate()?;
+
+    // Only the names of changed settings are logged, values may hold tokens
+    let before = serde_json::to_value(load()).unwrap_or_default();
+    let after = serde_json::to_value(&settings).unwrap_or_default();
+    let changed: Vec<&String> = after
+        .as_object()
+        .map(|fields| fields.iter().filter(|(key, value)| before.get(key.as_str()) != Some(*value)).map(|(key, _)| key).collect())
+        .unwrap_or_default();
+
+    save(&settings)?;
+    audit::record("settings.update", "settings", serde_json::json!({ "changed": changed }));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::TelegramChannel;
+    use crate::review_server::Member;
+
+    #[test]
+    fn redacted_settings_keep_stored_credentials_when_sent_back() {
+        let mut stored = Settings::default();
+        stored.collaboration.token = Some("shared-secret".to_string());
+        stored.collaboration.members.push(Member {
+            name: "aisha".to_string(),
+            token: "member-secret".to_string(),
+            role: stored.collaboration.default_role,
+        });
+        stored.notifications.telegram = Some(TelegramChannel { bot_token: "bot-secret".to_string(), chat_id: "42".to_string() });
+
+        let sent = serde_json::to_string(&stored.redacted()).unwrap();
+        assert!(stored.secrets().iter().all(|secret| !sent.contains(secret.as_str())));
+
+        let mut returned: Settings = serde_json::from_str(&sent).unwrap();
+        returned.collaboration.members[0].token = "new-secret".to_string();
+        returned.restore_secrets(&stored);
+        assert_eq!(returned.collaboration.token.as_deref(), Some("shared-secret"));
+        assert_eq!(returned.collaboration.members[0].token, "new-secret");
+        assert_eq!(returned.notifications.telegram.unwrap().bot_token, "bot-secret");
+    }
+}
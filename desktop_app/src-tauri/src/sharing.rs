@@ -0,0 +1,200 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::{self, Request, Response};
+use crate::metadata;
+use crate::review_server;
+use crate::settings;
+use crate::state;
+use crate::text;
+use crate::transcript;
+use crate::storage::storage;
+
+// Public read-only sharing of approved transcripts, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharingSettings {
+    pub bind: String,
+    // Required to listen on anything but a loopback address
+    pub allow_lan: bool,
+    // Heading of the shared pages
+    pub title: String,
+}
+
+impl Default for SharingSettings {
+    fn default() -> Self {
+        SharingSettings {
+            bind: "127.0.0.1:8780".to_string(),
+            allow_lan: false,
+            title: "Lecture transcripts".to_string(),
+        }
+    }
+}
+
+static SERVER: Mutex<Option<http::Server>> = Mutex::new(None);
+
+// Only approved transcripts are ever served
+pub fn approved() -> Result<Vec<String>, String> {
+    let state = state::load()?;
+    Ok(transcript::list_transcripts()
+        .into_iter()
+        .filter(|name| transcript::review_status(&state, name) == "approved")
+        .collect())
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Percent-encode a path segment
+pub fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn summary(name: &str, query: Option<&str>) -> Option<serde_json::Value> {
+    let content = storage().read_to_string(transcript::transcript_path(name)).ok()?;
+    let snippets = match query {
+        Some(query) => {
+            let found = text::snippets(&content, query);
+            if found.is_empty() {
+                return None;
+            }
+            found
+        }
+        None => Vec::new(),
+    };
+    let meta = metadata::load(name).unwrap_or_default();
+    Some(serde_json::json!({
+        "file_name": name,
+        "title": meta.title,
+        "speaker": meta.channel_name(),
+        "upload_date": meta.upload_date,
+        "words": content.split_whitespace().count(),
+        "snippets": snippets,
+    }))
+}
+
+fn listing(request: &Request) -> Vec<serde_json::Value> {
+    let query = request.query.get("q").map(|q| q.trim().to_lowercase()).filter(|q| !q.is_empty());
+    approved().unwrap_or_default().iter().filter_map(|name| summary(name, query.as_deref())).collect()
+}
+
+// A complete HTML page; also used by the static site export
+pub fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><meta name=\"viewport\" content=\"width=device-width\">\
+         <title>{title}</title><style>body{{max-width:46em;margin:2em auto;padding:0 1em;font-family:sans-serif;line-height:1.6}}\
+         .meta{{color:#666;font-size:.9em}}blockquote{{margin:.3em 0;color:#444}}pre{{white-space:pre-wrap;font-family:inherit}}</style>\
+         </head><body>{body}</body></html>",
+        title = escape(title),
+        body = body
+    )
+}
+
+fn page(title: &str, body: &str) -> Response {
+    Response::html(200, document(title, body))
+}
+
+fn index(request: &Request, heading: &str) -> Response {
+    let query = request.query.get("q").cloned().unwrap_or_default();
+    let mut body = format!(
+        "<h1>{}</h1><form><input name=\"q\" value=\"{}\" placeholder=\"Search\"> <button>Search</button></form><ul>",
+        escape(heading),
+        escape(&query)
+    );
+    for item in listing(request) {
+        let name = item["file_name"].as_str().unwrap_or_default();
+        let title = item["title"].as_str().unwrap_or(name);
+        body.push_str(&format!("<li><a href=\"/t/{}\">{}</a>", encode(name), escape(title)));
+        if let Some(speaker) = item["speaker"].as_str() {
+            body.push_str(&format!(" <span class=\"meta\">{}</span>", escape(speaker)));
+        }
+        for snippet in item["snippets"].as_array().into_iter().flatten() {
+            body.push_str(&format!("<blockquote>…{}…</blockquote>", escape(snippet.as_str().unwrap_or_default())));
+        }
+        body.push_str("</li>");
+    }
+    body.push_str("</ul>");
+    page(heading, &body)
+}
+
+fn read(name: &str) -> Result<String, Response> {
+    if !approved().unwrap_or_default().iter().any(|n| n == name) {
+        return Err(Response::error(404, "Not found"));
+    }
+    storage().read_to_string(transcript::transcript_path(name)).map_err(|_| Response::error(404, "Not found"))
+}
+
+fn route(request: &Request, heading: &str) -> Result<Response, Response> {
+    if request.method != "GET" {
+        return Err(Response::error(405, "Read-only"));
+    }
+
+    let segments: Vec<&str> = request.segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        [] => Ok(index(request, heading)),
+        ["t", name] => {
+            let content = read(name)?;
+            let meta = metadata::load(name).unwrap_or_default();
+            let title = meta.title.clone().unwrap_or_else(|| name.to_string());
+            let mut body = format!("<p><a href=\"/\">← {}</a></p><h1>{}</h1>", escape(heading), escape(&title));
+            let details: Vec<&str> = [meta.channel_name(), meta.upload_date.as_deref()].into_iter().flatten().collect();
+            if !details.is_empty() {
+                body.push_str(&format!("<p class=\"meta\">{}</p>", escape(&details.join(" · "))));
+            }
+            body.push_str(&format!("<pre>{}</pre>", escape(&content)));
+            Ok(page(&title, &body))
+        }
+        ["api", "transcripts"] => Ok(Response::json(200, &listing(request))),
+        ["api", "transcripts", name] => {
+            let content = read(name)?;
+            let mut value = summary(name, None).unwrap_or_default();
+            value["content"] = serde_json::json!(content);
+            Ok(Response::json(200, &value))
+        }
+        _ => Err(Response::error(404, "Not found")),
+    }
+}
+
+// Command to serve approved transcripts read-only, as HTML pages and JSON, without authentication
+#[tauri::command]
+pub fn start_sharing_server(bind: Option<String>) -> Result<serde_json::Value, String> {
+    let config = settings::load().sharing;
+    let bind = bind.unwrap_or(config.bind);
+    review_server::check_bind(&bind, config.allow_lan)?;
+
+    let mut server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(running) = server.as_ref().filter(|s| s.is_running()) {
+        return Err(format!("Sharing server already running on {}", running.address));
+    }
+
+    let heading = config.title;
+    let started = http::serve(&bind, move |request| route(request, &heading).unwrap_or_else(|response| response))?;
+    let address = started.address.to_string();
+    *server = Some(started);
+    Ok(serde_json::json!({ "running": true, "address": address, "transcripts": approved()?.len() }))
+}
+
+// Command to stop sharing transcripts
+#[tauri::command]
+pub fn stop_sharing_server() -> Result<(), String> {
+    if let Some(server) = SERVER.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        server.stop();
+    }
+    Ok(())
+}
+
+// Command to show whether the sharing server runs and where
+#[tauri::command]
+pub fn get_sharing_server_status() -> serde_json::Value {
+    let server = SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    let address = server.as_ref().filter(|s| s.is_running()).map(|s| s.address.to_string());
+    serde_json::json!({ "running": address.is_some(), "address": address })
+}
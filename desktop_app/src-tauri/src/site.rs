@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use crate::metadata;
+use crate::settings;
+use crate::sharing::{self, document, escape};
+use crate::state;
+use crate::transcript;
+use crate::storage::storage;
+
+// Client-side search over search-index.json; needs the site to be served over HTTP
+const SEARCH_SCRIPT: &str = r#"<script>
+fetch('search-index.json').then(r => r.json()).then(index => {
+  const input = document.getElementById('q');
+  const items = document.querySelectorAll('#transcripts li');
+  input.addEventListener('input', () => {
+    const q = input.value.trim().toLowerCase();
+    index.forEach((entry, i) => {
+      const hit = !q || entry.text.includes(q) || (entry.title || '').toLowerCase().includes(q);
+      items[i].style.display = hit ? '' : 'none';
+    });
+  });
+});
+</script>"#;
+
+fn write(path: PathBuf, content: String) -> Result<(), String> {
+    storage().write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// Command to export approved transcripts as a static HTML site with a search index
+#[tauri::command]
+pub fn export_site(path: String) -> Result<serde_json::Value, String> {
+    let root = Path::new(&path);
+    let _grant = storage().allow(root).map_err(|e| format!("Failed to access target: {}", e))?;
+    let pages = root.join("t");
+    storage().create_dir_all(&pages).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let heading = settings::load().sharing.title;
+    let mut list = String::new();
+    let mut index = Vec::new();
+    for name in sharing::approved()? {
+        let content = match storage().read_to_string(transcript::transcript_path(&name)) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        let meta = metadata::load(&name).unwrap_or_default();
+        let stem = Path::new(&name).file_stem().and_then(|s| s.to_str()).unwrap_or(&name).to_string();
+        let title = meta.title.clone().unwrap_or_else(|| stem.clone());
+        let page = format!("t/{}.html", sharing::encode(&stem));
+
+        let details: Vec<&str> = [meta.channel_name(), meta.upload_date.as_deref()].into_iter().flatten().collect();
+        let mut body = format!("<p><a href=\"../index.html\">← {}</a></p><h1>{}</h1>", escape(&heading), escape(&title));
+        if !details.is_empty() {
+            body.push_str(&format!("<p class=\"meta\">{}</p>", escape(&details.join(" · "))));
+        }
+        body.push_str(&format!("<pre>{}</pre>", escape(&content)));
+        write(pages.join(format!("{}.html", stem)), document(&title, &body))?;
+
+        list.push_str(&format!("<li><a href=\"{}\">{}</a>", page, escape(&title)));
+        if !details.is_empty() {
+            list.push_str(&format!(" <span class=\"meta\">{}</span>", escape(&details.join(" · "))));
+        }
+        list.push_str("</li>");
+
+        index.push(serde_json::json!({
+            "file_name": name,
+            "title": meta.title,
+            "speaker": meta.channel_name(),
+            "upload_date": meta.upload_date,
+            "page": page,
+            "text": content.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase(),
+        }));
+    }
+
+    let body = format!(
+        "<h1>{}</h1><input id=\"q\" placeholder=\"Search\"><ul id=\"transcripts\">{}</ul>{}",
+        escape(&heading),
+        list,
+        SEARCH_SCRIPT
+    );
+    write(root.join("index.html"), document(&heading, &body))?;
+    let search = serde_json::to_string(&index).map_err(|e| format!("Failed to serialize search index: {}", e))?;
+    write(root.join("search-index.json"), search)?;
+
+    Ok(serde_json::json!({ "path": path, "transcripts": index.len(), "exported_at": state::now() }))
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pipeline::Invocation;
+use crate::ratelimit;
+use crate::settings;
+
+// Channel and domain lists checked before a link is queued for download.
+// Non-empty allow lists admit only matching sources; block lists always win.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceLists {
+    pub allow_domains: Vec<String>,
+    pub block_domains: Vec<String>,
+    // Channel ids (UC...), @handles or channel names
+    pub allow_channels: Vec<String>,
+    pub block_channels: Vec<String>,
+}
+
+pub fn normalize(value: &str) -> String {
+    value.trim().trim_start_matches('@').to_lowercase()
+}
+
+// A domain entry matches the host itself and its subdomains
+fn domain_matches(host: &str, entry: &str) -> bool {
+    let entry = normalize(entry);
+    let entry = entry.strip_prefix("www.").unwrap_or(&entry);
+    host == entry || host.ends_with(&format!(".{}", entry))
+}
+
+// Channel identifiers that can be read from the URL itself (/@handle, /channel/UC..., /c/name, /user/name)
+pub fn channels_in_url(url: &str) -> Vec<String> {
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let segments: Vec<&str> = path.split(['/', '?', '#']).skip(1).collect();
+
+    let mut channels = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if let Some(handle) = segment.strip_prefix('@') {
+            channels.push(normalize(handle));
+        } else if ["channel", "c", "user"].contains(segment) {
+            if let Some(name) = segments.get(i + 1) {
+                channels.push(normalize(name));
+            }
+        }
+    }
+    channels
+}
+
+// Ask yt-dlp who published a video, without downloading it
+fn resolve_channels(url: &str) -> Vec<String> {
+    Invocation::new("yt-dlp")
+        .args([url, "--skip-download", "--no-playlist", "--print", "%(channel_id)s\n%(uploader_id)s\n%(channel)s"])
+        .in_workspace()
+        .run()
+        .ok()
+        .filter(|output| output.success)
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .map(normalize)
+                .filter(|l| !l.is_empty() && l != "na")
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl SourceLists {
+    // Reason a link may not be downloaded, or None when it is allowed
+    pub fn rejection(&self, url: &str) -> Option<String> {
+        let host = ratelimit::host(url);
+        if self.block_domains.iter().any(|d| domain_matches(&host, d)) {
+            return Some(format!("Domain {} is blocked", host));
+        }
+        if !self.allow_domains.is_empty() && !self.allow_domains.iter().any(|d| domain_matches(&host, d)) {
+            return Some(format!("Domain {} is not in the allow list", host));
+        }
+
+        if self.allow_channels.is_empty() && self.block_channels.is_empty() {
+            return None;
+        }
+
+        let mut channels = channels_in_url(url);
+        if channels.is_empty() {
+            channels = resolve_channels(url);
+        }
+        let listed = |list: &[String]| list.iter().map(|c| normalize(c)).any(|c| channels.contains(&c));
+
+        if listed(&self.block_channels) {
+            return Some(format!("Channel {} is blocked", channels.join(", ")));
+        }
+        if !self.allow_channels.is_empty() && !listed(&self.allow_channels) {
+            return Some(if channels.is_empty() {
+                "Channel could not be determined and an allow list is set".to_string()
+            } else {
+                format!("Channel {} is not in the allow list", channels.join(", "))
+            });
+        }
+        None
+    }
+}
+
+// Split links into those allowed by the configured lists and those rejected, with reasons
+pub fn screen(links: Vec<String>) -> (Vec<String>, Vec<(String, String)>) {
+    let lists = settings::load().sources;
+    let mut allowed = Vec::new();
+    let mut rejected = Vec::new();
+    for link in links {
+        match lists.rejection(&link) {
+            Some(reason) => rejected.push((link, reason)),
+            None => allowed.push(link),
+        }
+    }
+    (allowed, rejected)
+}
+
+// Command to check links against the allow and block lists without downloading them
+#[tauri::command]
+pub fn check_sources(links: Vec<String>) -> Vec<serde_json::Value> {
+    let lists = settings::load().sources;
+    links
+        .into_iter()
+        .map(|link| {
+            let reason = lists.rejection(&link);
+            serde_json::json!({ "link": link, "allowed": reason.is_none(), "reason": reason })
+        })
+        .collect()
+}
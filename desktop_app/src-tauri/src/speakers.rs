@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use tauri::AppHandle;
+
+use crate::audit;
+use crate::clips;
+use crate::events::{self, LogLevel, Stage};
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::scheduler::{self, Resource};
+use crate::state;
+
+// Minimum cosine similarity for a voice match
+const MATCH_THRESHOLD: f32 = 0.75;
+
+// Speech embedded per diarized voice, taken from its longest turns
+const SAMPLE_SECONDS: f64 = 120.0;
+
+// A known lecturer or qari
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerProfile {
+    pub id: String,
+    pub name: String,
+    pub channel: Option<String>,
+    pub voice_sample: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embedding: Vec<f32>,
+}
+
+// A speaker turn from diarization; `speaker` is the diarization label, e.g. "SPEAKER_01"
+#[derive(Debug, Clone, Deserialize)]
+pub struct Turn {
+    pub start: f64,
+    pub end: f64,
+    pub speaker: String,
+}
+
+// A diarized turn of a transcript and the known speaker it was matched to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerTurn {
+    pub start: f64,
+    pub end: f64,
+    pub label: String,
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeakerAssignment {
+    // Known speaker with the most speaking time, used for the transcript's dataset entries
+    pub speaker: Option<String>,
+    pub turns: Vec<SpeakerTurn>,
+}
+
+// Speaker turns of a clip, or None when the diarization helper is unavailable
+pub fn diarize(file_name: &str) -> Option<Vec<Turn>> {
+    let _permit = scheduler::acquire(Resource::Gpu).ok()?;
+    let output = Invocation::new("python3")
+        .args(["scripts/diarize.py", &clips::media_arg(metadata::stem(file_name), "mp3")])
+        .in_workspace()
+        .run()
+        .ok()?;
+    if !output.success {
+        return None;
+    }
+    serde_json::from_str(&output.stdout).ok()
+}
+
+// Compute a voice embedding for an audio file with the helper script, from the given
+// (start, end) spans only when there are any
+fn embed(audio: &Path, spans: &[(f64, f64)]) -> Result<Vec<f32>, String> {
+    let _permit = scheduler::acquire(Resource::Gpu)?;
+    let output = Invocation::new("python3")
+        .args(["scripts/speaker_embedding.py", &audio.to_string_lossy()])
+        .args(spans.iter().map(|(start, end)| format!("{}:{}", start, end)))
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute speaker embedding: {}", e))?;
+
+    if !output.success {
+        return Err(output.stderr);
+    }
+
+    serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse speaker embedding: {}", e))
+}
+
+// Longest turns of a diarized voice, up to SAMPLE_SECONDS of speech
+fn sample_spans(turns: &[SpeakerTurn], label: &str) -> Vec<(f64, f64)> {
+    let mut spans: Vec<(f64, f64)> = turns.iter().filter(|t| t.label == label).map(|t| (t.start, t.end)).collect();
+    spans.sort_by(|a, b| (b.1 - b.0).total_cmp(&(a.1 - a.0)));
+
+    let mut total = 0.0;
+    spans.retain(|(start, end)| {
+        let keep = total < SAMPLE_SECONDS;
+        total += end - start;
+        keep
+    });
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    spans
+}
+
+// Best registered voice for an embedding above the match threshold
+fn closest(speakers: &[SpeakerProfile], embedding: &[f32]) -> Option<String> {
+    speakers
+        .iter()
+        .filter(|s| !s.embedding.is_empty())
+        .map(|s| (s, cosine_similarity(&s.embedding, embedding)))
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(s, _)| s.id.clone())
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+// Command to register a speaker profile, embedding the voice sample if given
+#[tauri::command]
+pub fn register_speaker(
+    name: String,
+    channel: Option<String>,
+    voice_sample: Option<String>,
+) -> Result<SpeakerProfile, String> {
+    let embedding = match &voice_sample {
+        Some(sample) => embed(Path::new(sample), &[])?,
+        None => Vec::new(),
+    };
+
+    let id = name
+        .trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+    if id.is_empty() {
+        return Err("Speaker name is required".to_string());
+    }
+
+    let profile = SpeakerProfile { id, name, channel, voice_sample, embedding };
+    state::update(|state| {
+        state.speakers.retain(|s| s.id != profile.id);
+        state.speakers.push(profile.clone());
+    })?;
+    audit::record("speaker.register", &profile.id, serde_json::json!({ "name": profile.name }));
+
+    Ok(profile)
+}
+
+// Command to list registered speaker profiles
+#[tauri::command]
+pub fn get_speakers() -> Result<Vec<SpeakerProfile>, String> {
+    Ok(state::load()?.speakers)
+}
+
+// Command to attribute each diarized turn of a transcript to a known speaker: a profile bound to
+// the clip's channel takes the voice that talks the longest, the other voices are matched by
+// embedding. Without diarization the whole clip counts as one turn.
+#[tauri::command]
+pub fn assign_speaker(app: AppHandle, file_name: String) -> Result<SpeakerAssignment, String> {
+    let speakers = state::load()?.speakers;
+    let clip = clips::media_file(metadata::stem(&file_name), "mp3");
+
+    let diarized = diarize(&file_name);
+    let mut turns: Vec<SpeakerTurn> = match &diarized {
+        Some(turns) => turns
+            .iter()
+            .map(|t| SpeakerTurn { start: t.start, end: t.end, label: t.speaker.clone(), speaker: None })
+            .collect(),
+        None => {
+            let end = metadata::load(&file_name).and_then(|m| m.duration).unwrap_or(0.0);
+            vec![SpeakerTurn { start: 0.0, end, label: "SPEAKER_00".to_string(), speaker: None }]
+        }
+    };
+
+    // Diarization labels, longest speaking time first
+    let mut talk: BTreeMap<String, f64> = BTreeMap::new();
+    for turn in &turns {
+        *talk.entry(turn.label.clone()).or_insert(0.0) += turn.end - turn.start;
+    }
+    let mut labels: Vec<(String, f64)> = talk.into_iter().collect();
+    labels.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let channel = metadata::load(&file_name).and_then(|m| m.channel_name().map(str::to_string));
+    let bound = speakers.iter().find(|s| s.channel.is_some() && s.channel == channel).map(|s| s.id.clone());
+    let voices = speakers.iter().any(|s| !s.embedding.is_empty());
+    if voices {
+        events::log(&app, Stage::Analysis, LogLevel::Info, &format!("Matching voices for {}", file_name));
+    }
+
+    let mut matched: BTreeMap<String, String> = BTreeMap::new();
+    for (index, (label, _)) in labels.iter().enumerate() {
+        let speaker = match &bound {
+            Some(id) if index == 0 => Some(id.clone()),
+            _ if voices => {
+                let spans = if diarized.is_some() { sample_spans(&turns, label) } else { Vec::new() };
+                closest(&speakers, &embed(&clip, &spans)?)
+            }
+            _ => None,
+        };
+        if let Some(speaker) = speaker {
+            matched.insert(label.clone(), speaker);
+        }
+    }
+    for turn in &mut turns {
+        turn.speaker = matched.get(&turn.label).cloned();
+    }
+
+    // The transcript as a whole belongs to the known speaker who talks the longest
+    let mut known: BTreeMap<&str, f64> = BTreeMap::new();
+    for turn in &turns {
+        if let Some(speaker) = &turn.speaker {
+            *known.entry(speaker.as_str()).or_insert(0.0) += turn.end - turn.start;
+        }
+    }
+    let main = known.into_iter().max_by(|a, b| a.1.total_cmp(&b.1)).map(|(speaker, _)| speaker.to_string());
+
+    if main.is_none() {
+        let message = format!("No known speaker matched {}", file_name);
+        events::log(&app, Stage::Analysis, LogLevel::Warn, &message);
+    }
+
+    state::update(|state| {
+        state.speaker_turns.insert(file_name.clone(), turns.clone());
+        match &main {
+            Some(id) => {
+                state.transcript_speakers.insert(file_name.clone(), id.clone());
+            }
+            None => {
+                state.transcript_speakers.remove(&file_name);
+            }
+        }
+    })?;
+    audit::record(
+        "speaker.assign",
+        &file_name,
+        serde_json::json!({ "speaker": main, "voices": labels.len(), "matched": matched }),
+    );
+
+    Ok(SpeakerAssignment { speaker: main, turns })
+}
+
+// Command to show the speaker turns stored for a transcript by assign_speaker
+#[tauri::command]
+pub fn get_speaker_turns(file_name: String) -> Result<Vec<SpeakerTurn>, String> {
+    Ok(state::load()?.speaker_turns.remove(&file_name).unwrap_or_default())
+}
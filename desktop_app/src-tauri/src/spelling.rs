@@ -0,0 +1,205 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::integrity;
+use crate::journal;
+use crate::pipeline::Invocation;
+use crate::settings;
+use crate::storage::storage;
+use crate::transcript;
+
+// Frequent whisper mis-hearings of Islamic terms, applied before user corrections
+const DEFAULT_CORRECTIONS: &[(&str, &str)] = &[
+    ("tukwa", "taqwa"),
+    ("takwa", "taqwa"),
+    ("haran", "haram"),
+    ("subhanallah", "SubhanAllah"),
+    ("inshallah", "in sha Allah"),
+    ("mashallah", "masha Allah"),
+    ("hadees", "hadith"),
+    ("sahaba", "Sahabah"),
+    ("ramzan", "Ramadan"),
+];
+
+// A token flagged by the correction list or the spell checker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub token: String,
+    // Character offset of the token in the transcript
+    pub offset: usize,
+    pub suggestions: Vec<String>,
+    // "correction" for known ASR errors, "hunspell" for dictionary misses
+    pub source: String,
+}
+
+// A suggestion the reviewer accepted
+#[derive(Debug, Clone, Deserialize)]
+pub struct Replacement {
+    pub token: String,
+    pub offset: usize,
+    pub replacement: String,
+}
+
+// Words with their character offsets
+fn tokenize(text: &str) -> Vec<(usize, String)> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+
+    for (index, c) in text.chars().enumerate() {
+        if c.is_alphanumeric() || (c == '\'' && !current.is_empty()) {
+            if current.is_empty() {
+                start = index;
+            }
+            current.push(c);
+        } else if !current.is_empty() {
+            tokens.push((start, std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((start, current));
+    }
+
+    tokens
+}
+
+// Query hunspell in ispell pipe mode; None when hunspell is not installed
+fn hunspell_check(words: &BTreeSet<String>, dictionaries: &[String]) -> Option<BTreeMap<String, Vec<String>>> {
+    // A leading '^' stops hunspell from reading a line as a command
+    let input: String = words.iter().map(|w| format!("^{}\n", w)).collect();
+    let output = Invocation::new("hunspell")
+        .args(["-a", "-d", &dictionaries.join(",")])
+        .stdin(input)
+        .run()
+        .ok()?;
+    if !output.success {
+        return None;
+    }
+
+    // Each input line yields one result line followed by a blank line
+    let results = output.stdout.lines().skip(1).filter(|l| !l.is_empty());
+
+    let mut misses = BTreeMap::new();
+    for (word, result) in words.iter().zip(results) {
+        if let Some(rest) = result.strip_prefix("& ") {
+            let suggestions = rest
+                .split_once(": ")
+                .map(|(_, list)| list.split(", ").map(str::to_string).collect())
+                .unwrap_or_default();
+            misses.insert(word.clone(), suggestions);
+        } else if result.starts_with('#') {
+            misses.insert(word.clone(), Vec::new());
+        }
+    }
+
+    Some(misses)
+}
+
+// Flag known ASR errors and, when hunspell is available, dictionary misses
+pub fn suggest(text: &str) -> (Vec<Suggestion>, bool) {
+    let settings = settings::load();
+    let mut corrections: BTreeMap<String, String> = DEFAULT_CORRECTIONS
+        .iter()
+        .map(|(wrong, right)| (wrong.to_string(), right.to_string()))
+        .collect();
+    corrections.extend(settings.corrections.into_iter().map(|(k, v)| (k.to_lowercase(), v)));
+
+    let tokens = tokenize(text);
+    let words: BTreeSet<String> = tokens
+        .iter()
+        .map(|(_, w)| w.clone())
+        .filter(|w| !corrections.contains_key(&w.to_lowercase()) && !w.chars().all(|c| c.is_numeric()))
+        .collect();
+
+    let misses = hunspell_check(&words, &settings.spellcheck_dictionaries);
+    let hunspell_available = misses.is_some();
+    let misses = misses.unwrap_or_default();
+
+    let suggestions = tokens
+        .into_iter()
+        .filter_map(|(offset, token)| {
+            if let Some(right) = corrections.get(&token.to_lowercase()) {
+                return Some(Suggestion {
+                    token,
+                    offset,
+                    suggestions: vec![right.clone()],
+                    source: "correction".to_string(),
+                });
+            }
+            misses.get(&token).map(|suggestions| Suggestion {
+                suggestions: suggestions.clone(),
+                token,
+                offset,
+                source: "hunspell".to_string(),
+            })
+        })
+        .collect();
+
+    (suggestions, hunspell_available)
+}
+
+// Command to list flagged tokens in a transcript with proposed fixes
+#[tauri::command]
+pub fn get_suggestions(file_name: String) -> Result<serde_json::Value, String> {
+    let path = transcript::transcript_path(&file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let (suggestions, hunspell_available) = suggest(&content);
+
+    Ok(serde_json::json!({
+        "file_name": file_name,
+        "suggestions": suggestions,
+        "hunspell_available": hunspell_available
+    }))
+}
+
+// Command to apply accepted suggestions in one write; stale offsets are skipped
+#[tauri::command]
+pub fn apply_suggestions(file_name: String, replacements: Vec<Replacement>) -> Result<serde_json::Value, String> {
+    let path = transcript::transcript_path(&file_name);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let mut chars: Vec<char> = content.chars().collect();
+
+    // Apply from the end so earlier offsets stay valid
+    let mut replacements = replacements;
+    replacements.sort_by_key(|r| std::cmp::Reverse(r.offset));
+
+    let mut applied = 0;
+    let mut skipped = 0;
+    for replacement in replacements {
+        let end = replacement.offset + replacement.token.chars().count();
+        let matches = end <= chars.len()
+            && chars[replacement.offset..end].iter().copied().eq(replacement.token.chars());
+
+        if matches {
+            chars.splice(replacement.offset..end, replacement.replacement.chars());
+            applied += 1;
+        } else {
+            skipped += 1;
+        }
+    }
+
+    let updated: String = chars.into_iter().collect();
+
+    let mut journal = journal::begin("replace", &format!("Apply {} suggestions to {}", applied, file_name))?;
+    journal.preserve(&path)?;
+    storage().write(&path, &updated).map_err(|e| format!("Failed to write transcript: {}", e))?;
+    journal.commit()?;
+    integrity::record(&path);
+
+    Ok(serde_json::json!({
+        "applied": applied,
+        "skipped": skipped,
+        "revision": transcript::snapshot(&file_name, &updated)
+    }))
+}
@@ -0,0 +1,232 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api_tokens::ApiToken;
+use crate::autorebuild::AutoRebuildState;
+use crate::citations::Citation;
+use crate::clips::ClipNotes;
+use crate::coldstorage::ColdItem;
+use crate::compress::ArchivedClip;
+use crate::dataset::EntryCount;
+use crate::flagging::SegmentFlag;
+use crate::groundtruth::GroundTruthScore;
+use crate::history::DownloadRecord;
+use crate::jobs::JobRecord;
+use crate::manifest::ExportRecord;
+use crate::optouts::OptOut;
+use crate::permissions::ChannelPermission;
+use crate::review_server::{Claim, EditRecord};
+use crate::screening::ScreeningResult;
+use crate::speakers::{SpeakerProfile, SpeakerTurn};
+use crate::telemetry::TelemetryCounters;
+use crate::transcript::TranscriptSummary;
+use crate::workspace;
+use crate::storage::storage;
+
+// Serializes read-modify-write cycles on the state file
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+
+// Persistent pipeline state that does not belong in the output files themselves
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineState {
+    // Tags per transcript file name
+    pub transcript_tags: BTreeMap<String, Vec<String>>,
+    // Tags per dataset entry id
+    pub entry_tags: BTreeMap<String, Vec<String>>,
+    // Citations detected per transcript file name
+    pub citations: BTreeMap<String, Vec<Citation>>,
+    pub speakers: Vec<SpeakerProfile>,
+    // Main speaker id per transcript file name: the known speaker with the most speaking time
+    pub transcript_speakers: BTreeMap<String, String>,
+    // Diarized turns and their known speakers per transcript file name
+    pub speaker_turns: BTreeMap<String, Vec<SpeakerTurn>>,
+    // Listing data per transcript file name, invalidated by mtime and size
+    pub transcript_cache: BTreeMap<String, TranscriptSummary>,
+    // Review status per transcript file name; missing means "pending"
+    pub review_status: BTreeMap<String, String>,
+    // Curator rating and notes per clip stem
+    pub clip_notes: BTreeMap<String, ClipNotes>,
+    // Transcripts whose entries are left out of generated datasets
+    pub excluded_from_dataset: BTreeSet<String>,
+    // Opt-in usage counters, see telemetry.rs
+    pub telemetry: TelemetryCounters,
+    // Entry count per dataset file path, see dataset::entry_count
+    pub entry_counts: BTreeMap<String, EntryCount>,
+    // Every finished download, including ones whose clips were deleted later
+    pub download_history: Vec<DownloadRecord>,
+    // Content screening outcome per transcript file name; only matching transcripts appear
+    pub screening: BTreeMap<String, ScreeningResult>,
+    // Manifest of every dataset build and subset export, oldest first
+    pub export_history: Vec<ExportRecord>,
+    // What the last automatic dataset rebuild was made from
+    pub auto_rebuild: AutoRebuildState,
+    // LAN reviewers' claims per transcript file name
+    pub review_claims: BTreeMap<String, Claim>,
+    // Who changed which transcript, oldest first
+    pub edit_log: Vec<EditRecord>,
+    // When outputs of the old scripts were backfilled, see migrate.rs
+    pub migrated_at: Option<u64>,
+    // Automatic transcriptions scored against ground-truth transcripts, oldest first
+    pub ground_truth_scores: Vec<GroundTruthScore>,
+    // Every finished download and transcription job, oldest first
+    pub job_history: Vec<JobRecord>,
+    // Server-mode API tokens, stored hashed
+    pub api_tokens: Vec<ApiToken>,
+    // Segments matching flagged terms per transcript file name, see flagging.rs
+    pub segment_flags: BTreeMap<String, Vec<SegmentFlag>>,
+    // Sources excluded from every dataset, e.g. after takedown requests, see optouts.rs
+    pub opt_outs: Vec<OptOut>,
+    // Permission from channel owners by normalized channel name, see permissions.rs
+    pub channel_permissions: BTreeMap<String, ChannelPermission>,
+    // Clips replaced by compact archive copies, by original clip file name, see compress.rs
+    pub archived_clips: BTreeMap<String, ArchivedClip>,
+    // Items whose audio was moved to cold storage, by clip stem, see coldstorage.rs
+    pub cold_items: BTreeMap<String, ColdItem>,
+}
+
+// Path of the state store file
+pub fn state_path() -> PathBuf {
+    storage().path("output/state.json")
+}
+
+// Histories older than the newest this many records are dropped on save
+pub const HISTORY_LIMIT: usize = 5000;
+
+// Load the state store, starting fresh only if it is missing. A store that does not parse
+// is copied aside and reported, so that no update ever replaces it with an empty one.
+pub fn load() -> Result<PipelineState, String> {
+    let path = state_path();
+    if !storage().exists(&path) {
+        return Ok(PipelineState::default());
+    }
+
+    let content = storage().read_to_string(&path).map_err(|e| format!("Failed to read state: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| {
+        let backup = backup_path(&path);
+        if !storage().exists(&backup) {
+            storage().copy(&path, &backup).ok();
+        }
+        format!(
+            "Failed to parse state: {} (a copy is kept at {}; repair or remove {} to continue)",
+            e,
+            backup.display(),
+            path.display()
+        )
+    })
+}
+
+// One backup per version of an unparseable store, named after its modification time
+fn backup_path(path: &Path) -> PathBuf {
+    let modified = storage()
+        .metadata(path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs());
+    path.with_extension(format!("json.{}.bak", modified))
+}
+
+fn trim<T>(history: &mut Vec<T>) {
+    if history.len() > HISTORY_LIMIT {
+        history.drain(..history.len() - HISTORY_LIMIT);
+    }
+}
+
+fn save(state: &PipelineState) -> Result<(), String> {
+    workspace::ensure_writable()?;
+    let path = state_path();
+    if let Some(parent) = path.parent() {
+        storage().create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize state: {}", e))?;
+
+    // Write to a temporary file first so a crash never leaves a truncated store
+    let temp = path.with_extension("json.tmp");
+    storage().write(&temp, content).map_err(|e| format!("Failed to write state: {}", e))?;
+    storage().rename(&temp, &path).map_err(|e| format!("Failed to write state: {}", e))
+}
+
+// Apply a change to the state store and persist it
+pub fn update<T>(f: impl FnOnce(&mut PipelineState) -> T) -> Result<T, String> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut state = load()?;
+    let result = f(&mut state);
+    trim(&mut state.download_history);
+    trim(&mut state.job_history);
+    save(&state)?;
+    Ok(result)
+}
+
+// Current time as unix seconds, used for timestamps stored in sidecars and the state store
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{MutexGuard, Once};
+
+    static WORKSPACE: Once = Once::new();
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    // The workspace is process-wide, so tests holding it take turns on the store
+    fn workspace() -> MutexGuard<'static, ()> {
+        WORKSPACE.call_once(|| {
+            let root = std::env::temp_dir().join(format!("akhi-state-test-{}", std::process::id()));
+            std::fs::create_dir_all(root.join("output")).unwrap();
+            assert!(crate::portable::set_pipeline_dir(root));
+        });
+        SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_store(content: &str) {
+        std::fs::write(state_path(), content).unwrap();
+        for entry in std::fs::read_dir(storage().path("output")).unwrap() {
+            let path = entry.unwrap().path();
+            if path.to_string_lossy().ends_with(".bak") {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn older_stores_load_with_defaults_for_newer_fields() {
+        let _workspace = workspace();
+        write_store(r#"{"review_status": {"Lecture 1.txt": "approved"}}"#);
+
+        let state = load().unwrap();
+        assert_eq!(state.review_status["Lecture 1.txt"], "approved");
+        assert!(state.job_history.is_empty() && state.migrated_at.is_none());
+    }
+
+    #[test]
+    fn unparseable_store_is_backed_up_and_never_overwritten() {
+        let _workspace = workspace();
+        write_store(r#"{"review_status": {"Lecture 1.txt": "#);
+
+        let error = load().unwrap_err();
+        assert!(error.starts_with("Failed to parse state"));
+        let backup = backup_path(&state_path());
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), r#"{"review_status": {"Lecture 1.txt": "#);
+        assert!(update(|state| state.review_status.clear()).is_err());
+        assert_eq!(std::fs::read_to_string(state_path()).unwrap(), r#"{"review_status": {"Lecture 1.txt": "#);
+    }
+
+    #[test]
+    fn histories_keep_the_newest_records() {
+        let mut history: Vec<usize> = (0..HISTORY_LIMIT + 10).collect();
+        trim(&mut history);
+        assert_eq!(history.len(), HISTORY_LIMIT);
+        assert_eq!(history[0], 10);
+    }
+}
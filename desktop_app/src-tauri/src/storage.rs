@@ -0,0 +1,264 @@
+use std::fs::{File, Metadata, ReadDir};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::portable;
+
+static STORAGE: OnceLock<Storage> = OnceLock::new();
+
+// Owns the workspace root and is the only component that touches the filesystem.
+// Every path is checked against the workspace, plus any file the running operation was given.
+pub struct Storage {
+    root: PathBuf,
+    // Paths outside the workspace granted to running operations, e.g. export targets chosen in a dialog
+    allowed: Mutex<Vec<PathBuf>>,
+}
+
+// Access to a path outside the workspace, revoked when dropped
+pub struct Grant<'a> {
+    storage: &'a Storage,
+    path: PathBuf,
+}
+
+impl Drop for Grant<'_> {
+    fn drop(&mut self) {
+        let mut allowed = self.storage.allowed.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(at) = allowed.iter().position(|p| *p == self.path) {
+            allowed.remove(at);
+        }
+    }
+}
+
+// The workspace storage service
+pub fn storage() -> &'static Storage {
+    STORAGE.get_or_init(|| Storage::new(portable::pipeline_dir()))
+}
+
+// Absolute, lexically normalized form of a path; does not require it to exist
+fn normalize(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+// Absolute path with symlinks resolved: the deepest existing ancestor is canonicalized and the
+// rest, which does not exist yet, is normalized lexically below it
+fn resolve(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+
+    let mut existing = absolute.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return normalize(&missing.iter().rev().fold(canonical, |p, name| p.join(name)));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return normalize(&absolute),
+        }
+    }
+}
+
+fn out_of_scope(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!("{} is outside the workspace", path.display()),
+    )
+}
+
+impl Storage {
+    pub fn new(root: &Path) -> Self {
+        Storage { root: root.to_path_buf(), allowed: Mutex::new(Vec::new()) }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    // Path of a file or directory relative to the workspace root
+    pub fn path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    // Grant access to a path outside the workspace (and everything below it) until the grant
+    // is dropped; a filesystem root is never granted
+    pub fn allow(&self, path: impl AsRef<Path>) -> io::Result<Grant<'_>> {
+        let path = resolve(path.as_ref());
+        if path.parent().is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is a filesystem root", path.display()),
+            ));
+        }
+        self.allowed.lock().unwrap_or_else(|e| e.into_inner()).push(path.clone());
+        Ok(Grant { storage: self, path })
+    }
+
+    pub fn in_scope(&self, path: impl AsRef<Path>) -> bool {
+        let path = resolve(path.as_ref());
+        path.starts_with(resolve(&self.root))
+            || self
+                .allowed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .any(|allowed| path.starts_with(allowed))
+    }
+
+    fn check(&self, path: &Path) -> io::Result<()> {
+        if self.in_scope(path) {
+            Ok(())
+        } else {
+            Err(out_of_scope(path))
+        }
+    }
+
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        self.in_scope(&path) && path.as_ref().exists()
+    }
+
+    pub fn is_file(&self, path: impl AsRef<Path>) -> bool {
+        self.in_scope(&path) && path.as_ref().is_file()
+    }
+
+    pub fn is_dir(&self, path: impl AsRef<Path>) -> bool {
+        self.in_scope(&path) && path.as_ref().is_dir()
+    }
+
+    pub fn read_to_string(&self, path: impl AsRef<Path>) -> io::Result<String> {
+        self.check(path.as_ref())?;
+        std::fs::read_to_string(path)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        self.check(path.as_ref())?;
+        std::fs::write(path, contents)
+    }
+
+    pub fn open(&self, path: impl AsRef<Path>) -> io::Result<File> {
+        self.check(path.as_ref())?;
+        File::open(path)
+    }
+
+    // Create a file that must not exist yet
+    pub fn create_new(&self, path: impl AsRef<Path>) -> io::Result<File> {
+        self.check(path.as_ref())?;
+        std::fs::OpenOptions::new().write(true).create_new(true).open(path)
+    }
+
+    // Open a file for appending, creating it if needed
+    pub fn append(&self, path: impl AsRef<Path>) -> io::Result<File> {
+        self.check(path.as_ref())?;
+        std::fs::OpenOptions::new().append(true).create(true).open(path)
+    }
+
+    pub fn metadata(&self, path: impl AsRef<Path>) -> io::Result<Metadata> {
+        self.check(path.as_ref())?;
+        std::fs::metadata(path)
+    }
+
+    pub fn read_dir(&self, path: impl AsRef<Path>) -> io::Result<ReadDir> {
+        self.check(path.as_ref())?;
+        std::fs::read_dir(path)
+    }
+
+    pub fn create_dir_all(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.check(path.as_ref())?;
+        std::fs::create_dir_all(path)
+    }
+
+    pub fn copy(&self, from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+        self.check(from.as_ref())?;
+        self.check(to.as_ref())?;
+        std::fs::copy(from, to)
+    }
+
+    pub fn rename(&self, from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+        self.check(from.as_ref())?;
+        self.check(to.as_ref())?;
+        std::fs::rename(from, to)
+    }
+
+    pub fn remove_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.check(path.as_ref())?;
+        std::fs::remove_file(path)
+    }
+
+    pub fn remove_dir_all(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.check(path.as_ref())?;
+        std::fs::remove_dir_all(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("akhi-storage-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn grants_end_when_dropped() {
+        let storage = Storage::new(&temp_dir("workspace-grant"));
+        let outside = temp_dir("outside-grant");
+        assert!(!storage.in_scope(outside.join("export.json")));
+
+        let grant = storage.allow(&outside).unwrap();
+        assert!(storage.in_scope(outside.join("export.json")));
+        drop(grant);
+        assert!(!storage.in_scope(outside.join("export.json")));
+    }
+
+    #[test]
+    fn filesystem_roots_are_never_granted() {
+        let storage = Storage::new(&temp_dir("workspace-root"));
+        assert!(storage.allow("/").is_err());
+    }
+
+    #[test]
+    fn parent_components_cannot_leave_the_workspace() {
+        let root = temp_dir("workspace-parent");
+        let storage = Storage::new(&root);
+        assert!(storage.in_scope(root.join("output/../output/new.txt")));
+        assert!(!storage.in_scope(root.join("output/../../escaped.txt")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_cannot_leave_the_workspace() {
+        let root = temp_dir("workspace-link");
+        let outside = temp_dir("outside-link");
+        let link = root.join("link");
+        std::fs::remove_file(&link).ok();
+        std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+        let storage = Storage::new(&root);
+        assert!(!storage.in_scope(link.join("secret.txt")));
+        assert!(!storage.in_scope(&link));
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use crate::glossary;
+use crate::metadata;
+use crate::sanitize;
+use crate::settings;
+use crate::state;
+use crate::transcript::{self, Segment};
+use crate::translation;
+use crate::storage::storage;
+
+// Characters per caption line before wrapping, as recommended for YouTube captions
+const LINE_CHARS: usize = 42;
+
+// Segment texts as corrected in the reviewed transcript. Whisper writes one line per
+// segment, so the edited text is used whenever the line structure survived review.
+pub fn reviewed_segments(file_name: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = transcript::load_segments(file_name)
+        .ok_or_else(|| format!("No timed segments for transcript: {}", file_name))?;
+    let content = storage().read_to_string(transcript::transcript_path(file_name))
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() == segments.len() {
+        for (segment, line) in segments.iter_mut().zip(lines) {
+            segment.text = line.to_string();
+        }
+    }
+    Ok(segments)
+}
+
+// Clean caption text: control characters, glossary spellings, whitespace and line length
+fn clean(text: &str, glossary: &[glossary::GlossaryTerm]) -> String {
+    let (text, _) = glossary::apply(&sanitize::fix(text), glossary);
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    let mut lines: Vec<String> = vec![String::new()];
+    for word in words {
+        let line = lines.last_mut().expect("at least one line");
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > LINE_CHARS {
+            lines.push(word.to_string());
+        } else {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+    }
+    lines.join("\n")
+}
+
+// hh:mm:ss,mmm for SRT or hh:mm:ss.mmm for VTT
+fn timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+pub fn render(segments: &[Segment], format: &str) -> String {
+    let glossary = settings::load().glossary;
+    let separator = if format == "vtt" { '.' } else { ',' };
+    let mut output = if format == "vtt" { String::from("WEBVTT\n\n") } else { String::new() };
+
+    let cues = segments
+        .iter()
+        .map(|s| (s, clean(&s.text, &glossary)))
+        .filter(|(_, text)| !text.is_empty());
+    for (index, (segment, text)) in cues.enumerate() {
+        if format == "srt" {
+            output.push_str(&format!("{}\n", index + 1));
+        }
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(segment.start, separator),
+            timestamp(segment.end.max(segment.start), separator),
+            text
+        ));
+    }
+    output
+}
+
+fn write(path: &Path, content: &str) -> Result<String, String> {
+    storage().write(path, content).map_err(|e| format!("Failed to write subtitles: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// Command to export captions of an approved transcript as SRT or VTT, with an optional
+// English track from translate_transcript, into `path` or output/subtitles
+#[tauri::command]
+pub fn export_subtitles(
+    file: String,
+    format: String,
+    path: Option<String>,
+    include_translation: Option<bool>,
+    allow_unreviewed: Option<bool>,
+) -> Result<Vec<String>, String> {
+    if format != "srt" && format != "vtt" {
+        return Err(format!("Unknown subtitle format: {}", format));
+    }
+
+    let status = transcript::review_status(&state::load()?, &file);
+    if status != "approved" && !allow_unreviewed.unwrap_or(false) {
+        return Err(format!("Transcript {} is {}; approve it before exporting captions", file, status));
+    }
+
+    // The export directory is picked by the user, so it is granted for this export only
+    let _grant = path.as_ref().map(|p| storage().allow(p)).transpose().map_err(|e| format!("Failed to access target: {}", e))?;
+    let target_dir = match &path {
+        Some(path) => PathBuf::from(path),
+        None => storage().path("output/subtitles"),
+    };
+    storage().create_dir_all(&target_dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let stem = metadata::stem(&file);
+    let mut written = vec![write(
+        &target_dir.join(format!("{}.{}", stem, format)),
+        &render(&reviewed_segments(&file)?, &format),
+    )?];
+
+    if include_translation.unwrap_or(false) {
+        let translated = translation::load_translation(&file)
+            .ok_or_else(|| format!("No translation for transcript: {}", file))?;
+        written.push(write(&target_dir.join(format!("{}.en.{}", stem, format)), &render(&translated, &format))?);
+    }
+
+    Ok(written)
+}
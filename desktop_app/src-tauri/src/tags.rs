@@ -0,0 +1,71 @@
+use crate::audit;
+use crate::dataset;
+use crate::state;
+use crate::storage::storage;
+
+// Normalize user-entered tags: trimmed, lowercase, unique, sorted
+pub fn normalize(tags: Vec<String>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+// Command to replace the tags of a transcript or dataset entry
+#[tauri::command]
+pub fn set_tags(kind: String, id: String, tags: Vec<String>) -> Result<Vec<String>, String> {
+    let tags = normalize(tags);
+
+    state::update(|state| {
+        let map = match kind.as_str() {
+            "transcript" => &mut state.transcript_tags,
+            "entry" => &mut state.entry_tags,
+            _ => return Err(format!("Unknown tag target: {}", kind)),
+        };
+
+        if tags.is_empty() {
+            map.remove(&id);
+        } else {
+            map.insert(id.clone(), tags.clone());
+        }
+        Ok(())
+    })??;
+    audit::record("tags.set", &format!("{}:{}", kind, id), serde_json::json!({ "tags": tags }));
+    Ok(tags)
+}
+
+// Command to list transcripts and dataset entries carrying a tag
+#[tauri::command]
+pub fn get_by_tag(tag: String, dataset: Option<String>) -> Result<serde_json::Value, String> {
+    let tag = tag.trim().to_lowercase();
+    let state = state::load()?;
+
+    let transcripts: Vec<&String> = state
+        .transcript_tags
+        .iter()
+        .filter(|(_, tags)| tags.contains(&tag))
+        .map(|(name, _)| name)
+        .collect();
+
+    let mut entries = Vec::new();
+    let path = dataset::dataset_path(dataset.as_deref())?;
+    if storage().exists(&path) {
+        for mut entry in dataset::load_entries(&path)? {
+            dataset::annotate(&mut entry);
+            dataset::attach_tags(&mut entry, &state);
+            if entry.tags.contains(&tag) {
+                entries.push(entry);
+            }
+        }
+    }
+
+    Ok(serde_json::json!({
+        "tag": tag,
+        "transcripts": transcripts,
+        "entries": entries
+    }))
+}
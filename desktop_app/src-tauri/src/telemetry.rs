@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::Stage;
+use crate::pipeline::Invocation;
+use crate::settings;
+use crate::state;
+
+// Aggregate counters only: feature names and error categories, never content, paths or messages
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryCounters {
+    // Unix seconds of the first event since the last submission
+    pub since: Option<u64>,
+    pub usage: BTreeMap<String, u64>,
+    // Keyed by "<stage>.<category>"
+    pub errors: BTreeMap<String, u64>,
+}
+
+fn stage_name(stage: Stage) -> &'static str {
+    match stage {
+        Stage::Download => "download",
+        Stage::Transcribe => "transcribe",
+        Stage::Dataset => "dataset",
+        Stage::Analysis => "analysis",
+    }
+}
+
+// Reduce an error message to a coarse category so no user data leaves the machine
+pub fn categorize(message: &str) -> &'static str {
+    let message = message.to_lowercase();
+    if message.contains("no such file") || message.contains("not found") {
+        "not_found"
+    } else if message.contains("permission") {
+        "permission"
+    } else if message.contains("timed out") || message.contains("timeout") {
+        "timeout"
+    } else if message.contains("http") || message.contains("network") || message.contains("connection") {
+        "network"
+    } else if message.contains("failed to execute") {
+        "tool_missing"
+    } else {
+        "other"
+    }
+}
+
+fn bump(map_key: impl FnOnce(&mut TelemetryCounters) -> &mut BTreeMap<String, u64>, key: String) {
+    if !settings::load().telemetry_enabled {
+        return;
+    }
+
+    // Telemetry must never interfere with the pipeline, so write failures are ignored
+    state::update(|s| {
+        s.telemetry.since.get_or_insert_with(state::now);
+        *map_key(&mut s.telemetry).entry(key).or_insert(0) += 1;
+    })
+    .ok();
+}
+
+// Count the use of a feature, when telemetry is enabled
+pub fn record_usage(feature: &str) {
+    bump(|t| &mut t.usage, feature.to_string());
+}
+
+// Count a failure in a pipeline stage, when telemetry is enabled
+pub fn record_error(stage: Stage, message: &str) {
+    bump(|t| &mut t.errors, format!("{}.{}", stage_name(stage), categorize(message)));
+}
+
+pub fn record_stage(stage: Stage) {
+    record_usage(stage_name(stage));
+}
+
+fn report(counters: &TelemetryCounters) -> serde_json::Value {
+    serde_json::json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "since": counters.since,
+        "until": state::now(),
+        "usage": counters.usage,
+        "errors": counters.errors
+    })
+}
+
+// Command to show exactly what would be submitted
+#[tauri::command]
+pub fn get_telemetry() -> Result<serde_json::Value, String> {
+    let settings = settings::load();
+    Ok(serde_json::json!({
+        "enabled": settings.telemetry_enabled,
+        "endpoint": settings.telemetry_endpoint,
+        "report": report(&state::load()?.telemetry)
+    }))
+}
+
+// Command to send the aggregate counters to the configured endpoint and reset them
+#[tauri::command]
+pub fn submit_telemetry() -> Result<String, String> {
+    let settings = settings::load();
+    if !settings.telemetry_enabled {
+        return Err("Telemetry is disabled".to_string());
+    }
+    let endpoint = settings
+        .telemetry_endpoint
+        .ok_or_else(|| "No telemetry endpoint configured".to_string())?;
+
+    let counters = state::load()?.telemetry;
+    if counters.usage.is_empty() && counters.errors.is_empty() {
+        return Ok("Nothing to submit".to_string());
+    }
+
+    let body = serde_json::to_string(&report(&counters))
+        .map_err(|e| format!("Failed to serialize telemetry: {}", e))?;
+
+    let output = Invocation::new("curl")
+        .args(["-fsS", "-m", "30", "-X", "POST", "-H", "Content-Type: application/json", "--data-binary", "@-", &endpoint])
+        .stdin(body)
+        .run()
+        .map_err(|e| format!("Failed to execute curl: {}", e))?;
+    if !output.success {
+        return Err(format!("Failed to send telemetry: {}", output.stderr.trim()));
+    }
+
+    state::update(|s| s.telemetry = TelemetryCounters::default())?;
+    Ok("Telemetry submitted".to_string())
+}
+
+// Command to discard the locally recorded counters
+#[tauri::command]
+pub fn clear_telemetry() -> Result<(), String> {
+    state::update(|s| s.telemetry = TelemetryCounters::default())
+}
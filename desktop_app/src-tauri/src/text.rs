@@ -0,0 +1,93 @@
+// Small text-analysis helpers shared by the dataset and transcript commands
+
+// Keyword lexicon used to tag transcripts with coarse Islamic-studies topics
+const TOPIC_KEYWORDS: &[(&str, &[&str])] = &[
+    ("fiqh", &["fiqh", "salah", "prayer", "wudu", "zakat", "fasting", "hajj", "halal", "haram", "ruling", "sunnah"]),
+    ("aqeedah", &["aqeedah", "tawheed", "tawhid", "iman", "belief", "shirk", "qadr", "akhirah", "jannah", "hellfire"]),
+    ("tafsir", &["tafsir", "tafseer", "ayah", "ayat", "surah", "verse", "quran", "revealed", "revelation"]),
+    ("seerah", &["seerah", "prophet", "companions", "sahaba", "makkah", "madinah", "hijrah", "battle"]),
+    ("tazkiyah", &["tazkiyah", "heart", "sincerity", "patience", "taqwa", "repentance", "dhikr", "gratitude"]),
+    ("family", &["marriage", "wife", "husband", "children", "parents", "family", "divorce"]),
+];
+
+// Minimum keyword hits before a topic is assigned
+const TOPIC_MIN_HITS: usize = 2;
+
+// Characters of context around each search match
+const SNIPPET_CONTEXT: usize = 80;
+
+// Extract topics from a text, most prominent first
+pub fn extract_topics(text: &str) -> Vec<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+
+    let mut scored: Vec<(&str, usize)> = TOPIC_KEYWORDS
+        .iter()
+        .map(|(topic, keywords)| {
+            let hits = words.iter().filter(|w| keywords.contains(&w.as_str())).count();
+            (*topic, hits)
+        })
+        .filter(|(_, hits)| *hits >= TOPIC_MIN_HITS)
+        .collect();
+
+    scored.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+    scored.into_iter().map(|(topic, _)| topic.to_string()).collect()
+}
+
+// Check whether a character belongs to the Arabic script blocks
+pub fn is_arabic(c: char) -> bool {
+    matches!(c, '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' | '\u{08A0}'..='\u{08FF}' | '\u{FB50}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}')
+}
+
+// Guess the dominant language of a text from its script ("ar" or "en")
+pub fn detect_language(text: &str) -> &'static str {
+    let arabic = text.chars().filter(|c| is_arabic(*c)).count();
+    let latin = text.chars().filter(|c| c.is_ascii_alphabetic()).count();
+
+    if arabic > latin {
+        "ar"
+    } else {
+        "en"
+    }
+}
+
+// Stable 64-bit FNV-1a fingerprint of some bytes, as hex
+pub fn fingerprint(bytes: impl IntoIterator<Item = u8>) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+// Up to three snippets around matches of `query`, which must be lowercase
+pub fn snippets(content: &str, query: &str) -> Vec<String> {
+    let lower = content.to_lowercase();
+    // Lowercasing changed byte offsets; fall back to the opening of the transcript
+    if lower.len() != content.len() {
+        return match lower.contains(query) {
+            true => vec![content.chars().take(2 * SNIPPET_CONTEXT).collect()],
+            false => Vec::new(),
+        };
+    }
+
+    lower
+        .match_indices(query)
+        .take(3)
+        .map(|(start, _)| {
+            let mut from = start.saturating_sub(SNIPPET_CONTEXT);
+            while !content.is_char_boundary(from) {
+                from -= 1;
+            }
+            let mut to = (start + query.len() + SNIPPET_CONTEXT).min(content.len());
+            while !content.is_char_boundary(to) {
+                to += 1;
+            }
+            content[from..to].split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .collect()
+}
@@ -0,0 +1,396 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::i18n;
+use crate::metadata;
+use crate::state;
+use crate::text;
+use crate::storage::storage;
+
+// A timed segment from the whisper JSON output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    #[serde(default)]
+    pub avg_logprob: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+}
+
+// Word-level timing, present after forced alignment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperOutput {
+    #[serde(default)]
+    segments: Vec<Segment>,
+}
+
+// Path of a transcript text file
+pub fn transcript_path(file_name: &str) -> PathBuf {
+    storage().path("output/transcripts").join(file_name)
+}
+
+// Path of the whisper JSON written alongside a transcript
+pub fn segments_path(file_name: &str) -> PathBuf {
+    storage()
+        .path("output/transcripts")
+        .join(format!("{}.json", metadata::stem(file_name)))
+}
+
+// Load the timed segments of a transcript, if whisper wrote them
+pub fn load_segments(file_name: &str) -> Option<Vec<Segment>> {
+    let content = storage().read_to_string(segments_path(file_name)).ok()?;
+    serde_json::from_str::<WhisperOutput>(&content)
+        .ok()
+        .map(|output| output.segments)
+}
+
+// Mean per-segment token probability, weighted by segment duration
+pub fn confidence(segments: &[Segment]) -> Option<f64> {
+    let (weighted, total) = segments
+        .iter()
+        .filter_map(|s| s.avg_logprob.map(|lp| (lp.exp(), (s.end - s.start).max(0.0))))
+        .fold((0.0, 0.0), |(w, t), (p, d)| (w + p * d, t + d));
+
+    if total > 0.0 {
+        Some(weighted / total)
+    } else {
+        None
+    }
+}
+
+// List transcript file names in the transcripts directory
+pub fn list_transcripts() -> Vec<String> {
+    let mut names: Vec<String> = storage().read_dir(storage().path("output/transcripts"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+// Number of characters shown in transcript listings
+const PREVIEW_CHARS: usize = 200;
+
+// Listing data for a transcript, cached in the state store by modification time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSummary {
+    pub modified: u64,
+    pub size: u64,
+    pub word_count: usize,
+    pub preview: String,
+    pub confidence: Option<f64>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+// File name, modification time (unix seconds) and size of a transcript
+#[derive(Debug, Clone)]
+pub struct TranscriptFile {
+    pub file_name: String,
+    pub modified: u64,
+    pub size: u64,
+}
+
+// Stat every transcript without reading its contents
+pub fn stat_transcripts() -> Vec<TranscriptFile> {
+    list_transcripts()
+        .into_iter()
+        .filter_map(|file_name| {
+            let meta = storage().metadata(transcript_path(&file_name)).ok()?;
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs());
+            Some(TranscriptFile { file_name, modified, size: meta.len() })
+        })
+        .collect()
+}
+
+// Truncate text to the preview length on a character boundary
+pub fn preview(content: &str) -> String {
+    match content.char_indices().nth(PREVIEW_CHARS) {
+        Some((index, _)) => format!("{}...", &content[..index]),
+        None => content.to_string(),
+    }
+}
+
+// Read a transcript and compute its listing data
+pub fn summarize(file: &TranscriptFile) -> Option<TranscriptSummary> {
+    let content = storage().read_to_string(transcript_path(&file.file_name)).ok()?;
+
+    Some(TranscriptSummary {
+        modified: file.modified,
+        size: file.size,
+        word_count: content.split_whitespace().count(),
+        preview: preview(&content),
+        confidence: load_segments(&file.file_name).and_then(|s| confidence(&s)),
+        language: Some(text::detect_language(&content).to_string()),
+    })
+}
+
+// Return the cached summary if still fresh, otherwise compute and cache it
+pub fn cached_summary(
+    cache: &mut BTreeMap<String, TranscriptSummary>,
+    file: &TranscriptFile,
+    dirty: &mut bool,
+) -> Option<TranscriptSummary> {
+    if let Some(summary) = cache.get(&file.file_name) {
+        if summary.modified == file.modified && summary.size == file.size && summary.language.is_some() {
+            return Some(summary.clone());
+        }
+    }
+
+    let summary = summarize(file)?;
+    cache.insert(file.file_name.clone(), summary.clone());
+    *dirty = true;
+    Some(summary)
+}
+
+// Review states a transcript moves through before it is used for the dataset
+pub const REVIEW_STATUSES: &[&str] = &["pending", "in_review", "approved", "rejected"];
+
+// Review status of a transcript, "pending" until someone sets it
+pub fn review_status(state: &state::PipelineState, file_name: &str) -> String {
+    state
+        .review_status
+        .get(file_name)
+        .cloned()
+        .unwrap_or_else(|| "pending".to_string())
+}
+
+// Server-side filters for the transcript listing; empty fields match everything
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TranscriptFilter {
+    // Glob over the file name, e.g. "*tafsir*"
+    pub name_pattern: Option<String>,
+    // Characters that must appear in order in the file name, case-insensitive
+    pub fuzzy: Option<String>,
+    pub content_regex: Option<String>,
+    pub min_words: Option<usize>,
+    pub max_words: Option<usize>,
+    pub review_status: Option<String>,
+    pub language: Option<String>,
+}
+
+// A filter with its patterns compiled once per listing
+pub struct TranscriptMatcher {
+    filter: TranscriptFilter,
+    name: Option<Regex>,
+    content: Option<Regex>,
+}
+
+// Translate a glob with `*` and `?` wildcards into an anchored regex
+fn glob_to_regex(pattern: &str) -> Result<Regex, String> {
+    let mut regex = String::from("(?i)^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).map_err(|e| format!("Invalid name pattern: {}", e))
+}
+
+fn fuzzy_matches(needle: &str, haystack: &str) -> bool {
+    let haystack = haystack.to_lowercase();
+    let mut chars = haystack.chars();
+    needle
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| chars.any(|h| h == c))
+}
+
+impl TranscriptFilter {
+    pub fn compile(self) -> Result<TranscriptMatcher, String> {
+        let name = self.name_pattern.as_deref().map(glob_to_regex).transpose()?;
+        let content = self
+            .content_regex
+            .as_deref()
+            .map(|p| Regex::new(p).map_err(|e| format!("Invalid content regex: {}", e)))
+            .transpose()?;
+
+        Ok(TranscriptMatcher { filter: self, name, content })
+    }
+}
+
+impl TranscriptMatcher {
+    // Check a transcript, cheapest criteria first so file contents are only read when needed
+    pub fn matches(
+        &self,
+        file: &TranscriptFile,
+        state: &state::PipelineState,
+        cache: &mut BTreeMap<String, TranscriptSummary>,
+        dirty: &mut bool,
+    ) -> bool {
+        if self.name.as_ref().is_some_and(|r| !r.is_match(&file.file_name)) {
+            return false;
+        }
+
+        if self.filter.fuzzy.as_deref().is_some_and(|f| !fuzzy_matches(f, &file.file_name)) {
+            return false;
+        }
+
+        if let Some(status) = &self.filter.review_status {
+            if review_status(state, &file.file_name) != *status {
+                return false;
+            }
+        }
+
+        if self.filter.min_words.is_some() || self.filter.max_words.is_some() || self.filter.language.is_some() {
+            let summary = match cached_summary(cache, file, dirty) {
+                Some(summary) => summary,
+                None => return false,
+            };
+
+            if self.filter.min_words.is_some_and(|min| summary.word_count < min)
+                || self.filter.max_words.is_some_and(|max| summary.word_count > max)
+            {
+                return false;
+            }
+
+            if self.filter.language.is_some() && summary.language != self.filter.language {
+                return false;
+            }
+        }
+
+        if let Some(content) = &self.content {
+            return storage().read_to_string(transcript_path(&file.file_name))
+                .is_ok_and(|text| content.is_match(&text));
+        }
+
+        true
+    }
+}
+
+// Command to set the review status of a transcript
+#[tauri::command]
+pub fn set_review_status(file_name: String, status: String) -> Result<(), String> {
+    if !REVIEW_STATUSES.contains(&status.as_str()) {
+        return Err(i18n::message("error-unknown-review-status", &[("status", &status)]));
+    }
+
+    if !storage().exists(transcript_path(&file_name)) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    state::update(|state| {
+        state.review_status.insert(file_name.clone(), status.clone());
+    })?;
+    audit::record("transcript.status", &file_name, serde_json::json!({ "status": status }));
+    Ok(())
+}
+
+// Format seconds as [hh:]mm:ss for document and listing output
+pub fn format_timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (hours, minutes, secs) = (total / 3600, (total % 3600) / 60, total % 60);
+
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+// Number of revision snapshots kept per transcript for conflict resolution
+const KEPT_REVISIONS: usize = 10;
+
+// Revision identifier of a transcript's content
+pub fn revision(content: &str) -> String {
+    text::fingerprint(content.bytes())
+}
+
+fn revisions_dir(file_name: &str) -> PathBuf {
+    storage()
+        .path("output/revisions")
+        .join(metadata::stem(file_name))
+}
+
+// Keep a copy of saved content so a later conflict has a merge base
+pub fn snapshot(file_name: &str, content: &str) -> String {
+    let revision = revision(content);
+    let dir = revisions_dir(file_name);
+    let path = dir.join(format!("{}.txt", revision));
+
+    if !storage().exists(&path) && storage().create_dir_all(&dir).is_ok() && storage().write(&path, content).is_ok() {
+        // Drop the oldest snapshots beyond the retention limit
+        if let Ok(entries) = storage().read_dir(&dir) {
+            let mut snapshots: Vec<(std::time::SystemTime, PathBuf)> = entries
+                .filter_map(Result::ok)
+                .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+                .collect();
+            snapshots.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+            for (_, old) in snapshots.into_iter().skip(KEPT_REVISIONS) {
+                storage().remove_file(old).ok();
+            }
+        }
+    }
+
+    revision
+}
+
+// Content of a previously snapshotted revision
+pub fn load_snapshot(file_name: &str, revision: &str) -> Option<String> {
+    storage().read_to_string(revisions_dir(file_name).join(format!("{}.txt", revision))).ok()
+}
+
+// Inputs for a three-way merge when a save races another edit
+#[derive(Debug, Serialize)]
+pub struct MergePayload {
+    // Content at the revision the client started from, if still available
+    pub base: Option<String>,
+    // Content the client tried to save
+    pub ours: String,
+    // Content currently on disk
+    pub theirs: String,
+    pub base_revision: String,
+    pub current_revision: String,
+}
+
+// Error returned by transcript saves, tagged with a code the UI can branch on
+#[derive(Debug, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum SaveError {
+    NotFound { message: String },
+    RevisionRequired { message: String },
+    Conflict { message: String, merge: Box<MergePayload> },
+    Io { message: String },
+}
+
+// Extensions of the files whisper writes for one transcript
+pub const OUTPUT_EXTENSIONS: &[&str] = &["txt", "json", "srt", "vtt", "tsv"];
+
+// Paths of a transcript and its whisper sidecars that currently exist
+pub fn output_files(file_name: &str) -> Vec<PathBuf> {
+    let stem = metadata::stem(file_name);
+    OUTPUT_EXTENSIONS
+        .iter()
+        .map(|ext| storage().path("output/transcripts").join(format!("{}.{}", stem, ext)))
+        .filter(|p| storage().exists(p))
+        .collect()
+}
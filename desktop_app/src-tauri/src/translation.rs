@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::clips;
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::scheduler::{self, Resource};
+use crate::transcript::{self, Segment};
+use crate::storage::storage;
+
+// Whisper can only translate into English
+const TARGET_LANGUAGE: &str = "en";
+
+fn translations_dir() -> PathBuf {
+    storage().path("output/translations")
+}
+
+// Whisper JSON of a transcript's English translation
+pub fn translation_segments_path(file_name: &str) -> PathBuf {
+    translations_dir().join(format!("{}.{}.json", metadata::stem(file_name), TARGET_LANGUAGE))
+}
+
+// Timed segments of the English translation, if translate_transcript has run
+pub fn load_translation(file_name: &str) -> Option<Vec<Segment>> {
+    let content = storage().read_to_string(translation_segments_path(file_name)).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    serde_json::from_value(value.get("segments")?.clone()).ok()
+}
+
+// An original segment with the translated text spoken over the same time range
+#[derive(Debug, Serialize)]
+pub struct SegmentPair {
+    pub start: f64,
+    pub end: f64,
+    pub original: String,
+    pub translation: String,
+}
+
+// Attach every translated segment to the original segment its midpoint falls in, or the nearest one
+fn align(original: &[Segment], translation: &[Segment]) -> Vec<SegmentPair> {
+    let mut pairs: Vec<SegmentPair> = original
+        .iter()
+        .map(|s| SegmentPair {
+            start: s.start,
+            end: s.end,
+            original: s.text.trim().to_string(),
+            translation: String::new(),
+        })
+        .collect();
+    if pairs.is_empty() {
+        return pairs;
+    }
+
+    for segment in translation {
+        let middle = (segment.start + segment.end) / 2.0;
+        let distance = |p: &SegmentPair| {
+            if middle < p.start {
+                p.start - middle
+            } else if middle > p.end {
+                middle - p.end
+            } else {
+                0.0
+            }
+        };
+        let nearest = (0..pairs.len())
+            .min_by(|a, b| distance(&pairs[*a]).total_cmp(&distance(&pairs[*b])))
+            .unwrap_or(0);
+
+        let pair = &mut pairs[nearest];
+        if !pair.translation.is_empty() {
+            pair.translation.push(' ');
+        }
+        pair.translation.push_str(segment.text.trim());
+    }
+    pairs
+}
+
+// Command to get a transcript and its English translation aligned segment by segment
+#[tauri::command]
+pub fn get_transcript_pair(file: String) -> Result<serde_json::Value, String> {
+    let path = transcript::transcript_path(&file);
+    if !storage().exists(&path) {
+        return Err(format!("Transcript not found: {}", file));
+    }
+
+    let original = transcript::load_segments(&file)
+        .ok_or_else(|| format!("No timed segments for transcript: {}", file))?;
+    let translation = load_translation(&file);
+
+    Ok(serde_json::json!({
+        "file_name": file,
+        "language": TARGET_LANGUAGE,
+        "translated": translation.is_some(),
+        "segments": align(&original, &translation.unwrap_or_default())
+    }))
+}
+
+// Command to translate a transcript's clip into English with whisper, into output/translations
+#[tauri::command]
+pub fn translate_transcript(file: String) -> Result<serde_json::Value, String> {
+    let clip = clips::media_arg(metadata::stem(&file), "mp3");
+    if !storage().exists(storage().path(&clip)) {
+        return Err(format!("Clip not found for transcript: {}", file));
+    }
+    storage().create_dir_all(translations_dir())
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    // whisper names its output after the clip, so it goes through a scratch directory
+    let scratch = translations_dir().join(format!(".{}", metadata::stem(&file)));
+    let _permit = scheduler::acquire(Resource::Gpu)?;
+    let output = Invocation::new("faster-whisper")
+        .arg(&clip)
+        .args(["--task", "translate", "--output_format", "json", "--output_dir"])
+        .arg(&scratch)
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute translation: {}", e))?;
+
+    let produced = scratch.join(format!("{}.json", metadata::stem(&file)));
+    let result = if !output.success {
+        Err(format!("Translation failed: {}", output.stderr.trim()))
+    } else {
+        storage().rename(&produced, translation_segments_path(&file))
+            .map_err(|e| format!("Failed to write translation: {}", e))
+    };
+    storage().remove_dir_all(&scratch).ok();
+    result?;
+
+    get_transcript_pair(file)
+}
@@ -0,0 +1,106 @@
+use crate::metadata;
+use crate::storage::storage;
+
+// Buckwalter: lossless one-to-one ASCII mapping
+const BUCKWALTER: &[(char, &str)] = &[
+    ('ء', "'"), ('آ', "|"), ('أ', ">"), ('ؤ', "&"), ('إ', "<"), ('ئ', "}"), ('ا', "A"),
+    ('ب', "b"), ('ة', "p"), ('ت', "t"), ('ث', "v"), ('ج', "j"), ('ح', "H"), ('خ', "x"),
+    ('د', "d"), ('ذ', "*"), ('ر', "r"), ('ز', "z"), ('س', "s"), ('ش', "$"), ('ص', "S"),
+    ('ض', "D"), ('ط', "T"), ('ظ', "Z"), ('ع', "E"), ('غ', "g"), ('ـ', "_"), ('ف', "f"),
+    ('ق', "q"), ('ك', "k"), ('ل', "l"), ('م', "m"), ('ن', "n"), ('ه', "h"), ('و', "w"),
+    ('ى', "Y"), ('ي', "y"), ('ً', "F"), ('ٌ', "N"), ('ٍ', "K"), ('َ', "a"), ('ُ', "u"),
+    ('ِ', "i"), ('ّ', "~"), ('ْ', "o"), ('ٰ', "`"), ('ٱ', "{"),
+];
+
+// ALA-LC romanization; short vowels only appear where the text is vocalized
+const ALA_LC: &[(char, &str)] = &[
+    ('ء', "ʼ"), ('آ', "ā"), ('أ', "ʼ"), ('ؤ', "ʼ"), ('إ', "ʼ"), ('ئ', "ʼ"), ('ا', "ā"),
+    ('ب', "b"), ('ة', "h"), ('ت', "t"), ('ث', "th"), ('ج', "j"), ('ح', "ḥ"), ('خ', "kh"),
+    ('د', "d"), ('ذ', "dh"), ('ر', "r"), ('ز', "z"), ('س', "s"), ('ش', "sh"), ('ص', "ṣ"),
+    ('ض', "ḍ"), ('ط', "ṭ"), ('ظ', "ẓ"), ('ع', "ʻ"), ('غ', "gh"), ('ـ', ""), ('ف', "f"),
+    ('ق', "q"), ('ك', "k"), ('ل', "l"), ('م', "m"), ('ن', "n"), ('ه', "h"), ('و', "w"),
+    ('ى', "á"), ('ي', "y"), ('ً', "an"), ('ٌ', "un"), ('ٍ', "in"), ('َ', "a"), ('ُ', "u"),
+    ('ِ', "i"), ('ْ', ""), ('ٰ', "ā"), ('ٱ', ""),
+];
+
+// Arabic punctuation and digits shared by both schemes
+fn common(c: char) -> Option<char> {
+    match c {
+        '،' => Some(','),
+        '؛' => Some(';'),
+        '؟' => Some('?'),
+        '٠'..='٩' => char::from_digit(c as u32 - '٠' as u32, 10),
+        _ => None,
+    }
+}
+
+// Harakat and other combining marks written above or below a letter
+pub fn is_diacritic(c: char) -> bool {
+    matches!(c, '\u{064B}'..='\u{0652}' | '\u{0670}')
+}
+
+// Transliterate Arabic script, leaving other characters untouched
+pub fn transliterate(text: &str, scheme: &str) -> Result<String, String> {
+    let table = match scheme {
+        "buckwalter" => BUCKWALTER,
+        "ala-lc" => ALA_LC,
+        _ => return Err(format!("Unknown transliteration scheme: {}", scheme)),
+    };
+
+    let mut result = String::with_capacity(text.len());
+    let mut last_consonant = String::new();
+    let mut consonant_end = 0;
+
+    for c in text.chars() {
+        // ALA-LC writes shadda by doubling the preceding consonant, which may
+        // already be followed by its vowel mark
+        if c == 'ّ' && scheme == "ala-lc" {
+            result.insert_str(consonant_end, &last_consonant);
+            continue;
+        }
+
+        if let Some((_, latin)) = table.iter().find(|(arabic, _)| *arabic == c) {
+            result.push_str(latin);
+            if !is_diacritic(c) {
+                last_consonant = latin.to_string();
+                consonant_end = result.len();
+            }
+        } else if let Some(mapped) = common(c) {
+            result.push(mapped);
+        } else {
+            result.push(c);
+        }
+    }
+
+    Ok(result)
+}
+
+// Command to write a Latin-script transliteration of a transcript as a sidecar
+#[tauri::command]
+pub fn transliterate_transcript(file_name: String, scheme: String) -> Result<serde_json::Value, String> {
+    let file_path = storage().path("output/transcripts").join(&file_name);
+
+    if !storage().exists(&file_path) {
+        return Err(format!("Transcript not found: {}", file_name));
+    }
+
+    let content = storage().read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+    let transliterated = transliterate(&content, &scheme)?;
+
+    let sidecar_dir = storage().path("output/transliterations");
+    storage().create_dir_all(&sidecar_dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let sidecar = sidecar_dir.join(format!("{}.{}.txt", metadata::stem(&file_name), scheme));
+    storage().write(&sidecar, &transliterated)
+        .map_err(|e| format!("Failed to write transliteration: {}", e))?;
+
+    Ok(serde_json::json!({
+        "file_name": file_name,
+        "scheme": scheme,
+        "path": sidecar.to_string_lossy(),
+        "content": transliterated
+    }))
+}
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+// Latest published release as reported by the updater endpoint
+#[derive(Debug, Serialize)]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub current_version: String,
+    pub latest_version: String,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+}
+
+// Unsigned updates cannot be verified, so the updater stays off until a public key is configured
+fn ensure_configured(app: &AppHandle) -> Result<(), String> {
+    let config = app.config();
+    let updater = &config.tauri.updater;
+    if !updater.active || updater.pubkey.trim().is_empty() {
+        return Err("Updates are not configured: set tauri.updater.pubkey and enable tauri.updater.active in tauri.conf.json".to_string());
+    }
+    Ok(())
+}
+
+async fn check(app: AppHandle) -> Result<tauri::updater::UpdateResponse<tauri::Wry>, String> {
+    ensure_configured(&app)?;
+    tauri::updater::builder(app)
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+fn info(update: &tauri::updater::UpdateResponse<tauri::Wry>) -> UpdateInfo {
+    UpdateInfo {
+        available: update.is_update_available(),
+        current_version: update.current_version().to_string(),
+        latest_version: update.latest_version().to_string(),
+        date: update.date().map(|d| d.to_string()),
+        notes: update.body().cloned(),
+    }
+}
+
+// Command to check the release endpoint for a newer version of the app
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateInfo, String> {
+    Ok(info(&check(app).await?))
+}
+
+// Command to get the release notes of the latest published version
+#[tauri::command]
+pub async fn get_release_notes(app: AppHandle) -> Result<serde_json::Value, String> {
+    let update = check(app).await?;
+    Ok(serde_json::json!({
+        "version": update.latest_version(),
+        "notes": update.body().cloned().unwrap_or_default()
+    }))
+}
+
+// Command to download and install the latest release, then restart the app.
+// The package signature is verified against the public key in tauri.conf.json before installing.
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<String, String> {
+    let update = check(app.clone()).await?;
+    if !update.is_update_available() {
+        return Ok(format!("Already up to date ({})", update.current_version()));
+    }
+
+    let version = update.latest_version().to_string();
+    update
+        .download_and_install()
+        .await
+        .map_err(|e| format!("Failed to install update {}: {}", version, e))?;
+
+    app.restart();
+    Ok(format!("Installed {}", version))
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::clips;
+use crate::metadata;
+use crate::pipeline::Invocation;
+use crate::scheduler::{self, Resource};
+use crate::storage::storage;
+
+// Container formats yt-dlp leaves behind when the original video is kept
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "webm", "mkv", "mov"];
+
+// Seconds between extracted frames when no interval is given
+const DEFAULT_INTERVAL: f64 = 10.0;
+
+// Kept original video of a clip, transcript or video file name
+pub fn video_path(file_name: &str) -> Option<PathBuf> {
+    let stem = metadata::stem(file_name);
+    VIDEO_EXTENSIONS
+        .iter()
+        .map(|ext| clips::media_file(stem, ext))
+        .find(|p| storage().is_file(p))
+}
+
+pub fn frames_dir(file_name: &str) -> PathBuf {
+    storage().path("output/frames").join(metadata::stem(file_name))
+}
+
+#[derive(Debug, Serialize)]
+pub struct Frame {
+    pub path: String,
+    // Position of the frame in the video, in seconds
+    pub timestamp: f64,
+}
+
+// Command to extract one frame every `interval` seconds of a kept video into output/frames/<stem>
+#[tauri::command]
+pub fn extract_frames(file: String, interval: Option<f64>) -> Result<Vec<Frame>, String> {
+    let interval = interval.unwrap_or(DEFAULT_INTERVAL);
+    if !interval.is_finite() || interval <= 0.0 {
+        return Err(format!("Invalid frame interval: {}", interval));
+    }
+
+    let video = video_path(&file)
+        .ok_or_else(|| format!("No kept video for {}; enable keep_video before downloading", file))?;
+
+    // Replace frames from an earlier extraction, which may have used another interval
+    let dir = frames_dir(&file);
+    if storage().exists(&dir) {
+        storage().remove_dir_all(&dir).map_err(|e| format!("Failed to clear frames: {}", e))?;
+    }
+    storage().create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let _permit = scheduler::acquire(Resource::Cpu)?;
+    let output = Invocation::new("ffmpeg")
+        .arg("-hide_banner")
+        .arg("-loglevel").arg("error")
+        .arg("-i").arg(&video)
+        .arg("-vf").arg(format!("fps=1/{}", interval))
+        .arg("-q:v").arg("2")
+        .arg(dir.join("frame_%05d.jpg"))
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !output.success {
+        return Err(format!("Frame extraction failed: {}", output.stderr.trim()));
+    }
+
+    let mut names: Vec<String> = storage().read_dir(&dir)
+        .map_err(|e| format!("Failed to read frames: {}", e))?
+        .filter_map(Result::ok)
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .filter(|name| name.ends_with(".jpg"))
+        .collect();
+    names.sort();
+
+    // ffmpeg's fps filter takes the frame in the middle of each interval, numbered from 1
+    Ok(names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| Frame {
+            path: dir.join(name).to_string_lossy().to_string(),
+            timestamp: (i as f64 + 0.5) * interval,
+        })
+        .collect())
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pipeline::Invocation;
+use crate::scheduler;
+use crate::settings;
+use crate::whisper;
+
+// Memory kept free for the CUDA context and fragmentation
+const HEADROOM_MB: u64 = 512;
+
+// How transcription adapts to the GPU, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GpuSettings {
+    // Pick compute type and parallelism from free VRAM before each batch
+    pub auto_size: bool,
+    // Fall back to a smaller model instead of failing when the chosen one does not fit
+    pub allow_smaller_model: bool,
+}
+
+impl Default for GpuSettings {
+    fn default() -> Self {
+        GpuSettings { auto_size: true, allow_smaller_model: false }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuInfo {
+    pub name: String,
+    pub total_mb: u64,
+    pub free_mb: u64,
+}
+
+// Approximate faster-whisper memory use per loaded model, in MB
+struct Footprint {
+    model: &'static str,
+    float16_mb: u64,
+    int8_mb: u64,
+}
+
+// Largest first, so the first smaller model that fits is the best fallback
+const FOOTPRINTS: &[Footprint] = &[
+    Footprint { model: "large-v3", float16_mb: 4700, int8_mb: 3100 },
+    Footprint { model: "large-v2", float16_mb: 4700, int8_mb: 3100 },
+    Footprint { model: "large-v1", float16_mb: 4700, int8_mb: 3100 },
+    Footprint { model: "large", float16_mb: 4700, int8_mb: 3100 },
+    Footprint { model: "distil-large-v3", float16_mb: 3000, int8_mb: 2000 },
+    Footprint { model: "distil-large-v2", float16_mb: 3000, int8_mb: 2000 },
+    Footprint { model: "medium", float16_mb: 2600, int8_mb: 1700 },
+    Footprint { model: "medium.en", float16_mb: 2600, int8_mb: 1700 },
+    Footprint { model: "distil-medium.en", float16_mb: 1800, int8_mb: 1200 },
+    Footprint { model: "small", float16_mb: 1400, int8_mb: 950 },
+    Footprint { model: "small.en", float16_mb: 1400, int8_mb: 950 },
+    Footprint { model: "distil-small.en", float16_mb: 1000, int8_mb: 700 },
+    Footprint { model: "base", float16_mb: 900, int8_mb: 650 },
+    Footprint { model: "base.en", float16_mb: 900, int8_mb: 650 },
+    Footprint { model: "tiny", float16_mb: 750, int8_mb: 550 },
+    Footprint { model: "tiny.en", float16_mb: 750, int8_mb: 550 },
+];
+
+// Model, compute type and parallelism chosen for a transcription batch
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuPlan {
+    pub model: String,
+    // Passed to faster-whisper; None lets it choose, e.g. on CPU
+    pub compute_type: Option<String>,
+    // Transcriptions that fit on the GPU at once
+    pub parallelism: usize,
+    pub gpu: Option<GpuInfo>,
+    pub note: Option<String>,
+    // Model that was asked for, when a smaller one replaces it
+    pub replaced: Option<String>,
+}
+
+impl GpuPlan {
+    fn unplanned(model: &str, gpu: Option<GpuInfo>, note: &str) -> Self {
+        GpuPlan { model: model.to_string(), compute_type: None, parallelism: 1, gpu, note: Some(note.to_string()), replaced: None }
+    }
+}
+
+// English-only checkpoints (".en" and the distil models) cannot replace multilingual ones, nor
+// the reverse
+fn english_only(model: &str) -> bool {
+    model.ends_with(".en") || model.starts_with("distil-")
+}
+
+// Free memory of the first NVIDIA GPU; None without a GPU or nvidia-smi
+pub fn detect() -> Option<GpuInfo> {
+    let output = Invocation::new("nvidia-smi")
+        .args(["--query-gpu=name,memory.total,memory.free", "--format=csv,noheader,nounits"])
+        .run()
+        .ok()?;
+    if !output.success {
+        return None;
+    }
+    let line = output.stdout.lines().next()?;
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    match fields.as_slice() {
+        [name, total, free] => Some(GpuInfo {
+            name: name.to_string(),
+            total_mb: total.parse().ok()?,
+            free_mb: free.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+// Choose how to run `model` on the available GPU, failing before any work starts if it cannot fit
+pub fn plan(model: &str) -> Result<GpuPlan, String> {
+    let config = settings::load().gpu;
+    if !config.auto_size {
+        return Ok(GpuPlan::unplanned(model, None, "Automatic sizing is disabled"));
+    }
+    // The resident worker already holds this model, so free memory no longer counts it
+    if let Some((loaded, compute_type)) = whisper::loaded().filter(|(loaded, _)| loaded == model) {
+        return Ok(GpuPlan {
+            model: loaded,
+            compute_type,
+            parallelism: 1,
+            gpu: detect(),
+            note: Some("Model is already loaded".to_string()),
+            replaced: None,
+        });
+    }
+    let Some(gpu) = detect() else {
+        return Ok(GpuPlan::unplanned(model, None, "No NVIDIA GPU detected; transcribing on CPU"));
+    };
+    let Some(footprint) = FOOTPRINTS.iter().find(|f| f.model == model) else {
+        return Ok(GpuPlan::unplanned(model, Some(gpu), "Unknown model size; not sized"));
+    };
+
+    let available = gpu.free_mb.saturating_sub(HEADROOM_MB);
+    let fit = |f: &Footprint| {
+        if f.float16_mb <= available {
+            Some(("float16", (available / f.float16_mb) as usize))
+        } else if f.int8_mb <= available {
+            Some(("int8_float16", (available / f.int8_mb) as usize))
+        } else {
+            None
+        }
+    };
+
+    let chosen = match fit(footprint) {
+        Some(fitted) => Some((footprint, fitted, None)),
+        None if config.allow_smaller_model => FOOTPRINTS
+            .iter()
+            .filter(|f| f.float16_mb < footprint.float16_mb && english_only(f.model) == english_only(model))
+            .find_map(|f| fit(f).map(|fitted| (f, fitted, Some(format!("{} does not fit in GPU memory; using {} instead", model, f.model))))),
+        None => None,
+    };
+
+    match chosen {
+        Some((footprint, (compute_type, parallelism), note)) => Ok(GpuPlan {
+            model: footprint.model.to_string(),
+            compute_type: Some(compute_type.to_string()),
+            parallelism: parallelism.max(1),
+            gpu: Some(gpu),
+            replaced: note.is_some().then(|| model.to_string()),
+            note,
+        }),
+        None => Err(format!(
+            "Model {} needs about {} MB of GPU memory ({} MB with int8) but only {} MB of {} MB is free on {} \
+             and no smaller model of the same language family fits; choose a smaller model or free GPU memory",
+            model, footprint.float16_mb, footprint.int8_mb, gpu.free_mb, gpu.total_mb, gpu.name
+        )),
+    }
+}
+
+// Plan a batch and cap concurrent GPU jobs to what fits
+pub fn prepare(model: &str) -> Result<GpuPlan, String> {
+    let plan = plan(model)?;
+    scheduler::set_gpu_limit(plan.parallelism);
+    Ok(plan)
+}
+
+// Command to preview how a model would run on this machine's GPU
+#[tauri::command]
+pub fn get_gpu_plan(model: Option<String>) -> Result<GpuPlan, String> {
+    plan(&model.unwrap_or_else(|| settings::load().whisper_model))
+}
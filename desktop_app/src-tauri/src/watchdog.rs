@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::events::{self, LogLevel, PipelineEvent, Stage};
+use crate::jobs;
+use crate::settings;
+use crate::state;
+use crate::whisper;
+
+// How often running jobs are checked for heartbeats
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// Stall detection for download and transcription jobs, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StallSettings {
+    pub enabled: bool,
+    // Minutes without output from the running tool before its job is flagged as stalled
+    pub minutes: u64,
+}
+
+impl Default for StallSettings {
+    fn default() -> Self {
+        StallSettings { enabled: true, minutes: 10 }
+    }
+}
+
+fn check(app: &AppHandle) {
+    let config = settings::load().stall_detection;
+    if !config.enabled {
+        return;
+    }
+
+    let now = state::now();
+    for job in [Stage::Download, Stage::Transcribe].into_iter().flat_map(jobs::running) {
+        let idle_seconds = now.saturating_sub(job.last_heartbeat);
+        if idle_seconds < config.minutes * 60 || !jobs::mark_stalled(&job.id) {
+            continue;
+        }
+
+        let item = job.current_item.clone().unwrap_or_else(|| "the current item".to_string());
+        let message = format!("No output from {} for {} minutes; continue or kill the job", item, idle_seconds / 60);
+        events::log(app, job.stage, LogLevel::Warn, &message);
+        events::emit(
+            app,
+            PipelineEvent::JobStalled { job_id: job.id, stage: job.stage, item: job.current_item, idle_seconds },
+        );
+    }
+}
+
+// Start the background thread that flags jobs whose tools stopped producing output
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        check(&app);
+    });
+}
+
+// Command to kill the tool a job is running and skip its remaining items
+#[tauri::command]
+pub fn kill_job(job_id: String) -> Result<(), String> {
+    if !jobs::request_kill(&job_id) {
+        return Err(format!("Job is not running: {}", job_id));
+    }
+    // One-shot tools are killed by their runner; the resident whisper worker needs a signal
+    whisper::kill_for_job(&job_id);
+    Ok(())
+}
+
+// Command to keep waiting on a stalled job; it is flagged again after another stall timeout
+#[tauri::command]
+pub fn continue_job(job_id: String) -> Result<(), String> {
+    match jobs::get(&job_id) {
+        Some(job) if job.status == jobs::JobStatus::Running => {
+            jobs::heartbeat(&job_id);
+            Ok(())
+        }
+        _ => Err(format!("Job is not running: {}", job_id)),
+    }
+}
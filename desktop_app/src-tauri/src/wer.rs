@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::runs;
+
+// Word-level edit counts of a hypothesis against a reference
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WordErrors {
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    pub reference_words: usize,
+}
+
+impl WordErrors {
+    fn errors(&self) -> usize {
+        self.substitutions + self.deletions + self.insertions
+    }
+
+    // Word error rate; 0 for an empty reference with an empty hypothesis
+    pub fn rate(&self) -> f64 {
+        if self.reference_words == 0 {
+            return if self.insertions == 0 { 0.0 } else { 1.0 };
+        }
+        self.errors() as f64 / self.reference_words as f64
+    }
+
+    pub fn add(&mut self, other: &WordErrors) {
+        self.substitutions += other.substitutions;
+        self.deletions += other.deletions;
+        self.insertions += other.insertions;
+        self.reference_words += other.reference_words;
+    }
+}
+
+// Minimum edit alignment between word sequences, kept to two rows of the Levenshtein table
+pub fn word_errors(reference: &[String], hypothesis: &[String]) -> WordErrors {
+    let deleted = |n: usize| WordErrors { deletions: n, ..Default::default() };
+    let mut previous: Vec<WordErrors> =
+        (0..=hypothesis.len()).map(|n| WordErrors { insertions: n, ..Default::default() }).collect();
+
+    for (i, word) in reference.iter().enumerate() {
+        let mut current = vec![deleted(i + 1)];
+        for (j, candidate) in hypothesis.iter().enumerate() {
+            let mut diagonal = previous[j];
+            if word != candidate {
+                diagonal.substitutions += 1;
+            }
+            let mut up = previous[j + 1];
+            up.deletions += 1;
+            let mut left = current[j];
+            left.insertions += 1;
+
+            let best = [diagonal, up, left].into_iter().min_by_key(WordErrors::errors).unwrap_or(diagonal);
+            current.push(best);
+        }
+        previous = current;
+    }
+
+    WordErrors { reference_words: reference.len(), ..previous[hypothesis.len()] }
+}
+
+// Normalized words of a transcript, as compared for WER
+pub fn words(text: &str) -> Vec<String> {
+    text.split_whitespace().map(runs::normalize).filter(|w| !w.is_empty()).collect()
+}
+
+// Command to compute WER of one run against a reference run, per file and over all shared files
+#[tauri::command]
+pub fn compute_wer(reference_run: String, hypothesis_run: String) -> Result<serde_json::Value, String> {
+    let reference = runs::parse_run(&reference_run)?;
+    let hypothesis = runs::parse_run(&hypothesis_run)?;
+    let hypothesis_files = runs::run_transcripts(hypothesis);
+
+    let mut files = Vec::new();
+    let mut missing = Vec::new();
+    let mut total = WordErrors::default();
+    for file in runs::run_transcripts(reference) {
+        if !hypothesis_files.contains(&file) {
+            missing.push(file);
+            continue;
+        }
+        let errors = word_errors(
+            &words(&runs::read_run_transcript(&file, reference)?),
+            &words(&runs::read_run_transcript(&file, hypothesis)?),
+        );
+        total.add(&errors);
+        files.push(serde_json::json!({ "file": file, "wer": errors.rate(), "errors": errors }));
+    }
+
+    Ok(serde_json::json!({
+        "reference_run": reference_run,
+        "hypothesis_run": hypothesis_run,
+        "files": files,
+        // Missing from the hypothesis run, left out of the aggregate
+        "missing": missing,
+        "wer": total.rate(),
+        "errors": total
+    }))
+}
@@ -0,0 +1,275 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::events::{self, LogLevel, Stage};
+use crate::joblogs;
+use crate::jobs;
+use crate::pipeline::{self, ToolOutput};
+use crate::portable;
+use crate::settings;
+use crate::state;
+use crate::vram::{self, GpuPlan};
+use crate::storage::storage;
+
+// Advanced decoding options; the defaults match faster-whisper's own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecodingSettings {
+    // First sampling temperature; 0 decodes greedily or with beam search
+    pub temperature: f64,
+    // Retry a segment this much hotter while it fails the thresholds below, up to 1.0; None never retries
+    pub temperature_increment: Option<f64>,
+    // Segments whose text compresses better than this are treated as repetition loops
+    pub compression_ratio_threshold: Option<f64>,
+    pub log_prob_threshold: Option<f64>,
+    pub no_speech_threshold: Option<f64>,
+    // Feeding the previous text as a prompt helps context but can lock in loops on long audio
+    pub condition_on_previous_text: bool,
+    pub beam_size: usize,
+}
+
+impl Default for DecodingSettings {
+    fn default() -> Self {
+        DecodingSettings {
+            temperature: 0.0,
+            temperature_increment: Some(0.2),
+            compression_ratio_threshold: Some(2.4),
+            log_prob_threshold: Some(-1.0),
+            no_speech_threshold: Some(0.6),
+            condition_on_previous_text: true,
+            beam_size: 5,
+        }
+    }
+}
+
+impl DecodingSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.temperature) {
+            return Err("Temperature must be between 0 and 1".to_string());
+        }
+        if self.temperature_increment.is_some_and(|i| !(i > 0.0 && i <= 1.0)) {
+            return Err("Temperature increment must be above 0 and at most 1".to_string());
+        }
+        if self.beam_size == 0 {
+            return Err("Beam size must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    // Arguments for the faster-whisper command line
+    pub fn cli_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--temperature".to_string(),
+            self.temperature.to_string(),
+            "--beam_size".to_string(),
+            self.beam_size.to_string(),
+            "--condition_on_previous_text".to_string(),
+            if self.condition_on_previous_text { "True" } else { "False" }.to_string(),
+        ];
+        let optional = [
+            ("--temperature_increment_on_fallback", self.temperature_increment),
+            ("--compression_ratio_threshold", self.compression_ratio_threshold),
+            ("--logprob_threshold", self.log_prob_threshold),
+            ("--no_speech_threshold", self.no_speech_threshold),
+        ];
+        for (flag, value) in optional {
+            args.push(flag.to_string());
+            args.push(value.map_or("None".to_string(), |v| v.to_string()));
+        }
+        args
+    }
+}
+
+// Resident transcription backend that keeps the whisper model loaded between clips
+struct Worker {
+    model: String,
+    compute_type: Option<String>,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+static WORKER: Mutex<Option<Worker>> = Mutex::new(None);
+
+// Job and process id of the worker while it transcribes for a job, so kill_job can reach it
+// without waiting for the worker lock
+static BUSY: Mutex<Option<(String, u32)>> = Mutex::new(None);
+
+impl Worker {
+    fn spawn(model: &str, compute_type: Option<&str>) -> Result<Worker, String> {
+        let mut child = Command::new("python3")
+            .args(["scripts/transcribe_worker.py", model, compute_type.unwrap_or("default")])
+            .current_dir(storage().root())
+            .envs(portable::tool_env())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start transcription worker: {}", e))?;
+
+        let stdin = child.stdin.take().ok_or("Failed to start transcription worker")?;
+        let stdout = child.stdout.take().ok_or("Failed to start transcription worker")?;
+        let mut worker = Worker {
+            model: model.to_string(),
+            compute_type: compute_type.map(str::to_string),
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        };
+
+        // The worker answers once the model is downloaded and loaded
+        let ready = worker.read_reply()?;
+        if let Some(error) = ready.get("error").and_then(|e| e.as_str()) {
+            worker.child.kill().ok();
+            return Err(error.to_string());
+        }
+        Ok(worker)
+    }
+
+    // Next reply, passing heartbeats written during long clips on to the current job
+    fn read_reply(&mut self) -> Result<serde_json::Value, String> {
+        loop {
+            let mut line = String::new();
+            let read = self
+                .stdout
+                .read_line(&mut line)
+                .map_err(|e| format!("Transcription worker failed: {}", e))?;
+            if read == 0 {
+                return Err("Transcription worker exited".to_string());
+            }
+            let reply: serde_json::Value =
+                serde_json::from_str(&line).map_err(|e| format!("Invalid reply from transcription worker: {}", e))?;
+            if reply.get("heartbeat").is_none() {
+                return Ok(reply);
+            }
+            if let Some(job) = jobs::current() {
+                jobs::heartbeat(&job);
+            }
+        }
+    }
+
+    fn transcribe(&mut self, audio: &str, output_dir: &str) -> Result<serde_json::Value, String> {
+        let request = serde_json::json!({
+            "audio": audio,
+            "output_dir": output_dir,
+            "decoding": settings::load().decoding
+        });
+        writeln!(self.stdin, "{}", request).map_err(|e| format!("Transcription worker failed: {}", e))?;
+        self.stdin.flush().map_err(|e| format!("Transcription worker failed: {}", e))?;
+
+        let job = jobs::current();
+        if let Some(job) = &job {
+            joblogs::append(job, &format!("--- {} $ transcribe_worker {}", state::now(), request));
+            *BUSY.lock().unwrap_or_else(|e| e.into_inner()) = Some((job.clone(), self.child.id()));
+        }
+        let reply = self.read_reply();
+        BUSY.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(job) = &job {
+            match &reply {
+                Ok(reply) => joblogs::append(job, &reply.to_string()),
+                Err(e) => joblogs::append(job, e),
+            }
+        }
+        reply
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+// Model and compute type of the resident worker, whose memory is already in use
+pub fn loaded() -> Option<(String, Option<String>)> {
+    let guard = WORKER.lock().unwrap_or_else(|e| e.into_inner());
+    guard.as_ref().map(|w| (w.model.clone(), w.compute_type.clone()))
+}
+
+// Transcribe an audio file, relative to the pipeline directory, with the resident worker. None when no worker running the planned model is
+// loaded or it died, in which case the caller falls back to a one-shot whisper process.
+pub fn transcribe(audio: &str, plan: &GpuPlan, output_dir: &str) -> Option<ToolOutput> {
+    let mut guard = WORKER.lock().unwrap_or_else(|e| e.into_inner());
+    let worker = guard.as_mut().filter(|w| w.model == plan.model && w.compute_type == plan.compute_type)?;
+
+    match worker.transcribe(audio, output_dir) {
+        Ok(reply) => {
+            let error = reply.get("error").and_then(|e| e.as_str()).map(str::to_string);
+            Some(ToolOutput {
+                success: error.is_none(),
+                stdout: reply.to_string(),
+                stderr: error.unwrap_or_default(),
+            })
+        }
+        Err(e) => {
+            *guard = None;
+            // A worker killed on request must not be replaced by a one-shot run of the same clip
+            let killed = jobs::current().is_some_and(|job| jobs::kill_requested(&job));
+            killed.then(|| ToolOutput { success: false, stdout: String::new(), stderr: e })
+        }
+    }
+}
+
+// Kill the resident worker if it is transcribing for `job`; the worker is started again by
+// warm_up_transcription
+pub fn kill_for_job(job: &str) -> bool {
+    let Some(pid) = BUSY.lock().unwrap_or_else(|e| e.into_inner()).as_ref().filter(|(j, _)| j == job).map(|(_, pid)| *pid)
+    else {
+        return false;
+    };
+
+    let killed = if cfg!(windows) {
+        Command::new("taskkill").args(["/PID", &pid.to_string(), "/F"]).output()
+    } else {
+        Command::new("kill").args(["-9", &pid.to_string()]).output()
+    };
+    killed.is_ok_and(|o| o.status.success())
+}
+
+// Command to download and load the whisper model in the background so transcription
+// starts without the per-file model load; defaults to the model in settings
+#[tauri::command]
+pub fn warm_up_transcription(app: AppHandle, model: Option<String>) -> Result<String, String> {
+    let model = model.unwrap_or_else(|| settings::load().whisper_model);
+
+    {
+        let guard = WORKER.lock().unwrap_or_else(|e| e.into_inner());
+        if guard.as_ref().is_some_and(|w| w.model == model) {
+            return Ok(format!("Model {} is already loaded", model));
+        }
+    }
+
+    // Mocked tools have no model to load
+    if pipeline::mocked() {
+        return Ok(format!("Model {} is already loaded", model));
+    }
+
+    // Fail now rather than when the model runs out of GPU memory while loading
+    let plan = vram::plan(&model)?;
+    let model = plan.model.clone();
+    let message = format!("Loading model {}", model);
+    std::thread::spawn(move || {
+        events::log(&app, Stage::Transcribe, LogLevel::Info, &format!("Loading model {}", model));
+        match Worker::spawn(&model, plan.compute_type.as_deref()) {
+            Ok(worker) => {
+                *WORKER.lock().unwrap_or_else(|e| e.into_inner()) = Some(worker);
+                events::log(&app, Stage::Transcribe, LogLevel::Info, &format!("Model {} ready", model));
+            }
+            Err(e) => events::error(&app, Stage::Transcribe, &e, None),
+        }
+    });
+
+    Ok(message)
+}
+
+// Command to stop the resident transcription worker and free its memory
+#[tauri::command]
+pub fn release_transcription_backend() -> Result<(), String> {
+    *WORKER.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    Ok(())
+}
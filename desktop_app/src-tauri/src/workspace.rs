@@ -0,0 +1,102 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::state;
+use crate::storage::storage;
+
+// Set when another instance holds the workspace lock; all writes are refused
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+// Contents of the lock file, identifying the instance that owns the workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    pub acquired_at: u64,
+}
+
+pub fn lock_path() -> PathBuf {
+    storage().path(".workspace.lock")
+}
+
+fn read_lock() -> Option<LockInfo> {
+    storage().read_to_string(lock_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn process_alive(pid: u32) -> bool {
+    if cfg!(windows) {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+    } else {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+}
+
+fn try_create() -> std::io::Result<()> {
+    let info = LockInfo { pid: std::process::id(), acquired_at: state::now() };
+    let mut file = storage().create_new(lock_path())?;
+    file.write_all(serde_json::to_string(&info).unwrap_or_default().as_bytes())
+}
+
+// Take the workspace lock, replacing it when its owner is no longer running.
+// Falls back to read-only mode when another live instance holds it.
+pub fn acquire() {
+    if try_create().is_ok() {
+        return;
+    }
+
+    match read_lock() {
+        Some(info) if info.pid == std::process::id() => {}
+        Some(info) if process_alive(info.pid) => READ_ONLY.store(true, Ordering::SeqCst),
+        _ => {
+            // Stale or unreadable lock left by a crashed instance
+            storage().remove_file(lock_path()).ok();
+            if try_create().is_err() {
+                READ_ONLY.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+// Remove the lock if this instance owns it
+pub fn release() {
+    if !is_read_only() && read_lock().is_some_and(|info| info.pid == std::process::id()) {
+        storage().remove_file(lock_path()).ok();
+    }
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+}
+
+// Error out of any write while another instance owns the workspace
+pub fn ensure_writable() -> Result<(), String> {
+    if !is_read_only() {
+        return Ok(());
+    }
+
+    let holder = read_lock().map_or("another instance".to_string(), |info| format!("process {}", info.pid));
+    let workspace = storage().root().display().to_string();
+    Err(i18n::message("error-read-only", &[("holder", &holder), ("workspace", &workspace)]))
+}
+
+// Command to report whether this instance owns the workspace or runs read-only
+#[tauri::command]
+pub fn get_workspace_lock() -> serde_json::Value {
+    serde_json::json!({
+        "read_only": is_read_only(),
+        "path": lock_path().to_string_lossy(),
+        "holder": read_lock()
+    })
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::events::{self, LogLevel, Stage};
+use crate::pipeline::Invocation;
+use crate::settings;
+
+// When the managed yt-dlp updates itself, stored in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct YtdlpUpdateSettings {
+    // Update before download batches of at least `min_batch_size` links
+    pub auto_update: bool,
+    pub min_batch_size: usize,
+    // Release to update to, e.g. "2024.08.06", "nightly" or "stable@2024.08.06"; None is the latest stable
+    pub version: Option<String>,
+}
+
+impl Default for YtdlpUpdateSettings {
+    fn default() -> Self {
+        YtdlpUpdateSettings { auto_update: false, min_batch_size: 20, version: None }
+    }
+}
+
+// Version reported by the installed yt-dlp
+pub fn version() -> Option<String> {
+    let output = Invocation::new("yt-dlp").arg("--version").in_workspace().run().ok()?;
+    output.success.then(|| output.stdout.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+// pip installs cannot update themselves; yt-dlp says so and exits
+fn needs_pip(output: &str) -> bool {
+    output.contains("pip")
+}
+
+// Update yt-dlp to `target` (latest stable when None) and return the version now installed
+pub fn update(target: Option<&str>) -> Result<String, String> {
+    let args = match target {
+        Some(target) => vec!["--update-to", target],
+        None => vec!["-U"],
+    };
+    let output = Invocation::new("yt-dlp")
+        .args(args)
+        .in_workspace()
+        .run()
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    let combined = format!("{}{}", output.stdout, output.stderr);
+    if !output.success || needs_pip(&combined) {
+        let requirement = match target {
+            // Only plain release versions translate to a pip pin
+            Some(version) if version.chars().all(|c| c.is_ascii_digit() || c == '.') => format!("yt-dlp=={}", version),
+            _ => "yt-dlp".to_string(),
+        };
+        let pip = Invocation::new("python3")
+            .args(["-m", "pip", "install", "--upgrade", &requirement])
+            .in_workspace()
+            .run()
+            .map_err(|e| format!("Failed to execute pip: {}", e))?;
+        if !pip.success {
+            return Err(format!("Failed to update yt-dlp: {}", pip.stderr.trim()));
+        }
+    }
+
+    version().ok_or_else(|| "yt-dlp is not available after the update".to_string())
+}
+
+// Update before a large batch when enabled; returns the version the batch runs with.
+// A failed update is logged and the batch continues with the installed version.
+pub fn prepare_batch(app: &AppHandle, batch_size: usize) -> Option<String> {
+    let config = settings::load().ytdlp_update;
+    if config.auto_update && batch_size >= config.min_batch_size {
+        events::log(app, Stage::Download, LogLevel::Info, "Updating yt-dlp before a large batch");
+        match update(config.version.as_deref()) {
+            Ok(version) => {
+                events::log(app, Stage::Download, LogLevel::Info, &format!("Using yt-dlp {}", version));
+                return Some(version);
+            }
+            Err(e) => events::log(app, Stage::Download, LogLevel::Warn, &e),
+        }
+    }
+    version()
+}
+
+// Command to update yt-dlp now, to the configured version unless one is given
+#[tauri::command]
+pub fn update_ytdlp(version: Option<String>) -> Result<String, String> {
+    let target = version.or(settings::load().ytdlp_update.version);
+    update(target.as_deref())
+}
@@ -0,0 +1,76 @@
+// Minimal ZIP writer storing entries uncompressed, enough for EPUB and similar containers
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+struct Entry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+#[derive(Default)]
+pub struct ZipWriter {
+    data: Vec<u8>,
+    entries: Vec<Entry>,
+}
+
+impl ZipWriter {
+    // Append a file; entries keep the order they are added in
+    pub fn add(&mut self, name: &str, content: &[u8]) -> Result<(), String> {
+        let size = u32::try_from(content.len()).map_err(|_| format!("{} is too large for a ZIP entry", name))?;
+        let offset = u32::try_from(self.data.len()).map_err(|_| "ZIP archive is too large".to_string())?;
+        let crc = crc32(content);
+
+        // Local file header: version 2.0, no flags, stored, dated 1980-01-01
+        self.data.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        self.data.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 33, 0]);
+        self.data.extend_from_slice(&crc.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.extend_from_slice(content);
+
+        self.entries.push(Entry { name: name.to_string(), crc, size, offset });
+        Ok(())
+    }
+
+    // The complete archive, with central directory
+    pub fn finish(mut self) -> Vec<u8> {
+        let directory_start = self.data.len() as u32;
+        for entry in &self.entries {
+            self.data.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            self.data.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 33, 0]);
+            self.data.extend_from_slice(&entry.crc.to_le_bytes());
+            self.data.extend_from_slice(&entry.size.to_le_bytes());
+            self.data.extend_from_slice(&entry.size.to_le_bytes());
+            self.data.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            // Extra and comment lengths, disk number, internal and external attributes
+            self.data.extend_from_slice(&[0; 12]);
+            self.data.extend_from_slice(&entry.offset.to_le_bytes());
+            self.data.extend_from_slice(entry.name.as_bytes());
+        }
+        let directory_size = self.data.len() as u32 - directory_start;
+
+        let count = (self.entries.len() as u16).to_le_bytes();
+        self.data.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        self.data.extend_from_slice(&[0, 0, 0, 0]);
+        self.data.extend_from_slice(&count);
+        self.data.extend_from_slice(&count);
+        self.data.extend_from_slice(&directory_size.to_le_bytes());
+        self.data.extend_from_slice(&directory_start.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+        self.data
+    }
+}
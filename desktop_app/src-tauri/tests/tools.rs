@@ -0,0 +1,87 @@
+// Command handlers against canned tool runs; needs `cargo test --features mock-tools`
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+
+use akhi_data_builder_desktop::pipeline::{self, Invocation, MockRunner, ToolOutput, ToolRunner};
+use akhi_data_builder_desktop::portable;
+
+// The runner and the workspace are process-wide, so tests take turns
+static SERIAL: Mutex<()> = Mutex::new(());
+static WORKSPACE: Once = Once::new();
+
+fn workspace() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("akhi-tools-test-{}", std::process::id()));
+    WORKSPACE.call_once(|| {
+        std::fs::create_dir_all(root.join("output/clips")).unwrap();
+        assert!(portable::set_pipeline_dir(root.clone()));
+    });
+    root
+}
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Records every invocation and writes the clip path yt-dlp would have printed
+struct Recorder {
+    root: PathBuf,
+    calls: Mutex<Vec<Invocation>>,
+}
+
+impl ToolRunner for Recorder {
+    fn run(&self, invocation: &Invocation) -> io::Result<ToolOutput> {
+        self.calls.lock().unwrap().push(invocation.clone());
+        let args: Vec<_> = invocation.args.iter().map(|a| a.to_string_lossy().to_string()).collect();
+        if let Some(at) = args.iter().position(|a| a == "after_move:filepath") {
+            std::fs::write(self.root.join(&args[at + 1]), "output/clips/Lecture 1.mp3\n")?;
+        }
+        Ok(ToolOutput { success: true, ..Default::default() })
+    }
+}
+
+#[test]
+fn download_runs_ytdlp_in_the_workspace() {
+    let _serial = serial();
+    let recorder = Arc::new(Recorder { root: workspace(), calls: Mutex::new(Vec::new()) });
+    pipeline::set_runner(recorder.clone());
+
+    let download = pipeline::download_link("https://example.org/watch?v=1").unwrap();
+    assert!(download.output.success);
+    assert_eq!(download.clips, vec!["Lecture 1.mp3".to_string()]);
+
+    let calls = recorder.calls.lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].program, "yt-dlp");
+    assert!(calls[0].in_workspace);
+    // The link comes last, after "--", so it can never be read as an option
+    let args = &calls[0].args;
+    assert_eq!(args[args.len() - 2], "--");
+    assert_eq!(args[args.len() - 1], "https://example.org/watch?v=1");
+    assert!(calls[0].args.iter().any(|a| a == "--extract-audio"));
+}
+
+#[test]
+fn download_reports_a_failed_tool() {
+    let _serial = serial();
+    workspace();
+    let failure = ToolOutput { success: false, stdout: String::new(), stderr: "ERROR: HTTP Error 429".to_string() };
+    pipeline::set_runner(Arc::new(MockRunner::new(BTreeMap::from([("yt-dlp".to_string(), failure)]))));
+
+    let download = pipeline::download_link("https://example.com/watch?v=2").unwrap();
+    assert!(!download.output.success);
+    assert!(download.output.stderr.contains("429"));
+    assert!(download.clips.is_empty());
+}
+
+#[test]
+fn mock_runner_succeeds_for_unknown_programs() {
+    let _serial = serial();
+    workspace();
+    pipeline::set_runner(Arc::new(MockRunner::new(BTreeMap::new())));
+
+    let output = Invocation::new("ffmpeg").arg("-version").run().unwrap();
+    assert!(output.success);
+    assert!(output.stdout.is_empty());
+}
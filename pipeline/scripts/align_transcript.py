@@ -0,0 +1,32 @@
+import json, sys
+
+# Forced alignment of a (possibly edited) transcript against its audio.
+# Usage: align_transcript.py <audio> <transcript.txt> <language> <output.json>
+# Requires: pip install whisperx
+import whisperx
+
+audio_path, text_path, language, out_path = sys.argv[1:5]
+
+audio = whisperx.load_audio(audio_path)
+duration = len(audio) / whisperx.audio.SAMPLE_RATE
+
+with open(text_path) as f:
+    text = f.read().strip()
+
+model, metadata = whisperx.load_align_model(language_code=language, device="cpu")
+result = whisperx.align([{"start": 0.0, "end": duration, "text": text}], model, metadata, audio, "cpu")
+
+segments = []
+for segment in result["segments"]:
+    segments.append({
+        "start": segment["start"],
+        "end": segment["end"],
+        "text": segment["text"],
+        "words": [
+            {"word": w["word"], "start": w["start"], "end": w["end"]}
+            for w in segment.get("words", []) if "start" in w and "end" in w
+        ]
+    })
+
+with open(out_path, "w") as o:
+    json.dump({"text": text, "segments": segments}, o, indent=2)
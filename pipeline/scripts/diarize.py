@@ -0,0 +1,14 @@
+import json, os, sys
+
+# Prints speaker turns of an audio file as a JSON array of {start, end, speaker}.
+# Requires: pip install pyannote.audio, and a Hugging Face token in HF_TOKEN
+from pyannote.audio import Pipeline
+
+pipeline = Pipeline.from_pretrained("pyannote/speaker-diarization-3.1", use_auth_token=os.environ.get("HF_TOKEN"))
+diarization = pipeline(sys.argv[1])
+
+turns = [
+    {"start": turn.start, "end": turn.end, "speaker": speaker}
+    for turn, _, speaker in diarization.itertracks(yield_label=True)
+]
+print(json.dumps(turns))
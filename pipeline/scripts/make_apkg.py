@@ -0,0 +1,37 @@
+import hashlib, json, sys
+
+# Builds an Anki package from flashcards read as a JSON array of {front, back, tags} on stdin.
+# Usage: make_apkg.py <deck name> <output.apkg>
+# Requires: pip install genanki
+import genanki
+
+
+def stable_id(text):
+    # Anki ids must stay the same across exports so re-imports update cards instead of duplicating them
+    return int(hashlib.sha1(text.encode("utf-8")).hexdigest()[:8], 16)
+
+
+deck_name, output = sys.argv[1], sys.argv[2]
+cards = json.load(sys.stdin)
+
+model = genanki.Model(
+    stable_id("akhi-pipeline question/answer"),
+    "Akhi Question/Answer",
+    fields=[{"name": "Question"}, {"name": "Answer"}],
+    templates=[{
+        "name": "Card 1",
+        "qfmt": "{{Question}}",
+        "afmt": "{{FrontSide}}<hr id=\"answer\">{{Answer}}",
+    }],
+)
+deck = genanki.Deck(stable_id(deck_name), deck_name)
+for card in cards:
+    deck.add_note(genanki.Note(
+        model=model,
+        fields=[card["front"], card["back"]],
+        tags=card.get("tags", []),
+        guid=genanki.guid_for(card["front"]),
+    ))
+
+genanki.Package(deck).write_to_file(output)
+print(json.dumps({"cards": len(cards), "output": output}))
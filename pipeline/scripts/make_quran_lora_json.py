@@ -1,16 +1,146 @@
-import os, json, sys
+import difflib, os, json, sys
+
+from punctuate import punctuate_text
+
+# Optional third argument: segments per entry; 0 emits one entry per transcript file and
+# "sections" one entry per topical section stored in output/sections
+mode = sys.argv[3] if len(sys.argv) > 3 else "0"
+segments_per_entry = 0 if mode == "sections" else int(mode)
+
+# Optional fourth argument: persona JSON with instruction, output and system_prompt
+persona = json.loads(sys.argv[4]) if len(sys.argv) > 4 else {}
+INSTRUCTION = persona.get("instruction", "Summarize and offer Islamic advice based on this:")
+OUTPUT = persona.get("output", "Remember, Allah is always with those who are patient and sincere.")
+SYSTEM = persona.get("system_prompt", "")
+
+# Optional fifth argument: "1" restores missing punctuation before text is chunked into entries
+PUNCTUATE = len(sys.argv) > 5 and sys.argv[5] == "1"
+
+
+def framed(entry):
+    if SYSTEM:
+        entry = {"system": SYSTEM, **entry}
+    return entry
+
+
+def clip_for(file):
+    stem = file[:-len(".txt")]
+    return stem + ".mp3"
+
+
+def reviewed_text(file, segments):
+    with open(os.path.join(sys.argv[1], file)) as f:
+        content = f.read().strip()
+    if PUNCTUATE:
+        # Compared against the raw segments, so reviewed edits are never overwritten
+        content = punctuate_text(content, segments)
+    return content
+
+
+def comparable(word):
+    return word.strip(".,;:!?\"'()[]«»،؛؟").lower()
+
+
+def load_segments(file):
+    # Whisper writes <stem>.json with timed segments next to each transcript. Their text is
+    # replaced by the reviewed transcript, aligned word by word, so edits reach every mode.
+    segments_path = os.path.join(sys.argv[1], file[:-len(".txt")] + ".json")
+    if not os.path.exists(segments_path):
+        return []
+    with open(segments_path) as f:
+        segments = json.load(f).get("segments", [])
+
+    raw, owner = [], []
+    for i, segment in enumerate(segments):
+        for word in segment.get("text", "").split():
+            raw.append(word)
+            owner.append(i)
+    reviewed = reviewed_text(file, segments).split()
+    if not raw:
+        return segments
+
+    words = [[] for _ in segments]
+    matcher = difflib.SequenceMatcher(None, [comparable(w) for w in raw], [comparable(w) for w in reviewed], autojunk=False)
+    for _, i1, i2, j1, j2 in matcher.get_opcodes():
+        if i1 == i2:
+            # Inserted words belong with the word before them
+            words[owner[max(i1 - 1, 0)]].extend(reviewed[j1:j2])
+            continue
+        # Matched and replaced words are spread over the raw words they stand for
+        for k in range(j1, j2):
+            words[owner[i1 + (k - j1) * (i2 - i1) // (j2 - j1)]].append(reviewed[k])
+    return [{**segment, "text": " ".join(w)} for segment, w in zip(segments, words)]
+
+
+def section_entries(file):
+    sections_path = os.path.join("output/sections", file[:-len(".txt")] + ".json")
+    if not os.path.exists(sections_path):
+        return []
+    with open(sections_path) as f:
+        sections = json.load(f)
+    segments = load_segments(file)
+
+    entries = []
+    for section in sections:
+        group = segments[section["first_segment"]:section["end_segment"]]
+        text = " ".join(s["text"].strip() for s in group if s.get("text", "").strip())
+        if text:
+            entries.append(framed({
+                "instruction": INSTRUCTION,
+                "input": text,
+                "output": OUTPUT,
+                "source": file,
+                "section": section["title"],
+                "clip": clip_for(file),
+                "start": section["start"],
+                "end": section["end"]
+            }))
+    return entries
+
+
+def segment_entries(file):
+    segments = [s for s in load_segments(file) if s.get("text", "").strip()]
+
+    entries = []
+    for i in range(0, len(segments), segments_per_entry):
+        group = segments[i:i + segments_per_entry]
+        entries.append(framed({
+            "instruction": INSTRUCTION,
+            "input": " ".join(s["text"].strip() for s in group),
+            "output": OUTPUT,
+            "source": file,
+            "clip": clip_for(file),
+            "start": group[0]["start"],
+            "end": group[-1]["end"]
+        }))
+    return entries
+
 
 results = []
-for file in os.listdir(sys.argv[1]):
+for file in sorted(os.listdir(sys.argv[1])):
     if file.endswith(".txt"):
-        with open(os.path.join(sys.argv[1], file)) as f:
-            content = f.read().strip()
-            if len(content.split()) > 50:
-                results.append({
-                    "instruction": "Summarize and offer Islamic advice based on this:",
-                    "input": content,
-                    "output": "Remember, Allah is always with those who are patient and sincere."
-                })
-
-with open("output/json/akhi_lora.json", "w") as o:
+        if mode == "sections":
+            results.extend(section_entries(file))
+            continue
+        if segments_per_entry > 0:
+            results.extend(segment_entries(file))
+            continue
+        segments_path = os.path.join(sys.argv[1], file[:-len(".txt")] + ".json")
+        segments = []
+        if os.path.exists(segments_path):
+            with open(segments_path) as s:
+                segments = json.load(s).get("segments", [])
+        content = reviewed_text(file, segments)
+        if len(content.split()) > 50:
+            results.append(framed({
+                "instruction": INSTRUCTION,
+                "input": content,
+                "output": OUTPUT,
+                "source": file
+            }))
+
+# Optional second argument: dataset output path
+out_path = sys.argv[2] if len(sys.argv) > 2 else "output/json/akhi_lora.json"
+
+with open(out_path, "w") as o:
     json.dump(results, o, indent=2)
@@ -0,0 +1,97 @@
+import re
+
+# Rule-based punctuation and sentence-boundary restoration for whisper segments.
+# Whisper's Arabic output often has no punctuation at all; segment timing still shows where
+# the speaker paused, which is where sentences and clauses usually end.
+
+# Pauses (seconds) before the next segment that end a sentence or a clause
+SENTENCE_PAUSE = 0.6
+CLAUSE_PAUSE = 0.25
+
+# Share of words followed by punctuation below which a text counts as unpunctuated
+MIN_PUNCTUATION_DENSITY = 0.02
+
+ARABIC = re.compile(r"[؀-ۿݐ-ݿࢠ-ࣿﭐ-﷿ﹰ-﻿]")
+TERMINAL = tuple(".!?؟…")
+CLAUSE = tuple(",،;؛:")
+
+INTERROGATIVES_EN = {"what", "why", "how", "when", "where", "who", "which", "is", "are", "can", "could",
+                     "should", "does", "do", "did", "would", "will"}
+INTERROGATIVES_AR = {"هل", "ما", "ماذا", "لماذا", "كيف", "متى", "أين", "من", "أي", "كم"}
+
+
+def is_arabic(text):
+    arabic = len(ARABIC.findall(text))
+    latin = sum(1 for c in text if c.isascii() and c.isalpha())
+    return arabic > latin
+
+
+def needs_restoration(text):
+    words = text.split()
+    if not words:
+        return False
+    marked = sum(1 for w in words if w.endswith(TERMINAL + CLAUSE))
+    return marked / len(words) < MIN_PUNCTUATION_DENSITY
+
+
+def sentence_end(sentence, arabic):
+    words = sentence.split()
+    first = words[0].lower().strip("\"'«(") if words else ""
+    question = first in (INTERROGATIVES_AR if arabic else INTERROGATIVES_EN)
+    if question:
+        return "؟" if arabic else "?"
+    return "."
+
+
+def capitalize(sentence):
+    return sentence[:1].upper() + sentence[1:] if sentence[:1].isascii() else sentence
+
+
+def punctuate_segments(segments):
+    """Copies of the segments with punctuation added at pauses, when the text lacks it."""
+    texts = [s.get("text", "").strip() for s in segments]
+    if not needs_restoration(" ".join(texts)):
+        return segments
+
+    arabic = is_arabic(" ".join(texts))
+    restored = []
+    sentence_start = True
+    sentence = []
+    for i, segment in enumerate(segments):
+        text = texts[i]
+        if not text:
+            restored.append(dict(segment))
+            continue
+        if sentence_start and not arabic:
+            text = capitalize(text)
+        sentence.append(text)
+
+        following = next((segments[j] for j in range(i + 1, len(segments)) if texts[j]), None)
+        pause = following["start"] - segment["end"] if following else SENTENCE_PAUSE
+        if text.endswith(TERMINAL):
+            sentence_start, sentence = True, []
+        elif pause >= SENTENCE_PAUSE or following is None:
+            text += sentence_end(" ".join(sentence), arabic)
+            sentence_start, sentence = True, []
+        elif pause >= CLAUSE_PAUSE and not text.endswith(CLAUSE):
+            text += "،" if arabic else ","
+            sentence_start = False
+        else:
+            sentence_start = False
+        restored.append({**segment, "text": text})
+    return restored
+
+
+def normalized(text):
+    return " ".join(re.sub(r"[^\w\s]", " ", text).lower().split())
+
+
+def punctuate_text(text, segments):
+    """The text rebuilt from punctuated segments, if it lacks punctuation and still matches
+    them word for word; edited transcripts are left as they are."""
+    if not segments or not needs_restoration(text):
+        return text
+    joined = " ".join(s.get("text", "").strip() for s in segments)
+    if normalized(joined) != normalized(text):
+        return text
+    return " ".join(s["text"] for s in punctuate_segments(segments) if s.get("text", "").strip())
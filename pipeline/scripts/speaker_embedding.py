@@ -0,0 +1,22 @@
+import json, sys
+
+# Prints a speaker embedding for an audio file as a JSON array.
+# Optional START:END arguments (seconds) embed only those spans, e.g. one diarized voice's turns.
+# Requires: pip install resemblyzer
+import librosa
+import numpy as np
+from resemblyzer import VoiceEncoder, preprocess_wav
+from resemblyzer.hparams import sampling_rate
+
+path, spans = sys.argv[1], sys.argv[2:]
+if spans:
+    audio, _ = librosa.load(path, sr=sampling_rate)
+    parts = []
+    for span in spans:
+        start, end = (float(x) for x in span.split(":"))
+        parts.append(audio[int(start * sampling_rate):int(end * sampling_rate)])
+    wav = preprocess_wav(np.concatenate(parts), source_sr=sampling_rate)
+else:
+    wav = preprocess_wav(path)
+embedding = VoiceEncoder().embed_utterance(wav)
+print(json.dumps([float(x) for x in embedding]))
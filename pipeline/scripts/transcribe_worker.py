@@ -0,0 +1,100 @@
+import json, os, sys, threading
+
+# Long-running transcription backend: loads the model once, then transcribes one clip per request.
+# Usage: transcribe_worker.py <model> [compute_type]
+# Protocol: one JSON object per line on stdin ({"audio": ..., "output_dir": ..., "decoding": {...}}),
+# one JSON reply per line on stdout. The first line written is {"ready": true} once the model is loaded.
+# While a clip is transcribing, {"heartbeat": true} is written every HEARTBEAT_SECONDS.
+# Requires: pip install faster-whisper
+from faster_whisper import WhisperModel
+
+HEARTBEAT_SECONDS = 30
+output_lock = threading.Lock()
+
+
+def reply(message):
+    with output_lock:
+        sys.stdout.write(json.dumps(message) + "\n")
+        sys.stdout.flush()
+
+
+def heartbeat(done):
+    while not done.wait(HEARTBEAT_SECONDS):
+        reply({"heartbeat": True})
+
+
+def timestamp(seconds, separator):
+    ms = int(round(seconds * 1000))
+    h, ms = divmod(ms, 3600000)
+    m, ms = divmod(ms, 60000)
+    s, ms = divmod(ms, 1000)
+    return f"{h:02d}:{m:02d}:{s:02d}{separator}{ms:03d}"
+
+
+def write_outputs(stem, output_dir, segments, language):
+    text = " ".join(s["text"].strip() for s in segments).strip()
+    base = os.path.join(output_dir, stem)
+
+    with open(base + ".txt", "w") as o:
+        o.write(text + "\n")
+    with open(base + ".json", "w") as o:
+        json.dump({"text": text, "language": language, "segments": segments}, o, indent=2)
+    with open(base + ".srt", "w") as o:
+        for i, s in enumerate(segments, 1):
+            o.write(f"{i}\n{timestamp(s['start'], ',')} --> {timestamp(s['end'], ',')}\n{s['text'].strip()}\n\n")
+    with open(base + ".vtt", "w") as o:
+        o.write("WEBVTT\n\n")
+        for s in segments:
+            o.write(f"{timestamp(s['start'], '.')} --> {timestamp(s['end'], '.')}\n{s['text'].strip()}\n\n")
+    with open(base + ".tsv", "w") as o:
+        o.write("start\tend\ttext\n")
+        for s in segments:
+            o.write(f"{int(s['start'] * 1000)}\t{int(s['end'] * 1000)}\t{s['text'].strip()}\n")
+
+
+try:
+    model = WhisperModel(
+        sys.argv[1] if len(sys.argv) > 1 else "small",
+        compute_type=sys.argv[2] if len(sys.argv) > 2 else "default",
+    )
+except Exception as e:
+    reply({"error": f"Failed to load model: {e}"})
+    sys.exit(1)
+
+reply({"ready": True})
+
+def decoding_options(decoding):
+    # Temperature schedule: start, then step up to 1.0 while a segment fails the thresholds
+    start = decoding.get("temperature", 0.0)
+    step = decoding.get("temperature_increment")
+    temperatures = [start]
+    while step and temperatures[-1] + step <= 1.0 + 1e-6:
+        temperatures.append(round(temperatures[-1] + step, 4))
+    return {
+        "temperature": temperatures,
+        "compression_ratio_threshold": decoding.get("compression_ratio_threshold", 2.4),
+        "log_prob_threshold": decoding.get("log_prob_threshold", -1.0),
+        "no_speech_threshold": decoding.get("no_speech_threshold", 0.6),
+        "condition_on_previous_text": decoding.get("condition_on_previous_text", True),
+        "beam_size": decoding.get("beam_size", 5),
+    }
+
+
+for line in sys.stdin:
+    done = threading.Event()
+    threading.Thread(target=heartbeat, args=(done,), daemon=True).start()
+    try:
+        request = json.loads(line)
+        audio = request["audio"]
+        result, info = model.transcribe(audio, **decoding_options(request.get("decoding") or {}))
+        segments = [
+            {"start": s.start, "end": s.end, "text": s.text, "avg_logprob": s.avg_logprob}
+            for s in result
+        ]
+        stem = os.path.splitext(os.path.basename(audio))[0]
+        write_outputs(stem, request["output_dir"], segments, info.language)
+        done.set()
+        reply({"ok": True, "language": info.language})
+    except Exception as e:
+        done.set()
+        reply({"error": str(e)})